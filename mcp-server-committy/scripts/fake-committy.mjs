@@ -11,6 +11,10 @@ function has(flag) {
   return process.argv.includes(flag);
 }
 
+function envelope(command, data, errors = []) {
+  return { command, ok: errors.length === 0, data, errors, warnings: [] };
+}
+
 async function main() {
   const args = process.argv.slice(2);
   // Expected forms:
@@ -21,7 +25,7 @@ async function main() {
 
   if (args.includes('lint-message')) {
     const msg = getArg('--message') || '';
-    const out = { command: 'lint-message', valid: true, issues: [], message: msg };
+    const out = envelope('lint-message', { count: 0, issues: [], message: msg });
     process.stdout.write(JSON.stringify(out));
     process.exit(0);
   }
@@ -40,7 +44,7 @@ async function main() {
         process.stdout.write('{ not-json: true ');
         process.exit(0);
       }
-      const out = { command: 'group-commit', mode: 'plan', ok: true, groups };
+      const out = envelope('group-commit', { mode: 'plan', groups, commits: [], pushed: null });
       process.stdout.write(JSON.stringify(out));
       process.exit(forcedPlanExit || 0);
     } else {
@@ -53,7 +57,7 @@ async function main() {
         { group: 'code', message: 'feat: add feature', ok: true, sha: 'def456' },
       ];
       const pushed = has('--push');
-      const out = { command: 'group-commit', mode: 'apply', ok: true, groups, commits, pushed };
+      const out = envelope('group-commit', { mode: 'apply', groups, commits, pushed });
       process.stdout.write(JSON.stringify(out));
       process.exit(forcedApplyExit || 0);
     }
@@ -62,12 +66,12 @@ async function main() {
   if (args.includes('tag')) {
     const source = getArg('--source') || '';
     if (has('--dry-run')) {
-      const out = { command: 'compute-next-tag', source, next_tag: 'v1.2.3' };
+      const out = envelope('tag', { previous_tag: 'v1.2.2', new_tag: 'v1.2.3', pre_release: false, dry_run: true, source });
       process.stdout.write(JSON.stringify(out));
       process.exit(0);
     }
     const name = getArg('--name') || 'v1.2.3';
-    const out = { command: 'apply-tag', source, name, pushed: true };
+    const out = envelope('tag', { previous_tag: 'v1.2.2', new_tag: name, pre_release: false, dry_run: false, source });
     process.stdout.write(JSON.stringify(out));
     process.exit(0);
   }
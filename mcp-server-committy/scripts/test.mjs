@@ -31,12 +31,12 @@ async function testNonZeroExitWithJson() {
     process.env.FAKE_GC_EXIT_PLAN = '3';
     const plan = await groupCommitPlan(tmpRepo, {});
     assert.equal(plan.ok, false, 'plan ok should be false when exit code is non-zero');
-    assert.ok(plan.result && plan.result.mode === 'plan', 'plan should still parse JSON result');
+    assert.ok(plan.result && plan.result.data.mode === 'plan', 'plan should still parse JSON result');
 
     process.env.FAKE_GC_EXIT_APPLY = '2';
     const apply = await groupCommitApply(tmpRepo, {});
     assert.equal(apply.ok, false, 'apply ok should be false when exit code is non-zero');
-    assert.ok(apply.result && apply.result.mode === 'apply', 'apply should still parse JSON result');
+    assert.ok(apply.result && apply.result.data.mode === 'apply', 'apply should still parse JSON result');
   } finally {
     if (oldBin === undefined) delete process.env.COMMITTY_BIN; else process.env.COMMITTY_BIN = oldBin;
     if (oldScript === undefined) delete process.env.COMMITTY_SCRIPT; else process.env.COMMITTY_SCRIPT = oldScript;
@@ -85,14 +85,14 @@ async function testFakeCliGroupCommitPlanApply() {
     const plan = await groupCommitPlan(tmpRepo, { includeUnstaged: true, ai: false });
     assert.equal(plan.ok, true, 'groupCommitPlan should succeed with fake CLI');
     assert.ok(plan.result && plan.result.command === 'group-commit', 'plan result has command');
-    assert.equal(plan.result.mode, 'plan', 'plan mode is plan');
-    assert.ok(Array.isArray(plan.result.groups) && plan.result.groups.length >= 1, 'plan has groups');
+    assert.equal(plan.result.data.mode, 'plan', 'plan mode is plan');
+    assert.ok(Array.isArray(plan.result.data.groups) && plan.result.data.groups.length >= 1, 'plan has groups');
 
     const apply = await groupCommitApply(tmpRepo, { autoStage: true, push: true });
     assert.equal(apply.ok, true, 'groupCommitApply should succeed with fake CLI');
-    assert.equal(apply.result?.mode, 'apply', 'apply mode is apply');
-    assert.ok(Array.isArray(apply.result?.commits) && apply.result.commits.length >= 1, 'apply has commits');
-    assert.equal(apply.result?.pushed, true, 'apply pushed should be true when --push is passed');
+    assert.equal(apply.result?.data.mode, 'apply', 'apply mode is apply');
+    assert.ok(Array.isArray(apply.result?.data.commits) && apply.result.data.commits.length >= 1, 'apply has commits');
+    assert.equal(apply.result?.data.pushed, true, 'apply pushed should be true when --push is passed');
   } finally {
     if (oldBin === undefined) delete process.env.COMMITTY_BIN; else process.env.COMMITTY_BIN = oldBin;
     if (oldScript === undefined) delete process.env.COMMITTY_SCRIPT; else process.env.COMMITTY_SCRIPT = oldScript;
@@ -110,11 +110,11 @@ async function testFakeCliComputeAndApplyTag() {
 
     const comp = await computeNextTag({ source: '/tmp/repo', fetch: false });
     assert.equal(comp.ok, true, 'computeNextTag should succeed with fake CLI');
-    assert.ok(comp.result && comp.result.next_tag === 'v1.2.3', 'computeNextTag returns next_tag');
+    assert.ok(comp.result && comp.result.data.new_tag === 'v1.2.3', 'computeNextTag returns new_tag');
 
     const appl = await applyTag({ source: '/tmp/repo', name: 'v1.2.3' });
     assert.equal(appl.ok, true, 'applyTag should succeed with fake CLI');
-    assert.ok(appl.result && appl.result.name === 'v1.2.3', 'applyTag returns name');
+    assert.ok(appl.result && appl.result.data.new_tag === 'v1.2.3', 'applyTag returns new_tag');
   } finally {
     if (oldBin === undefined) delete process.env.COMMITTY_BIN; else process.env.COMMITTY_BIN = oldBin;
     if (oldScript === undefined) delete process.env.COMMITTY_SCRIPT; else process.env.COMMITTY_SCRIPT = oldScript;
@@ -222,24 +222,39 @@ export interface CommitRecord {
   error?: string;
 }
 
-export interface GroupCommitPlanResult {
-  command: "group-commit";
-  mode: "plan";
+/** A structured error of the Rust CLI; `code` matches its exit code. */
+export interface CommittyError {
+  code: string;
+  message: string;
+  hint: string | null;
+}
+
+/** Every `--output json` payload of the Rust CLI is wrapped in this envelope. */
+export interface Envelope<T> {
+  command: string;
   ok: boolean;
+  data: T | null;
+  errors: CommittyError[];
+  warnings: string[];
+}
+
+export interface GroupCommitPlanData {
+  mode: "plan";
   groups: PlanGroup[];
-  errors?: string[];
+  commits: CommitRecord[];
+  pushed: boolean | null;
 }
 
-export interface GroupCommitApplyResult {
-  command: "group-commit";
+export interface GroupCommitApplyData {
   mode: "apply";
-  ok: boolean;
   groups: PlanGroup[];
   commits: CommitRecord[];
-  pushed?: boolean;
-  errors?: string[];
+  pushed: boolean | null;
 }
 
+export type GroupCommitPlanResult = Envelope<GroupCommitPlanData>;
+export type GroupCommitApplyResult = Envelope<GroupCommitApplyData>;
+
 export interface GroupCommitCommonOptions {
   includeUnstaged?: boolean;
   // AI options (must match Rust flags)
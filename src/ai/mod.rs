@@ -1,3 +1,5 @@
+pub mod redact;
+
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use thiserror::Error;
@@ -12,6 +14,8 @@ pub enum LlmError {
     _Timeout,
     #[error("AI response parse error: {0}")]
     Parse(String),
+    #[error("Unknown AI provider: {0}")]
+    UnknownProvider(String),
 }
 
 #[async_trait::async_trait]
@@ -237,5 +241,6 @@ pub struct AiCommitSuggestion {
     pub short: Option<String>,
     pub scope: Option<String>,
     pub long: Option<String>,
+    pub breaking: Option<bool>,
     pub message: Option<String>,
 }
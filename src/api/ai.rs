@@ -0,0 +1,454 @@
+use super::commit::{plan_commit, CommitRequest};
+use super::groups::classify_file;
+use crate::ai::redact::{redact, RedactionReport, DEFAULT_DENY_PATHS};
+use crate::ai::{AiCommitSuggestion, LlmClient, LlmError, OllamaClient, OpenRouterClient};
+use crate::config::commit_types::CommitTypeRegistry;
+use crate::config::RepoConfig;
+use crate::error::CliError;
+use crate::git::{self, DiffBudget, DiffSource, FileDiff};
+use crate::linter::{header_layout, locate_message_issues};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Settings for asking an AI provider to suggest commit messages.
+#[derive(Debug, Clone)]
+pub struct AiOptions {
+    /// `openrouter` or `ollama`
+    pub provider: String,
+    pub model: Option<String>,
+    /// Environment variable holding the OpenRouter API key
+    pub api_key_env: String,
+    pub base_url: Option<String>,
+    pub max_tokens: u32,
+    pub temperature: f32,
+    pub timeout_ms: u64,
+    pub json_mode: bool,
+    pub system_prompt: Option<String>,
+    pub system_prompt_file: Option<String>,
+    /// Maximum number of file names sent per prompt
+    pub file_limit: usize,
+    /// Send file names and diffs to the provider
+    pub allow_sensitive: bool,
+    /// Lines of each file's diff sent per prompt
+    pub diff_lines_per_file: usize,
+    /// Estimated tokens of all the diffs sent per prompt
+    pub diff_token_budget: usize,
+}
+
+impl Default for AiOptions {
+    fn default() -> Self {
+        AiOptions {
+            provider: "openrouter".into(),
+            model: None,
+            api_key_env: "OPENROUTER_API_KEY".into(),
+            base_url: None,
+            max_tokens: 256,
+            temperature: 0.2,
+            timeout_ms: 20000,
+            json_mode: true,
+            system_prompt: None,
+            system_prompt_file: None,
+            file_limit: 20,
+            allow_sensitive: false,
+            diff_lines_per_file: 80,
+            diff_token_budget: 3000,
+        }
+    }
+}
+
+impl AiOptions {
+    pub(super) fn diff_budget(&self) -> DiffBudget {
+        DiffBudget {
+            lines_per_file: self.diff_lines_per_file,
+            max_tokens: self.diff_token_budget,
+        }
+    }
+
+    /// The system prompt from `system_prompt_file`, else `system_prompt`, else `default`.
+    /// An unreadable prompt file is an error rather than a silent fallback.
+    pub(super) fn system_prompt(
+        &self,
+        default: impl FnOnce() -> String,
+    ) -> Result<String, CliError> {
+        match (&self.system_prompt_file, &self.system_prompt) {
+            (Some(path), _) => fs::read_to_string(path).map_err(|e| {
+                CliError::ConfigError(format!("Cannot read the AI system prompt file {path}: {e}"))
+            }),
+            (None, Some(prompt)) => Ok(prompt.clone()),
+            (None, None) => Ok(default()),
+        }
+    }
+}
+
+/// A commit message suggested for the staged changes, as returned by [`suggest_commit`].
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CommitSuggestion {
+    pub commit_type: String,
+    pub scope: String,
+    pub short_message: String,
+    pub long_message: String,
+    pub breaking_change: bool,
+    /// The suggested message, formatted
+    pub message: String,
+    /// What the linter found wrong with the message; empty when it can be committed as is
+    pub issues: Vec<String>,
+    /// What was kept from the provider
+    pub redactions: RedactionReport,
+}
+
+/// The registered commit types, listed for the AI system prompt.
+pub(super) fn allowed_types_prompt(types: &CommitTypeRegistry) -> String {
+    let lines: Vec<String> = types
+        .visible()
+        .iter()
+        .map(|t| format!("- {}: {}", t.name, t.description))
+        .collect();
+    format!("Allowed commit types:\n{}", lines.join("\n"))
+}
+
+/// The path denylist of the repository at `repo_path`: the built-in patterns and the
+/// `deny-paths` of its `.committy.toml`.
+pub(super) fn deny_paths(repo_path: &Path) -> Result<Vec<String>, CliError> {
+    let repo_config = RepoConfig::load_for(repo_path).map_err(CliError::ConfigError)?;
+    Ok(DEFAULT_DENY_PATHS
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .chain(repo_config.ai.deny_paths)
+        .collect())
+}
+
+/// Whether `path` matches one of the `deny_paths` and must not be sent.
+pub(super) fn is_denied(path: &str, deny_paths: &[String]) -> bool {
+    deny_paths
+        .iter()
+        .any(|pattern| git::path_matches(pattern, path))
+}
+
+/// Ask the provider configured by `ai` to answer `user_prompt`. Secrets are masked from
+/// both prompts first and counted in `report`.
+pub(super) fn complete(
+    ai: &AiOptions,
+    system_prompt: &str,
+    user_prompt: &str,
+    report: &mut RedactionReport,
+) -> Result<String, LlmError> {
+    let system_prompt = redact(system_prompt, report);
+    let user_prompt = redact(user_prompt, report);
+    let client: Box<dyn LlmClient> = match ai.provider.as_str() {
+        "openrouter" => Box::new(OpenRouterClient {
+            base_url: ai
+                .base_url
+                .clone()
+                .unwrap_or_else(|| "https://openrouter.ai/api/v1".to_string()),
+            api_key: env::var(&ai.api_key_env).ok(),
+            model: ai
+                .model
+                .clone()
+                .unwrap_or_else(|| "openrouter/auto".to_string()),
+        }),
+        "ollama" => Box::new(OllamaClient {
+            base_url: ai
+                .base_url
+                .clone()
+                .unwrap_or_else(|| "http://localhost:11434".to_string()),
+            model: ai.model.clone().unwrap_or_else(|| "llama3.2".to_string()),
+        }),
+        other => return Err(LlmError::UnknownProvider(other.to_string())),
+    };
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| LlmError::RequestFailed(e.to_string()))?;
+    runtime.block_on(client.suggest_commit(
+        &system_prompt,
+        &user_prompt,
+        ai.json_mode,
+        ai.max_tokens,
+        ai.temperature,
+        ai.timeout_ms,
+    ))
+}
+
+/// Ask an AI provider for a commit message describing the changes staged in the
+/// repository at `repo_path`. The suggestion is validated like [`plan_commit`] and
+/// linted; what is wrong with it is reported in [`CommitSuggestion::issues`] so that it
+/// can still be edited. Files matching the path denylist are left out of the prompt.
+pub fn suggest_commit(repo_path: &Path, ai: &AiOptions) -> Result<CommitSuggestion, CliError> {
+    let repo = git::open_repository(repo_path)?;
+    let deny_paths = deny_paths(repo_path)?;
+    let mut report = RedactionReport::default();
+    let staged = git::changed_files(&repo, false)?;
+    if staged.is_empty() {
+        return Err(CliError::NoStagedChanges);
+    }
+    // Denied files are dropped before the diffs are read, so they spend none of the budget
+    let mut allowed = Vec::new();
+    for path in staged {
+        if is_denied(&path, &deny_paths) {
+            report.withhold(&path);
+        } else {
+            allowed.push(path);
+        }
+    }
+    if allowed.is_empty() {
+        return Err(CliError::AiError(
+            "All the staged files match the AI path denylist, nothing can be sent".to_string(),
+        ));
+    }
+    let files = git::collect_diffs(&repo, DiffSource::Index, Some(&allowed), ai.diff_budget())?;
+
+    let types = CommitTypeRegistry::load_for(repo_path);
+    let system_prompt = ai.system_prompt(|| format!(
+        "You are a commit message assistant. Write one conventional commit message for the staged changes.\nReturn a JSON object {{\"commit_type\", \"scope\", \"short\", \"long\", \"breaking\"}}: 'short' is the description that follows '<type>(<scope>): ' in the first line, which must stay under 72 characters; 'scope' is optional; 'long' explains why the change was made.\n{}",
+        allowed_types_prompt(&types)
+    ))?;
+
+    let text = complete(ai, &system_prompt, &diff_prompt(&files, ai), &mut report)?;
+    let request = parse_suggestion(&text, ai.json_mode)?;
+    Ok(validate(repo_path, &types, request, report))
+}
+
+/// List `files` with their diffs, as far as the budget allowed, for a prompt that may
+/// contain sensitive content.
+pub(super) fn diff_section(files: &[FileDiff], file_limit: usize) -> String {
+    let mut section = String::from("Files:\n");
+    for file in files.iter().take(file_limit) {
+        let omission = file
+            .omitted
+            .map(|omission| format!(", {}, diff not shown", omission.as_str()))
+            .unwrap_or_default();
+        section.push_str(&format!(
+            "- {} (+{} -{}{omission})\n",
+            file.path, file.insertions, file.deletions
+        ));
+    }
+    if files.len() > file_limit {
+        section.push_str("- ...\n");
+    }
+    let patches: String = files
+        .iter()
+        .take(file_limit)
+        .map(|f| f.patch.as_str())
+        .collect();
+    if !patches.is_empty() {
+        section.push_str("\nDiff:\n");
+        section.push_str(&patches);
+    }
+    section
+}
+
+/// Describe the staged changes: names and diffs only when sensitive content is allowed.
+fn diff_prompt(files: &[FileDiff], ai: &AiOptions) -> String {
+    let insertions: usize = files.iter().map(|f| f.insertions).sum();
+    let deletions: usize = files.iter().map(|f| f.deletions).sum();
+    let mut prompt = format!(
+        "Staged changes: {} file(s), +{insertions} -{deletions}\n",
+        files.len()
+    );
+
+    if ai.allow_sensitive {
+        prompt.push_str(&diff_section(files, ai.file_limit));
+    } else {
+        let mut kinds: BTreeMap<&str, usize> = BTreeMap::new();
+        for file in files {
+            *kinds.entry(classify_file(&file.path).as_str()).or_default() += 1;
+        }
+        let kinds: Vec<String> = kinds
+            .iter()
+            .map(|(kind, count)| format!("{kind} ({count})"))
+            .collect();
+        prompt.push_str(&format!(
+            "Kinds of files changed: {}\nFile names and contents are withheld; suggest a message from this summary.\n",
+            kinds.join(", ")
+        ));
+    }
+
+    if ai.json_mode {
+        prompt.push_str(
+            "Return a JSON object with fields: commit_type, scope, short, long, breaking.",
+        );
+    } else {
+        prompt.push_str("Return only the commit message.");
+    }
+    prompt
+}
+
+/// The parts of the commit the provider suggested in `text`.
+fn parse_suggestion(text: &str, json_mode: bool) -> Result<CommitRequest, CliError> {
+    // Models like to wrap their answer in a code fence
+    let text = text.trim();
+    let text = text
+        .strip_prefix("```json")
+        .or_else(|| text.strip_prefix("```"))
+        .and_then(|t| t.strip_suffix("```"))
+        .unwrap_or(text)
+        .trim();
+    if !json_mode {
+        return Ok(parse_message(text));
+    }
+
+    let suggestion: AiCommitSuggestion =
+        serde_json::from_str(text).map_err(|e| LlmError::Parse(e.to_string()))?;
+    if let Some(message) = &suggestion.message {
+        return Ok(parse_message(message));
+    }
+    Ok(CommitRequest {
+        commit_type: suggestion.commit_type.unwrap_or_default(),
+        scope: suggestion.scope.unwrap_or_default(),
+        short_message: suggestion.short.unwrap_or_default(),
+        long_message: suggestion.long.unwrap_or_default(),
+        breaking_change: suggestion.breaking.unwrap_or(false),
+        amend: false,
+    })
+}
+
+/// Split a full commit message into its parts.
+fn parse_message(message: &str) -> CommitRequest {
+    let mut lines = message.trim().lines();
+    let header = lines.next().unwrap_or_default().trim();
+    let long_message = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+
+    let layout = header_layout(header);
+    let chars: Vec<char> = header.chars().collect();
+    let Some(separator) = layout.separator else {
+        return CommitRequest {
+            short_message: header.to_string(),
+            long_message,
+            ..Default::default()
+        };
+    };
+    let part = |start: usize, end: usize| chars[start..end].iter().collect::<String>();
+    let scope = match &layout.scope {
+        Some(scope) if layout.scope_closed => part(scope.start, scope.end),
+        _ => String::new(),
+    };
+    CommitRequest {
+        commit_type: part(0, layout.type_end),
+        scope,
+        short_message: part(separator + 2, chars.len()).trim().to_string(),
+        breaking_change: chars[..separator].ends_with(&['!'])
+            || long_message.contains("BREAKING CHANGE: "),
+        long_message,
+        amend: false,
+    }
+}
+
+/// Correct the suggestion like a commit request and lint its message.
+fn validate(
+    repo_path: &Path,
+    types: &CommitTypeRegistry,
+    request: CommitRequest,
+    redactions: RedactionReport,
+) -> CommitSuggestion {
+    match plan_commit(repo_path, &request) {
+        Ok(plan) => CommitSuggestion {
+            issues: locate_message_issues(&plan.message, types)
+                .into_iter()
+                .map(|issue| issue.issue)
+                .collect(),
+            commit_type: plan.commit_type,
+            scope: plan.scope,
+            short_message: request.short_message,
+            long_message: request.long_message,
+            breaking_change: request.breaking_change,
+            message: plan.message,
+            redactions,
+        },
+        Err(e) => CommitSuggestion {
+            message: git::format_commit_message(
+                &request.commit_type,
+                request.breaking_change,
+                &request.scope,
+                &request.short_message,
+                &request.long_message,
+            ),
+            issues: vec![e.to_string()],
+            commit_type: request.commit_type,
+            scope: request.scope,
+            short_message: request.short_message,
+            long_message: request.long_message,
+            breaking_change: request.breaking_change,
+            redactions,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_messages_are_split_into_parts() {
+        let request = parse_message("feat(api)!: add the endpoint\n\nBecause we need it.");
+        assert_eq!(request.commit_type, "feat");
+        assert_eq!(request.scope, "api");
+        assert!(request.breaking_change);
+        assert_eq!(request.short_message, "add the endpoint");
+        assert_eq!(request.long_message, "Because we need it.");
+
+        let request = parse_message("add the endpoint");
+        assert_eq!(request.commit_type, "");
+        assert_eq!(request.short_message, "add the endpoint");
+    }
+
+    #[test]
+    fn test_json_suggestions_are_parsed() {
+        let text = "```json\n{\"commit_type\": \"fix\", \"short\": \"handle empty input\", \"scope\": \"cli\"}\n```";
+        let request = parse_suggestion(text, true).unwrap();
+        assert_eq!(request.commit_type, "fix");
+        assert_eq!(request.scope, "cli");
+        assert_eq!(request.short_message, "handle empty input");
+
+        let request = parse_suggestion("{\"message\": \"docs: fix typo\"}", true).unwrap();
+        assert_eq!(request.commit_type, "docs");
+
+        let error = parse_suggestion("not json", true).unwrap_err();
+        assert_eq!(error.kind(), crate::error::ErrorKind::AiFailed);
+    }
+
+    #[test]
+    fn test_prompt_withholds_names_and_diffs_unless_allowed() {
+        let files = [FileDiff {
+            path: "src/secret_plan.rs".to_string(),
+            insertions: 2,
+            deletions: 1,
+            patch: "+let key = 1;\n".to_string(),
+            truncated: false,
+            omitted: None,
+        }];
+        let prompt = diff_prompt(&files, &AiOptions::default());
+        assert!(prompt.contains("code (1)"));
+        assert!(!prompt.contains("secret_plan"));
+        assert!(!prompt.contains("let key"));
+
+        let ai = AiOptions {
+            allow_sensitive: true,
+            ..AiOptions::default()
+        };
+        let prompt = diff_prompt(&files, &ai);
+        assert!(prompt.contains("- src/secret_plan.rs (+2 -1)"));
+        assert!(prompt.contains("+let key = 1;"));
+    }
+
+    #[test]
+    fn test_diff_section_only_shows_the_files_listed() {
+        let file = |path: &str, patch: &str| FileDiff {
+            path: path.to_string(),
+            insertions: 1,
+            deletions: 0,
+            patch: patch.to_string(),
+            truncated: false,
+            omitted: None,
+        };
+        let files = [file("a.rs", "+first\n"), file("b.rs", "+second\n")];
+        let section = diff_section(&files, 1);
+        assert!(section.contains("- a.rs (+1 -0)\n- ...\n"));
+        assert!(section.contains("+first"));
+        assert!(!section.contains("b.rs"));
+        assert!(!section.contains("+second"));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::config::commit_types::CommitTypeRegistry;
+use crate::error::CliError;
+use crate::git;
+use crate::input::validation::{auto_correct_scope, suggest_registered_type};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::Path;
+
+/// The parts of a conventional commit message.
+#[derive(Debug, Clone, Default)]
+pub struct CommitRequest {
+    pub commit_type: String,
+    pub scope: String,
+    pub short_message: String,
+    pub long_message: String,
+    pub breaking_change: bool,
+    pub amend: bool,
+}
+
+/// A value that was corrected while planning a commit.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq, Eq)]
+pub struct Correction {
+    pub field: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// A validated commit message, as returned by [`plan_commit`].
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CommitPlan {
+    pub commit_type: String,
+    pub scope: String,
+    pub breaking_change: bool,
+    pub message: String,
+    pub corrections: Vec<Correction>,
+}
+
+/// The commit made by [`create_commit`].
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CommitResult {
+    pub sha: String,
+    pub amended: bool,
+    #[serde(flatten)]
+    pub plan: CommitPlan,
+}
+
+/// Validate `request` against the commit types of the repository at `repo_path` and
+/// format its message. Misspelled types and malformed scopes are corrected and reported
+/// in [`CommitPlan::corrections`]; an unknown type or an empty message is an error.
+pub fn plan_commit(repo_path: &Path, request: &CommitRequest) -> Result<CommitPlan, CliError> {
+    let types = CommitTypeRegistry::load_for(repo_path);
+    let mut corrections = Vec::new();
+
+    let Some(commit_type) = suggest_registered_type(&request.commit_type, &types) else {
+        return Err(CliError::InputError(format!(
+            "Invalid commit type '{}'. Valid types are: {}",
+            request.commit_type,
+            types.names().join(", ")
+        )));
+    };
+    if commit_type != request.commit_type {
+        corrections.push(Correction {
+            field: "type".to_string(),
+            from: request.commit_type.clone(),
+            to: commit_type.clone(),
+        });
+    }
+
+    let scope = auto_correct_scope(&request.scope);
+    if scope != request.scope {
+        corrections.push(Correction {
+            field: "scope".to_string(),
+            from: request.scope.clone(),
+            to: scope.clone(),
+        });
+    }
+
+    if request.short_message.trim().is_empty() {
+        return Err(CliError::InputError(
+            "Short message is required".to_string(),
+        ));
+    }
+
+    let message = git::format_commit_message(
+        &commit_type,
+        request.breaking_change,
+        &scope,
+        &request.short_message,
+        &request.long_message,
+    );
+    Ok(CommitPlan {
+        commit_type,
+        scope,
+        breaking_change: request.breaking_change,
+        message,
+        corrections,
+    })
+}
+
+/// Commit the staged changes of the repository at `repo_path` with the message planned
+/// by [`plan_commit`], or amend HEAD with it (and anything staged) when
+/// [`CommitRequest::amend`] is set.
+pub fn create_commit(repo_path: &Path, request: &CommitRequest) -> Result<CommitResult, CliError> {
+    let repo = git::open_repository(repo_path)?;
+    git::check_git_config(&repo)?;
+    if !request.amend && !git::index_has_changes(&repo)? {
+        return Err(CliError::NoStagedChanges);
+    }
+
+    let plan = plan_commit(repo_path, request)?;
+    let oid = git::commit_index(&repo, &plan.message, request.amend)?;
+    Ok(CommitResult {
+        sha: oid.to_string(),
+        amended: request.amend,
+        plan,
+    })
+}
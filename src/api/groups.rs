@@ -0,0 +1,453 @@
+use super::ai::{allowed_types_prompt, complete, deny_paths, diff_section, is_denied, AiOptions};
+use crate::ai::redact::RedactionReport;
+use crate::ai::AiCommitSuggestion;
+use crate::config::commit_types::CommitTypeRegistry;
+use crate::error::CliError;
+use crate::git::{self, DiffSource};
+use crate::linter::check_message_format;
+use git2::Repository;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::{Command as ProcCommand, Stdio};
+
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum GroupName {
+    Docs,
+    Tests,
+    Ci,
+    Deps,
+    Build,
+    Chore,
+    Code,
+}
+
+impl GroupName {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GroupName::Docs => "docs",
+            GroupName::Tests => "tests",
+            GroupName::Ci => "ci",
+            GroupName::Deps => "deps",
+            GroupName::Build => "build",
+            GroupName::Chore => "chore",
+            GroupName::Code => "code",
+        }
+    }
+
+    fn default_type(&self) -> &'static str {
+        match self {
+            GroupName::Docs => "docs",
+            GroupName::Tests => "test",
+            GroupName::Ci => "ci",
+            GroupName::Deps => "chore",
+            GroupName::Build => "build",
+            GroupName::Chore => "chore",
+            GroupName::Code => "chore",
+        }
+    }
+
+    fn default_short(&self) -> &'static str {
+        match self {
+            GroupName::Docs => "update docs",
+            GroupName::Tests => "update tests",
+            GroupName::Ci => "update CI",
+            GroupName::Deps => "update dependencies",
+            GroupName::Build => "update build config",
+            GroupName::Chore => "misc maintenance",
+            GroupName::Code => "update code",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct PlanGroup {
+    pub name: GroupName,
+    pub commit_type: String,
+    pub files: Vec<String>,
+    pub suggested_message: String,
+}
+
+/// What [`plan_groups`] and [`apply_groups`] look at.
+#[derive(Debug, Clone, Default)]
+pub struct GroupOptions {
+    /// Include unstaged and untracked changes, not only the index
+    pub include_unstaged: bool,
+    /// Improve the suggested messages with an AI provider
+    pub ai: Option<AiOptions>,
+}
+
+/// The groups of changed files, as returned by [`plan_groups`].
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct GroupPlan {
+    pub groups: Vec<PlanGroup>,
+    /// Problems that did not stop planning, such as a failed AI suggestion
+    pub errors: Vec<String>,
+    /// With AI, what was kept from the provider
+    pub redactions: Option<RedactionReport>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct CommitRecord {
+    pub group: GroupName,
+    pub message: String,
+    pub ok: bool,
+    pub sha: Option<String>,
+    pub error: Option<String>,
+}
+
+/// The commits made by [`apply_groups`].
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct GroupApply {
+    pub groups: Vec<PlanGroup>,
+    pub commits: Vec<CommitRecord>,
+    pub pushed: Option<bool>,
+    pub errors: Vec<String>,
+    /// With AI, what was kept from the provider
+    pub redactions: Option<RedactionReport>,
+}
+
+pub(super) fn classify_file(file: &str) -> GroupName {
+    let f = file.trim_start_matches("./");
+    // CI
+    if f.starts_with(".github/") {
+        return GroupName::Ci;
+    }
+    // Docs
+    if f.starts_with("docs/")
+        || f.ends_with("README.md")
+        || f.ends_with("README.MD")
+        || f.ends_with(".md")
+        || f.ends_with(".MD")
+        || f.ends_with(".mdx")
+        || f.ends_with(".MDX")
+    {
+        return GroupName::Docs;
+    }
+    // Tests
+    if f.starts_with("tests/")
+        || f.ends_with("_test.rs")
+        || f.ends_with(".test.js")
+        || f.ends_with(".test.ts")
+        || f.ends_with(".spec.js")
+        || f.ends_with(".spec.ts")
+    {
+        return GroupName::Tests;
+    }
+    // Deps (lockfiles)
+    if f.ends_with("package-lock.json")
+        || f.ends_with("npm-shrinkwrap.json")
+        || f.ends_with("pnpm-lock.yaml")
+        || f.ends_with("yarn.lock")
+        || f.ends_with("Cargo.lock")
+    {
+        return GroupName::Deps;
+    }
+    // Build/config
+    if f.ends_with("Cargo.toml")
+        || f.ends_with("build.rs")
+        || f.ends_with("package.json")
+        || f.ends_with("tsconfig.json")
+        || f.contains("eslint.")
+        || f.ends_with(".eslintrc")
+        || f.contains("vite.config")
+        || f.ends_with("rollup.config.js")
+        || f.ends_with("rollup.config.cjs")
+        || f.ends_with("rollup.config.mjs")
+    {
+        return GroupName::Build;
+    }
+    // Chore (editor/config meta)
+    if f.starts_with(".vscode/")
+        || f.ends_with(".editorconfig")
+        || f.ends_with(".gitignore")
+        || f.ends_with(".npmrc")
+    {
+        return GroupName::Chore;
+    }
+    // Everything else
+    GroupName::Code
+}
+
+/// The commit type for a group: a registered type named after the group (e.g. a custom
+/// `deps` type) wins over the built-in mapping.
+fn commit_type_for(name: GroupName, types: &CommitTypeRegistry) -> String {
+    let group = name.as_str();
+    if types.get(group).is_some() {
+        group.to_string()
+    } else {
+        name.default_type().to_string()
+    }
+}
+
+fn build_message_from_suggestion(
+    s: &AiCommitSuggestion,
+    fallback_type: &str,
+    fallback_short: &str,
+) -> String {
+    if let Some(msg) = &s.message {
+        return msg.trim().to_string();
+    }
+    let commit_type = s.commit_type.as_deref().unwrap_or(fallback_type);
+    let short = s.short.as_deref().unwrap_or(fallback_short);
+    let scope = s.scope.as_deref().unwrap_or("");
+    let long = s.long.as_deref().unwrap_or("");
+    git::format_commit_message(commit_type, false, scope, short, long)
+}
+
+/// Group the changed files of the repository at `repo_path` by kind (docs, tests, CI,
+/// dependencies, build, chore, code) and suggest a commit message for each group.
+pub fn plan_groups(repo_path: &Path, options: &GroupOptions) -> Result<GroupPlan, CliError> {
+    let repo = git::open_repository(repo_path)?;
+    let mut by_group: BTreeMap<GroupName, Vec<String>> = BTreeMap::new();
+    for file in git::changed_files(&repo, options.include_unstaged)? {
+        by_group.entry(classify_file(&file)).or_default().push(file);
+    }
+
+    let types = CommitTypeRegistry::load_for(repo_path);
+    let mut groups: Vec<PlanGroup> = by_group
+        .into_iter()
+        .map(|(name, files)| {
+            let commit_type = commit_type_for(name, &types);
+            let suggested_message =
+                git::format_commit_message(&commit_type, false, "", name.default_short(), "");
+            PlanGroup {
+                name,
+                commit_type,
+                files,
+                suggested_message,
+            }
+        })
+        .collect();
+
+    let mut errors = Vec::new();
+    let mut redactions = None;
+    if let Some(ai) = &options.ai {
+        let source = if options.include_unstaged {
+            DiffSource::Workdir
+        } else {
+            DiffSource::Index
+        };
+        let mut report = RedactionReport::default();
+        suggest_with_ai(
+            &repo,
+            source,
+            ai,
+            &types,
+            &mut groups,
+            &mut errors,
+            &mut report,
+        )?;
+        redactions = Some(report);
+    }
+    Ok(GroupPlan {
+        groups,
+        errors,
+        redactions,
+    })
+}
+
+/// Replace the suggested messages with the provider's, when they pass the linter. Files
+/// matching the path denylist are left out of the prompts.
+fn suggest_with_ai(
+    repo: &Repository,
+    source: DiffSource,
+    ai: &AiOptions,
+    types: &CommitTypeRegistry,
+    groups: &mut [PlanGroup],
+    errors: &mut Vec<String>,
+    report: &mut RedactionReport,
+) -> Result<(), CliError> {
+    let deny_paths = deny_paths(repo.path())?;
+    let system_prompt = ai.system_prompt(|| {
+        format!("You are a commit message assistant. Generate conventional commit messages. Prefer JSON if requested.\nReturn an object {{\"commit_type\", \"short\", \"scope\", \"long\", \"message\"}}. If 'message' is present, it should be a full commit message with the first line formatted as '<type>(<scope>): <short>' (scope optional).\n{}", allowed_types_prompt(types))
+    })?;
+
+    for g in groups.iter_mut() {
+        let mut allowed = Vec::new();
+        for file in &g.files {
+            if is_denied(file, &deny_paths) {
+                report.withhold(file);
+            } else {
+                allowed.push(file.clone());
+            }
+        }
+        if allowed.is_empty() {
+            continue;
+        }
+        let user_prompt = if ai.allow_sensitive {
+            let files = git::collect_diffs(repo, source, Some(&allowed), ai.diff_budget())?;
+            format!(
+                "Group: {}\nDefault type: {}\nDefault short: {}\n{}\nReturn a JSON object with fields: commit_type, short, scope, long, message.",
+                g.name.as_str(),
+                g.commit_type,
+                g.name.default_short(),
+                diff_section(&files, ai.file_limit)
+            )
+        } else {
+            format!(
+                "Group: {}\nDefault type: {}\nDefault short: {}\nWithout revealing code or filenames, suggest a better short description if needed. Return JSON.",
+                g.name.as_str(),
+                g.commit_type,
+                g.name.default_short()
+            )
+        };
+
+        let result = complete(ai, &system_prompt, &user_prompt, report);
+
+        match result {
+            Ok(text) => {
+                let candidate = if ai.json_mode {
+                    match serde_json::from_str::<AiCommitSuggestion>(text.trim()) {
+                        Ok(sug) => build_message_from_suggestion(
+                            &sug,
+                            &g.commit_type,
+                            g.name.default_short(),
+                        ),
+                        Err(e) => {
+                            errors.push(format!("AI JSON parse failed: {e}"));
+                            g.suggested_message.clone()
+                        }
+                    }
+                } else {
+                    // Treat the first non-empty line as the commit header
+                    text.lines()
+                        .find(|l| !l.trim().is_empty())
+                        .unwrap_or(g.suggested_message.as_str())
+                        .trim()
+                        .to_string()
+                };
+                // Lint and fallback
+                let issues = check_message_format(&candidate);
+                if issues.is_empty() {
+                    g.suggested_message = candidate;
+                } else {
+                    errors.push(format!("AI suggestion failed lint: {issues:?}"));
+                }
+            }
+            Err(e) => {
+                errors.push(format!("AI error: {e}"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Quietly run `git` in `root`.
+fn run_git(root: &Path, args: &[&str]) -> Result<(), CliError> {
+    let status = ProcCommand::new("git")
+        .args(args)
+        .current_dir(root)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| {
+            CliError::GitError(git2::Error::from_str(&format!("Failed to run git: {e}")))
+        })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(CliError::GitError(git2::Error::from_str(&format!(
+            "git {args:?} failed with status {status:?}"
+        ))))
+    }
+}
+
+/// Plan the groups as [`plan_groups`] does and make one commit per group. With
+/// `auto_stage`, each group's files are staged on their own before its commit;
+/// otherwise each commit takes whatever is staged. With `push`, the branch is pushed
+/// afterwards. A failed group is recorded in [`GroupApply::commits`] and does not stop
+/// the others.
+pub fn apply_groups(
+    repo_path: &Path,
+    options: &GroupOptions,
+    auto_stage: bool,
+    push: bool,
+) -> Result<GroupApply, CliError> {
+    let GroupPlan {
+        groups,
+        mut errors,
+        redactions,
+    } = plan_groups(repo_path, options)?;
+    let root = git::open_repository(repo_path)?
+        .workdir()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| repo_path.to_path_buf());
+
+    let mut commits: Vec<CommitRecord> = Vec::new();
+    for g in &groups {
+        // Validate message again and fallback to default formatting
+        let candidate = g.suggested_message.trim().to_string();
+        let final_msg = if check_message_format(&candidate).is_empty() {
+            candidate
+        } else {
+            git::format_commit_message(&g.commit_type, false, "", g.name.default_short(), "")
+        };
+
+        if auto_stage {
+            // Unstage everything back to HEAD, then stage only the group's files
+            if let Err(e) = run_git(&root, &["reset", "-q", "HEAD", "--"]) {
+                errors.push(format!(
+                    "git reset failed before staging {}: {}",
+                    g.name.as_str(),
+                    e
+                ));
+            }
+            let mut args: Vec<&str> = vec!["add", "--"];
+            args.extend(g.files.iter().map(String::as_str));
+            if let Err(e) = run_git(&root, &args) {
+                errors.push(format!(
+                    "git add failed for group {}: {}",
+                    g.name.as_str(),
+                    e
+                ));
+                commits.push(CommitRecord {
+                    group: g.name,
+                    message: final_msg,
+                    ok: false,
+                    sha: None,
+                    error: Some("failed to stage files".into()),
+                });
+                continue;
+            }
+        }
+
+        // Reopened for each commit, so the index staged by `git add` is the one committed
+        let committed = git::open_repository(repo_path)
+            .and_then(|repo| git::commit_index(&repo, &final_msg, false));
+        match committed {
+            Ok(oid) => commits.push(CommitRecord {
+                group: g.name,
+                message: final_msg,
+                ok: true,
+                sha: Some(oid.to_string()),
+                error: None,
+            }),
+            Err(e) => {
+                errors.push(format!(
+                    "commit failed for group {}: {}",
+                    g.name.as_str(),
+                    e
+                ));
+                commits.push(CommitRecord {
+                    group: g.name,
+                    message: final_msg,
+                    ok: false,
+                    sha: None,
+                    error: Some(e.to_string()),
+                });
+            }
+        }
+    }
+
+    let pushed = push.then(|| run_git(&root, &["push"]).is_ok());
+    Ok(GroupApply {
+        groups,
+        commits,
+        pushed,
+        errors,
+        redactions,
+    })
+}
@@ -0,0 +1,39 @@
+//! Operations for embedding committy in other tools.
+//!
+//! Each function works on the repository at the path it is given and returns a typed,
+//! serializable result. Nothing is printed, nothing is prompted for and no telemetry is
+//! sent; the `committy` commands only render these results.
+//!
+//! ```no_run
+//! use committy::api::{self, CommitRequest};
+//! use std::path::Path;
+//!
+//! let request = CommitRequest {
+//!     commit_type: "feat".into(),
+//!     scope: "cli".into(),
+//!     short_message: "add the api module".into(),
+//!     ..Default::default()
+//! };
+//! let commit = api::create_commit(Path::new("."), &request)?;
+//! println!("{} {}", commit.sha, commit.plan.message);
+//! # Ok::<(), committy::error::CliError>(())
+//! ```
+
+mod ai;
+mod commit;
+mod groups;
+mod release;
+
+pub use ai::{suggest_commit, AiOptions, CommitSuggestion};
+pub use commit::{create_commit, plan_commit, CommitRequest, CommitResult};
+pub use groups::{apply_groups, plan_groups, CommitRecord, GroupOptions, PlanGroup};
+pub use release::{
+    compute_next_version, create_release, create_tag, lint_range, LintReport, VersionOptions,
+};
+// Result types only named by library callers; the commands take them by inference
+#[allow(unused_imports)]
+pub use commit::{CommitPlan, Correction};
+#[allow(unused_imports)]
+pub use groups::{GroupApply, GroupName, GroupPlan};
+#[allow(unused_imports)]
+pub use release::TagResult;
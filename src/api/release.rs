@@ -0,0 +1,186 @@
+use crate::error::CliError;
+use crate::git::{self, TagGenerator, TagGeneratorOptions, TagPlan};
+use crate::linter::{CommitIssue, CommitLinter};
+use crate::release::pipeline::{ReleaseOptions, ReleasePipeline, ReleaseReport, ReleaseState};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+/// The lint issues of a range of commits, as returned by [`lint_range`].
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LintReport {
+    /// Start of the range (exclusive), `None` for the last tag
+    pub from: Option<String>,
+    pub to: String,
+    pub count: usize,
+    pub issues: Vec<CommitIssue>,
+}
+
+/// The tag made by [`create_tag`].
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TagResult {
+    /// The tag the new one was computed from, `None` for a named tag
+    pub previous_tag: Option<String>,
+    pub new_tag: String,
+    pub pre_release: bool,
+    pub dry_run: bool,
+}
+
+/// What [`compute_next_version`] computes the next tag from. Other settings, such as
+/// the tag format and the pre-release channels, come from the repository's
+/// `.committy.toml` as for `committy tag`.
+#[derive(Debug, Clone)]
+pub struct VersionOptions {
+    /// The repository
+    pub source: PathBuf,
+    /// Bump used when the commits do not call for one
+    pub default_bump: String,
+    /// Force the bump level (`major`, `minor`, `patch` or `prerelease`)
+    pub bump: Option<String>,
+    /// Release exactly this version (e.g. `1.0.0`)
+    pub release_as: Option<String>,
+    pub prerelease: bool,
+    pub prerelease_suffix: String,
+    pub release_branches: Vec<String>,
+    /// Version the next one is computed from when there is no tag yet
+    pub initial_version: String,
+    /// Tags without the `v` prefix
+    pub not_with_v: bool,
+    /// Fetch tags from `origin` first
+    pub fetch: bool,
+}
+
+impl Default for VersionOptions {
+    fn default() -> Self {
+        VersionOptions {
+            source: PathBuf::from("."),
+            default_bump: "minor".into(),
+            bump: None,
+            release_as: None,
+            prerelease: false,
+            prerelease_suffix: "beta".into(),
+            release_branches: vec!["master".into(), "main".into()],
+            initial_version: "0.0.0".into(),
+            not_with_v: false,
+            fetch: false,
+        }
+    }
+}
+
+impl VersionOptions {
+    /// The tag options for a dry run, as the `tag` command would parse them.
+    fn tag_options(&self) -> Result<TagGeneratorOptions, CliError> {
+        let source = self.source.to_string_lossy();
+        let branches = self.release_branches.join(",");
+        let mut args = vec![
+            "committy",
+            "--dry-run",
+            "--not-publish",
+            "--source",
+            &source,
+            "--default-bump",
+            &self.default_bump,
+            "--prerelease-suffix",
+            &self.prerelease_suffix,
+            "--release-branches",
+            &branches,
+            "--initial-version",
+            &self.initial_version,
+        ];
+        args.push(if self.fetch { "--fetch" } else { "--no-fetch" });
+        if let Some(bump) = &self.bump {
+            args.extend(["--bump", bump]);
+        }
+        if let Some(version) = &self.release_as {
+            args.extend(["--release-as", version]);
+        }
+        if self.prerelease {
+            args.push("--prerelease");
+        }
+        if self.not_with_v {
+            args.push("--not-with-v");
+        }
+        TagGeneratorOptions::from_iter_safe(args).map_err(|e| CliError::InputError(e.message))
+    }
+}
+
+/// Compute the next tag for the repository at `options.source` from the commits since
+/// the previous tag, without creating anything. Tags are fetched from `origin` first
+/// only with `fetch`. Returns `None` when there is nothing to release.
+pub fn compute_next_version(options: &VersionOptions) -> Result<Option<TagPlan>, CliError> {
+    TagGenerator::new(options.tag_options()?, false).plan()
+}
+
+/// Create the next tag (or `name`) in the repository at `options`' source, bumping
+/// version files first with `bump_files`. The tag is pushed unless `--not-publish` is
+/// set. Returns `None` when there is nothing to release.
+pub fn create_tag(
+    options: &TagGeneratorOptions,
+    name: Option<&str>,
+    bump_files: bool,
+) -> Result<Option<TagResult>, CliError> {
+    let mut generator = TagGenerator::new(options.clone(), bump_files);
+    let repo = generator.open_repository()?;
+    if git::index_has_changes(&repo)? {
+        return Err(CliError::StagedChanges);
+    }
+
+    if let Some(name) = name {
+        generator.create_and_push_tag(&repo, name)?;
+        return Ok(Some(TagResult {
+            previous_tag: None,
+            new_tag: name.to_string(),
+            pre_release: false,
+            dry_run: generator.is_dry_run(),
+        }));
+    }
+    let Some(plan) = generator.plan()? else {
+        return Ok(None);
+    };
+    generator.apply()?;
+    Ok(Some(TagResult {
+        previous_tag: Some(plan.previous_tag),
+        new_tag: plan.new_tag,
+        pre_release: plan.pre_release,
+        dry_run: generator.is_dry_run(),
+    }))
+}
+
+/// Run the release pipeline (lint, version, bump, changelog, commit, tag, push and
+/// publish) on the repository at `options`' source, or resume the one in progress.
+/// Returns `None` when there is nothing to release.
+pub fn create_release(
+    options: &TagGeneratorOptions,
+    release: ReleaseOptions,
+) -> Result<Option<ReleaseReport>, CliError> {
+    let generator = TagGenerator::new(options.clone(), release.bump_files);
+    let repo = generator.open_repository()?;
+    if git::index_has_changes(&repo)? && !(release.resume && only_release_files_staged(&repo)?) {
+        return Err(CliError::StagedChanges);
+    }
+    ReleasePipeline::new(generator, release).run()
+}
+
+/// Whether everything staged belongs to the release in progress, as when its commit
+/// step failed after staging the bumped files.
+fn only_release_files_staged(repo: &git2::Repository) -> Result<bool, CliError> {
+    let Some(state) = ReleaseState::load(repo)? else {
+        return Ok(false);
+    };
+    Ok(git::changed_files(repo, false)?
+        .iter()
+        .all(|path| state.files.contains(path)))
+}
+
+/// Lint the commits of `from..to` in the repository at `repo_path` against the
+/// conventional commit format. Without `from`, the commits since the last tag are linted.
+pub fn lint_range(repo_path: &Path, from: Option<&str>, to: &str) -> Result<LintReport, CliError> {
+    let issues = CommitLinter::new(&repo_path.to_string_lossy())?.check_revisions(from, to)?;
+    Ok(LintReport {
+        from: from.map(String::from),
+        to: to.to_string(),
+        count: issues.len(),
+        issues,
+    })
+}
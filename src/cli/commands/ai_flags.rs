@@ -0,0 +1,104 @@
+use crate::api::AiOptions;
+use structopt::StructOpt;
+
+/// The AI provider flags shared by the commands that can ask for messages.
+#[derive(Debug, StructOpt)]
+pub struct AiFlags {
+    /// Provider for AI
+    #[structopt(long = "ai-provider", default_value = "openrouter", possible_values = &["openrouter", "ollama"])]
+    ai_provider: String,
+
+    /// AI model identifier
+    #[structopt(long = "ai-model")]
+    ai_model: Option<String>,
+
+    /// Env var name to read the API key from (OpenRouter)
+    #[structopt(long = "ai-api-key-env", default_value = "OPENROUTER_API_KEY")]
+    ai_api_key_env: String,
+
+    /// Base URL for the provider API
+    #[structopt(long = "ai-base-url")]
+    ai_base_url: Option<String>,
+
+    /// Max tokens for AI response
+    #[structopt(long = "ai-max-tokens", default_value = "256")]
+    ai_max_tokens: u32,
+
+    /// Temperature for AI sampling
+    #[structopt(long = "ai-temperature", default_value = "0.2")]
+    ai_temperature: f32,
+
+    /// Timeout in milliseconds
+    #[structopt(long = "ai-timeout-ms", default_value = "20000")]
+    ai_timeout_ms: u64,
+
+    /// Disable JSON mode for AI output
+    #[structopt(long = "no-ai-json-mode")]
+    no_ai_json_mode: bool,
+
+    /// Custom system prompt
+    #[structopt(long = "ai-system-prompt")]
+    ai_system_prompt: Option<String>,
+
+    /// System prompt file
+    #[structopt(long = "ai-system-prompt-file")]
+    ai_system_prompt_file: Option<String>,
+
+    /// Max file names sent to AI
+    #[structopt(long = "ai-file-limit", default_value = "20")]
+    ai_file_limit: usize,
+
+    /// Diff lines per file sent to AI
+    #[structopt(long = "ai-diff-lines-per-file", default_value = "80")]
+    ai_diff_lines_per_file: usize,
+
+    /// Estimated tokens of all the diffs sent to AI
+    #[structopt(long = "ai-diff-token-budget", default_value = "3000")]
+    ai_diff_token_budget: usize,
+
+    /// Allow sending sensitive content to external AI providers
+    #[structopt(long = "ai-allow-sensitive")]
+    ai_allow_sensitive: bool,
+}
+
+impl Default for AiFlags {
+    fn default() -> Self {
+        AiFlags {
+            ai_provider: "openrouter".into(),
+            ai_model: None,
+            ai_api_key_env: "OPENROUTER_API_KEY".into(),
+            ai_base_url: None,
+            ai_max_tokens: 256,
+            ai_temperature: 0.2,
+            ai_timeout_ms: 20000,
+            no_ai_json_mode: false,
+            ai_system_prompt: None,
+            ai_system_prompt_file: None,
+            ai_file_limit: 20,
+            ai_diff_lines_per_file: 80,
+            ai_diff_token_budget: 3000,
+            ai_allow_sensitive: false,
+        }
+    }
+}
+
+impl AiFlags {
+    pub fn options(&self) -> AiOptions {
+        AiOptions {
+            provider: self.ai_provider.clone(),
+            model: self.ai_model.clone(),
+            api_key_env: self.ai_api_key_env.clone(),
+            base_url: self.ai_base_url.clone(),
+            max_tokens: self.ai_max_tokens,
+            temperature: self.ai_temperature,
+            timeout_ms: self.ai_timeout_ms,
+            json_mode: !self.no_ai_json_mode,
+            system_prompt: self.ai_system_prompt.clone(),
+            system_prompt_file: self.ai_system_prompt_file.clone(),
+            file_limit: self.ai_file_limit,
+            allow_sensitive: self.ai_allow_sensitive,
+            diff_lines_per_file: self.ai_diff_lines_per_file,
+            diff_token_budget: self.ai_diff_token_budget,
+        }
+    }
+}
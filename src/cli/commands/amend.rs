@@ -1,14 +1,25 @@
+use std::env;
+
+use crate::api;
+use crate::cli::output::Envelope;
 use crate::cli::Command;
 use crate::error::CliError;
-use crate::git;
 use crate::input;
 use log::info;
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
-pub struct AmendCommand {}
+pub struct AmendCommand {
+    /// Output format: text or json
+    #[structopt(long, default_value = "text", possible_values = &["text", "json"])]
+    output: String,
+}
 
 impl Command for AmendCommand {
+    fn json_output(&self) -> bool {
+        self.output == "json"
+    }
+
     fn execute(&self, non_interactive: bool) -> Result<(), CliError> {
         if non_interactive {
             return Err(CliError::InputError(
@@ -16,23 +27,22 @@ impl Command for AmendCommand {
             ));
         }
 
-        let commit_type = input::select_commit_type()?;
-        let breaking_change = input::confirm_breaking_change()?;
-        let scope = input::input_scope()?;
-        let short_message = input::input_short_message()?;
-        let long_message = input::input_long_message()?;
-
-        let full_message = git::format_commit_message(
-            &commit_type,
-            breaking_change,
-            &scope,
-            &short_message,
-            &long_message,
-        );
+        let request = api::CommitRequest {
+            commit_type: input::select_commit_type()?,
+            breaking_change: input::confirm_breaking_change()?,
+            scope: input::input_scope()?,
+            short_message: input::input_short_message()?,
+            long_message: input::input_long_message()?,
+            amend: true,
+        };
 
-        git::commit_changes(&full_message, true)?;
+        let commit = api::create_commit(&env::current_dir()?, &request)?;
 
-        info!("Previous commit amended successfully! 🎉");
+        if self.output == "json" {
+            Envelope::new("amend", &commit).print();
+        } else {
+            info!("Previous commit amended successfully! 🎉");
+        }
         Ok(())
     }
 }
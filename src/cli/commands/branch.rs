@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::cli::output::Envelope;
 use crate::cli::Command;
 use crate::error::CliError;
 use crate::git;
@@ -7,6 +8,8 @@ use crate::input;
 use crate::telemetry;
 use log::debug;
 use log::info;
+use schemars::JsonSchema;
+use serde::Serialize;
 use serde_json::Value;
 use structopt::StructOpt;
 
@@ -20,15 +23,53 @@ pub struct BranchCommand {
 
     #[structopt(short, long, help = "Validate branch name")]
     validate: bool,
+
+    /// Output format: text or json
+    #[structopt(long, default_value = "text", possible_values = &["text", "json"])]
+    output: String,
+}
+
+/// `branch --output json` payload.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BranchOutput {
+    pub name: String,
+    /// The branch (or detached commit) the new branch starts from, null when aborted
+    pub base: Option<String>,
+    pub created: bool,
+    pub checked_out: bool,
+}
+
+impl BranchCommand {
+    fn render(&self, output: BranchOutput) {
+        if self.output == "json" {
+            Envelope::new("branch", output).print();
+            return;
+        }
+        if output.created {
+            println!("Branch {} created successfully!", output.name);
+        }
+        if output.checked_out {
+            println!("Switched to branch {}", output.name);
+        }
+    }
 }
 
 impl Command for BranchCommand {
+    fn json_output(&self) -> bool {
+        self.output == "json"
+    }
+
     fn execute(&self, non_interactive: bool) -> Result<(), CliError> {
         git::validate_git_config()?;
 
         if let Some(name) = &self.name {
-            git::create_branch(name, self.force)?;
-            println!("Branch {name} created successfully!");
+            let base = git::create_branch(name, self.force)?;
+            self.render(BranchOutput {
+                name: name.clone(),
+                base: Some(base),
+                created: true,
+                checked_out: false,
+            });
         } else {
             if non_interactive {
                 return Err(CliError::InputError(
@@ -53,12 +94,22 @@ impl Command for BranchCommand {
             };
             if !validate {
                 info!("Abort");
+                self.render(BranchOutput {
+                    name: branch_name,
+                    base: None,
+                    created: false,
+                    checked_out: false,
+                });
                 return Ok(());
             }
-            git::create_branch(&branch_name, self.force)?;
-            println!("Branch {branch_name} created successfully!");
+            let base = git::create_branch(&branch_name, self.force)?;
             git::checkout_branch(&branch_name)?;
-            println!("Switched to branch {branch_name}");
+            self.render(BranchOutput {
+                name: branch_name,
+                base: Some(base),
+                created: true,
+                checked_out: true,
+            });
             if let Err(e) =
                 tokio::runtime::Runtime::new()
                     .unwrap()
@@ -1,15 +1,32 @@
 use serde_json::Value;
 use std::collections::HashMap;
+use std::env;
 
+use super::ai_flags::AiFlags;
+use crate::ai::redact::RedactionReport;
+use crate::api;
+use crate::cli::output::Envelope;
 use crate::cli::Command;
+use crate::config::commit_types::CommitTypeRegistry;
 use crate::error::CliError;
 use crate::git;
 use crate::input;
-use crate::input::validation::{auto_correct_scope, suggest_commit_type};
+use crate::input::validation::suggest_commit_type;
 use crate::telemetry;
-use log::{debug, info};
+use log::{debug, info, warn};
+use schemars::JsonSchema;
+use serde::Serialize;
 use structopt::StructOpt;
 
+/// `commit --output json` payload.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CommitOutput {
+    #[serde(flatten)]
+    pub commit: api::CommitResult,
+    /// With `--ai`, what was kept from the provider
+    pub redactions: Option<RedactionReport>,
+}
+
 #[derive(Debug, StructOpt, Default)]
 pub struct CommitCommand {
     #[structopt(long = "type", help = "Type of commit (e.g., feat, fix, docs)")]
@@ -29,9 +46,24 @@ pub struct CommitCommand {
 
     #[structopt(long, help = "Amend the previous commit")]
     amend: bool,
+
+    /// Suggest the message from the staged changes with an AI provider
+    #[structopt(long)]
+    ai: bool,
+
+    #[structopt(flatten)]
+    ai_flags: AiFlags,
+
+    /// Output format: text or json
+    #[structopt(long, default_value = "text", possible_values = &["text", "json"])]
+    output: String,
 }
 
 impl Command for CommitCommand {
+    fn json_output(&self) -> bool {
+        self.output == "json"
+    }
+
     fn execute(&self, non_interactive: bool) -> Result<(), CliError> {
         // Validate git configuration first
         git::validate_git_config()?;
@@ -43,88 +75,120 @@ impl Command for CommitCommand {
         // In non-interactive mode (from the command root), all required fields must be provided
         if non_interactive {
             debug!("Running in non-interactive mode");
-            if self.commit_type.is_none() || self.message.is_none() {
+            if !self.ai && (self.commit_type.is_none() || self.message.is_none()) {
                 return Err(CliError::InputError(
                     "In non-interactive mode, --type and --message are required".to_string(),
                 ));
             }
         }
 
-        // Handle commit type with auto-correction
-        let commit_type = if let Some(commit_type) = &self.commit_type {
-            if let Some(suggested) = suggest_commit_type(commit_type) {
-                if suggested != commit_type {
-                    info!("Auto-correcting commit type from '{commit_type}' to '{suggested}'");
-                    debug!("Auto-corrected commit type from '{commit_type}' to '{suggested}'");
+        // The suggestion fills in what was not given, or pre-fills the prompts
+        let mut warnings = Vec::new();
+        let suggestion = if self.ai {
+            let suggestion = api::suggest_commit(&env::current_dir()?, &self.ai_flags.options())?;
+            debug!("AI suggestion: {}", suggestion.message);
+            if !suggestion.issues.is_empty() {
+                let issues = suggestion.issues.join("; ");
+                if non_interactive {
+                    return Err(CliError::AiError(format!(
+                        "The suggested message '{}' does not pass the linter: {issues}",
+                        suggestion.message
+                    )));
                 }
-                suggested.to_string()
-            } else {
+                warn!("The suggested message needs editing: {issues}");
+                warnings.push(format!("AI suggestion needed editing: {issues}"));
+            }
+            Some(suggestion)
+        } else {
+            None
+        };
+        let suggested = |field: fn(&api::CommitSuggestion) -> &str| {
+            suggestion.as_ref().map_or("", field).to_string()
+        };
+
+        // Prompt for whatever was not given; corrections are made by the commit plan
+        let commit_type = match &self.commit_type {
+            // Fail on an unknown type before prompting for the rest
+            Some(commit_type) if suggest_commit_type(commit_type).is_none() => {
                 return Err(CliError::InputError(format!(
                     "Invalid commit type '{}'. Valid types are: {}",
                     commit_type,
-                    crate::config::COMMIT_TYPES.join(", ")
+                    CommitTypeRegistry::load().names().join(", ")
                 )));
             }
-        } else {
-            input::select_commit_type()?
+            Some(commit_type) => commit_type.clone(),
+            None if !non_interactive => input::select_commit_type_with_default(
+                suggestion.as_ref().map(|s| s.commit_type.as_str()),
+            )?,
+            None => suggested(|s| &s.commit_type),
         };
 
-        // Handle breaking change
+        let suggested_breaking = suggestion.as_ref().is_some_and(|s| s.breaking_change);
         let breaking_change = if self.breaking_change {
             debug!("Breaking change flag is set");
             true
         } else if !non_interactive {
-            input::confirm_breaking_change()?
+            input::confirm_breaking_change_with_default(suggested_breaking)?
         } else {
-            false
+            suggested_breaking
         };
 
-        // Handle scope with auto-correction
-        let scope = if let Some(scope) = &self.scope {
-            if !non_interactive {
-                // In interactive mode, validate and potentially correct the scope
-                input::validate_scope_input(scope)?
-            } else {
-                // In non-interactive mode, apply corrections automatically
-                let corrected = auto_correct_scope(scope);
-                if corrected != *scope {
-                    info!("Auto-correcting scope from '{scope}' to '{corrected}'");
-                }
-                corrected
-            }
-        } else if !non_interactive {
-            input::input_scope()?
-        } else {
-            String::new()
+        let scope = match &self.scope {
+            // In interactive mode, validate and potentially correct the scope
+            Some(scope) if !non_interactive => input::validate_scope_input(scope)?,
+            Some(scope) => scope.clone(),
+            None if !non_interactive => input::input_scope_with_default(&suggested(|s| &s.scope))?,
+            None => suggested(|s| &s.scope),
         };
 
-        // Handle messages
         let short_message = match &self.message {
             Some(msg) if !msg.is_empty() => msg.clone(),
-            _ if !non_interactive => input::input_short_message()?,
-            _ => {
-                return Err(CliError::InputError(
-                    "Short message is required".to_string(),
-                ))
+            _ if !non_interactive => {
+                input::input_short_message_with_default(&suggested(|s| &s.short_message))?
             }
+            _ => suggested(|s| &s.short_message),
         };
 
         let long_message = match &self.long_message {
             Some(msg) => msg.clone(),
-            None if !non_interactive => input::input_long_message()?,
-            None => String::new(),
+            None if !non_interactive => {
+                input::input_long_message_with_default(&suggested(|s| &s.long_message))?
+            }
+            None => suggested(|s| &s.long_message),
         };
 
-        let full_message = git::format_commit_message(
-            &commit_type,
+        let request = api::CommitRequest {
+            commit_type,
+            scope,
+            short_message,
+            long_message,
             breaking_change,
-            &scope,
-            &short_message,
-            &long_message,
-        );
+            amend: self.amend,
+        };
+        let commit = api::create_commit(&env::current_dir()?, &request)?;
+        let corrections: Vec<String> = commit
+            .plan
+            .corrections
+            .iter()
+            .map(|c| format!("Auto-corrected {} from '{}' to '{}'", c.field, c.from, c.to))
+            .collect();
+        warnings.extend(corrections.iter().cloned());
+        debug!("Formatted commit message: {}", commit.plan.message);
+        if self.output == "json" {
+            let output = CommitOutput {
+                commit: commit.clone(),
+                redactions: suggestion.map(|s| s.redactions),
+            };
+            Envelope::new("commit", output)
+                .with_warnings(warnings)
+                .print();
+        } else {
+            for correction in &corrections {
+                info!("{correction}");
+            }
+        }
 
-        debug!("Formatted commit message: {full_message}");
-        git::commit_changes(&full_message, self.amend)?;
+        let plan = &commit.plan;
         // fire off telemetry without making this function async
         if let Err(e) =
             tokio::runtime::Runtime::new()
@@ -132,23 +196,29 @@ impl Command for CommitCommand {
                 .block_on(telemetry::posthog::publish_event(
                     "commit_created",
                     HashMap::from([
-                        ("commit_type", Value::from(commit_type.as_str())),
+                        ("commit_type", Value::from(plan.commit_type.as_str())),
                         (
                             "is_breaking_change",
-                            Value::from(breaking_change.to_string()),
+                            Value::from(plan.breaking_change.to_string()),
                         ),
-                        ("as_scope", Value::from((!scope.is_empty()).to_string())),
-                        ("len_scope", Value::from(scope.len())),
+                        (
+                            "as_scope",
+                            Value::from((!plan.scope.is_empty()).to_string()),
+                        ),
+                        ("len_scope", Value::from(plan.scope.len())),
                         (
                             "as_short_message",
-                            Value::from((!short_message.is_empty()).to_string()),
+                            Value::from((!request.short_message.is_empty()).to_string()),
+                        ),
+                        (
+                            "len_short_message",
+                            Value::from(request.short_message.len()),
                         ),
-                        ("len_short_message", Value::from(short_message.len())),
                         (
                             "as_long_message",
-                            Value::from((!long_message.is_empty()).to_string()),
+                            Value::from((!request.long_message.is_empty()).to_string()),
                         ),
-                        ("len_long_message", Value::from(long_message.len())),
+                        ("len_long_message", Value::from(request.long_message.len())),
                     ]),
                 ))
         {
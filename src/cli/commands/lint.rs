@@ -1,7 +1,8 @@
+use crate::api;
+use crate::cli::output::Envelope;
 use crate::cli::Command;
 use crate::error::CliError;
-use crate::linter::CommitLinter;
-use serde::Serialize;
+use std::path::Path;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -16,45 +17,36 @@ pub struct LintCommand {
 }
 
 impl Command for LintCommand {
-    fn execute(&self, _non_interactive: bool) -> Result<(), CliError> {
-        let linter =
-            CommitLinter::new(&self.repo_path).map_err(|e| CliError::Generic(e.to_string()))?;
+    fn json_output(&self) -> bool {
+        self.output == "json"
+    }
 
-        match linter.check_commits_since_last_tag() {
-            Ok(issues) => {
-                if self.output == "json" {
-                    #[derive(Serialize)]
-                    struct LintOutput<'a> {
-                        ok: bool,
-                        count: usize,
-                        issues: &'a [crate::linter::CommitIssue],
-                    }
-                    let payload = LintOutput {
-                        ok: issues.is_empty(),
-                        count: issues.len(),
-                        issues: &issues,
-                    };
-                    println!("{}", serde_json::to_string(&payload).unwrap());
-                } else if issues.is_empty() {
-                    println!(
-                        "✅ All commits since the last tag follow the conventional commit format!"
-                    );
-                } else {
-                    println!("❌ Found {} commit(s) with issues:", issues.len());
-                    for issue in &issues {
-                        println!("\nCommit: {}", issue.commit_id);
-                        println!("Message: {}", issue.message);
-                        println!("Issue: {}", issue.issue);
-                    }
-                }
+    fn execute(&self, _non_interactive: bool) -> Result<(), CliError> {
+        let report = api::lint_range(Path::new(&self.repo_path), None, "HEAD")?;
+        let count = report.count;
 
-                if issues.is_empty() {
-                    Ok(())
-                } else {
-                    Err(CliError::LintIssues(issues.len()))
-                }
+        if self.output == "json" {
+            let errors = if count == 0 {
+                Vec::new()
+            } else {
+                vec![(&CliError::LintIssues(count)).into()]
+            };
+            Envelope::new("lint", report).with_errors(errors).print();
+        } else if report.issues.is_empty() {
+            println!("✅ All commits since the last tag follow the conventional commit format!");
+        } else {
+            println!("❌ Found {count} commit(s) with issues:");
+            for issue in &report.issues {
+                println!("\nCommit: {}", issue.commit_id);
+                println!("Message: {}", issue.message);
+                println!("Issue: {}", issue.issue);
             }
-            Err(e) => Err(CliError::Generic(e.to_string())),
+        }
+
+        if count == 0 {
+            Ok(())
+        } else {
+            Err(CliError::LintIssues(count))
         }
     }
 }
@@ -1,11 +1,20 @@
+use crate::cli::output::Envelope;
 use crate::cli::Command;
 use crate::error::CliError;
 use crate::linter::check_message_format;
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::fs;
 use std::io::{self, Read};
 use structopt::StructOpt;
 
+/// `lint-message --output json` payload.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LintMessageOutput {
+    pub count: usize,
+    pub issues: Vec<String>,
+}
+
 #[derive(Debug, StructOpt)]
 #[structopt(
     name = "lint-message",
@@ -26,48 +35,47 @@ pub struct LintMessageCommand {
 }
 
 impl Command for LintMessageCommand {
+    fn json_output(&self) -> bool {
+        self.output == "json"
+    }
+
     fn execute(&self, _non_interactive: bool) -> Result<(), CliError> {
         // Source the message
         let msg = if let Some(m) = &self.message {
             m.clone()
         } else if let Some(path) = &self.file {
-            fs::read_to_string(path).map_err(|e| CliError::Generic(e.to_string()))?
+            fs::read_to_string(path)?
         } else {
             let mut buf = String::new();
-            io::stdin()
-                .read_to_string(&mut buf)
-                .map_err(|e| CliError::Generic(e.to_string()))?;
+            io::stdin().read_to_string(&mut buf)?;
             buf
         };
 
         let issues = check_message_format(&msg);
 
+        let count = issues.len();
         if self.output == "json" {
-            #[derive(Serialize)]
-            struct LintMessageOutput<'a> {
-                ok: bool,
-                count: usize,
-                issues: &'a [String],
-            }
-            let payload = LintMessageOutput {
-                ok: issues.is_empty(),
-                count: issues.len(),
-                issues: &issues,
+            let errors = if count == 0 {
+                Vec::new()
+            } else {
+                vec![(&CliError::LintIssues(count)).into()]
             };
-            println!("{}", serde_json::to_string(&payload).unwrap());
+            Envelope::new("lint-message", LintMessageOutput { count, issues })
+                .with_errors(errors)
+                .print();
         } else if issues.is_empty() {
             println!("✅ Commit message is valid!");
         } else {
-            println!("❌ Found {} issue(s):", issues.len());
+            println!("❌ Found {count} issue(s):");
             for issue in &issues {
                 println!("- {issue}");
             }
         }
 
-        if issues.is_empty() {
+        if count == 0 {
             Ok(())
         } else {
-            Err(CliError::LintIssues(issues.len()))
+            Err(CliError::LintIssues(count))
         }
     }
 }
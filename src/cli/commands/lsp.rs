@@ -0,0 +1,21 @@
+use crate::cli::Command;
+use crate::error::CliError;
+use crate::lsp;
+use log::info;
+use std::io;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct LspCommand {
+    /// Accepted for editors that pass it; stdio is the only transport
+    #[structopt(long = "stdio")]
+    _stdio: bool,
+}
+
+impl Command for LspCommand {
+    fn execute(&self, _non_interactive: bool) -> Result<(), CliError> {
+        // stdout is the protocol channel, logs stay on stderr
+        info!("Serving the commit message language server over stdio");
+        lsp::serve(io::stdin().lock(), io::stdout().lock())
+    }
+}
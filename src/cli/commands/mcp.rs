@@ -0,0 +1,17 @@
+use crate::cli::Command;
+use crate::error::CliError;
+use crate::mcp;
+use log::info;
+use std::io;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct McpCommand {}
+
+impl Command for McpCommand {
+    fn execute(&self, _non_interactive: bool) -> Result<(), CliError> {
+        // stdout is the protocol channel, logs stay on stderr
+        info!("Serving MCP over stdio");
+        mcp::serve(io::stdin().lock(), io::stdout().lock())
+    }
+}
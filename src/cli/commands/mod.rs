@@ -1,7 +1,13 @@
+pub mod ai_flags;
 pub mod amend;
 pub mod branch;
 pub mod commit;
 pub mod group_commit;
 pub mod lint;
 pub mod lint_message;
+pub mod lsp;
+pub mod mcp;
+pub mod release;
+pub mod schema;
 pub mod tag;
+pub mod version;
@@ -0,0 +1,121 @@
+use crate::api;
+use crate::cli::output::Envelope;
+use crate::cli::Command;
+use crate::error::CliError;
+use crate::git;
+use crate::release::pipeline::{ReleaseOptions, ReleaseReport, ReleaseState, StepStatus};
+use schemars::JsonSchema;
+use serde::Serialize;
+use structopt::StructOpt;
+
+/// `release --output json` payload.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ReleaseOutput {
+    /// Whether there was something to release
+    pub released: bool,
+    /// With `--abort`, whether a release in progress was discarded
+    pub discarded: bool,
+    pub release: Option<ReleaseReport>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ReleaseCommand {
+    #[structopt(
+        long,
+        help = "Continue the release in progress from the step that failed"
+    )]
+    resume: bool,
+
+    #[structopt(
+        long,
+        conflicts_with = "resume",
+        help = "Discard the release in progress (commits and tags already made are kept)"
+    )]
+    abort: bool,
+
+    #[structopt(long, help = "Do not lint the commits being released")]
+    no_lint: bool,
+
+    #[structopt(long, help = "Do not bump the version in version files")]
+    no_bump_files: bool,
+
+    #[structopt(
+        long,
+        default_value = "CHANGELOG.md",
+        help = "Changelog file to add the release notes to"
+    )]
+    changelog: String,
+
+    #[structopt(long, help = "Do not update the changelog")]
+    no_changelog: bool,
+
+    #[structopt(flatten)]
+    tag_options: git::TagGeneratorOptions,
+
+    /// Output format: text or json
+    #[structopt(long, default_value = "text", possible_values = &["text", "json"])]
+    output: String,
+}
+
+impl Command for ReleaseCommand {
+    fn json_output(&self) -> bool {
+        self.output == "json"
+    }
+
+    fn execute(&self, _non_interactive: bool) -> Result<(), CliError> {
+        if self.abort {
+            let generator = git::TagGenerator::new(self.tag_options.clone(), false);
+            let discarded = ReleaseState::discard(&generator.open_repository()?)?;
+            if self.output == "json" {
+                let output = ReleaseOutput {
+                    released: false,
+                    discarded,
+                    release: None,
+                };
+                Envelope::new("release", output).print();
+            } else if discarded {
+                println!("Discarded the release in progress.");
+            } else {
+                println!("No release in progress.");
+            }
+            return Ok(());
+        }
+
+        let options = ReleaseOptions {
+            lint: !self.no_lint,
+            bump_files: !self.no_bump_files,
+            changelog: (!self.no_changelog).then(|| self.changelog.clone()),
+            resume: self.resume,
+        };
+        let report = api::create_release(&self.tag_options, options)?;
+
+        if self.output == "json" {
+            let output = ReleaseOutput {
+                released: report.is_some(),
+                discarded: false,
+                release: report,
+            };
+            Envelope::new("release", output).print();
+            return Ok(());
+        }
+        let Some(report) = report else {
+            println!("No releasable commits since the previous tag, nothing to release.");
+            return Ok(());
+        };
+        for step in &report.steps {
+            let marker = match step.status {
+                StepStatus::Done => "✓",
+                StepStatus::Resumed => "↺",
+                StepStatus::Planned => "→",
+                StepStatus::Skipped => "-",
+            };
+            println!("{marker} {:<9} {}", step.step.as_str(), step.detail);
+        }
+        if report.dry_run {
+            println!("Dry run: {} was not released.", report.new_tag);
+        } else {
+            println!("Released {}!", report.new_tag);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use crate::api::CommitResult;
+use crate::api::LintReport;
+use crate::cli::commands::{
+    branch::BranchOutput, commit::CommitOutput, group_commit::GroupCommitOutput,
+    lint_message::LintMessageOutput, release::ReleaseOutput, tag::TagOutput,
+    version::VersionOutput,
+};
+use crate::cli::output::Envelope;
+use crate::cli::Command;
+use crate::error::CliError;
+use schemars::{schema_for, Schema};
+use structopt::StructOpt;
+
+/// Commands with an `--output json` payload.
+pub const SCHEMA_COMMANDS: &[&str] = &[
+    "amend",
+    "branch",
+    "commit",
+    "group-commit",
+    "lint",
+    "lint-message",
+    "release",
+    "tag",
+    "version",
+];
+
+/// The JSON Schema of `committy <command> --output json`.
+pub fn output_schema(command: &str) -> Option<Schema> {
+    let mut schema = match command {
+        "amend" => schema_for!(Envelope<CommitResult>),
+        "commit" => schema_for!(Envelope<CommitOutput>),
+        "branch" => schema_for!(Envelope<BranchOutput>),
+        "group-commit" => schema_for!(Envelope<GroupCommitOutput>),
+        "lint" => schema_for!(Envelope<LintReport>),
+        "lint-message" => schema_for!(Envelope<LintMessageOutput>),
+        "release" => schema_for!(Envelope<ReleaseOutput>),
+        "tag" => schema_for!(Envelope<TagOutput>),
+        "version" => schema_for!(Envelope<VersionOutput>),
+        _ => return None,
+    };
+    schema.insert(
+        "title".into(),
+        format!("committy {command} --output json").into(),
+    );
+    if let Some(property) = schema
+        .get_mut("properties")
+        .and_then(|properties| properties.get_mut("command"))
+    {
+        *property = serde_json::json!({ "const": command });
+    }
+    Some(schema)
+}
+
+#[derive(Debug, StructOpt)]
+pub struct SchemaCommand {
+    /// Command whose JSON output to describe
+    #[structopt(possible_values = SCHEMA_COMMANDS)]
+    command: String,
+}
+
+impl Command for SchemaCommand {
+    fn execute(&self, _non_interactive: bool) -> Result<(), CliError> {
+        let schema = output_schema(&self.command).ok_or_else(|| {
+            CliError::InputError(format!("No JSON output for '{}'", self.command))
+        })?;
+        println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::cli::output::Envelope;
 use crate::cli::Command;
 use crate::error::CliError;
 use crate::git;
@@ -7,9 +8,33 @@ use crate::input;
 use crate::telemetry;
 use log::debug;
 use log::info;
+use schemars::JsonSchema;
+use serde::Serialize;
 use serde_json::Value;
 use structopt::StructOpt;
 
+/// `tag --output json` payload.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TagOutput {
+    /// The tag the new one was computed from, null with `--name`
+    pub previous_tag: Option<String>,
+    /// The new tag, null when there was nothing to release
+    pub new_tag: Option<String>,
+    pub pre_release: bool,
+    pub dry_run: bool,
+}
+
+impl TagOutput {
+    fn from_generator(generator: &git::TagGenerator) -> Self {
+        TagOutput {
+            previous_tag: generator.previous_tag().map(String::from),
+            new_tag: (!generator.new_tag.is_empty()).then(|| generator.new_tag.clone()),
+            pre_release: generator.is_pre_release,
+            dry_run: generator.is_dry_run(),
+        }
+    }
+}
+
 #[derive(Debug, StructOpt)]
 pub struct TagCommand {
     #[structopt(short, long, help = "Provide a tag name")]
@@ -34,6 +59,10 @@ pub struct TagCommand {
 }
 
 impl Command for TagCommand {
+    fn json_output(&self) -> bool {
+        self.output == "json"
+    }
+
     fn execute(&self, non_interactive: bool) -> Result<(), CliError> {
         if git::has_staged_changes()? {
             return Err(CliError::StagedChanges);
@@ -44,11 +73,16 @@ impl Command for TagCommand {
                 git::TagGenerator::new(self.tag_options.clone(), self.bump_config_files);
             version_manager.create_and_push_tag(&version_manager.open_repository()?, name)?;
             if self.output == "json" {
-                let payload = serde_json::json!({
-                    "ok": true,
-                    "new_tag": name,
-                });
-                println!("{}", serde_json::to_string(&payload).unwrap());
+                Envelope::new(
+                    "tag",
+                    TagOutput {
+                        previous_tag: None,
+                        new_tag: Some(name.clone()),
+                        pre_release: false,
+                        dry_run: version_manager.is_dry_run(),
+                    },
+                )
+                .print();
             } else {
                 println!("Tag {name} created successfully!");
             }
@@ -60,37 +94,67 @@ impl Command for TagCommand {
 
             // Print the calculated tag so callers/tests can consume it
             if self.output == "json" {
-                let payload = serde_json::json!({
-                    "ok": true,
-                    "old_tag": version_manager.current_tag,
-                    "new_tag": version_manager.new_tag,
-                    "pre_release": version_manager.is_pre_release,
-                });
-                println!("{}", serde_json::to_string(&payload).unwrap());
+                Envelope::new("tag", TagOutput::from_generator(&version_manager)).print();
             } else {
                 println!("{}", version_manager.new_tag);
             }
         } else {
-            let validate = if !self.validate {
-                input::ask_want_create_new_tag()?
-            } else {
-                true
-            };
-            if !validate {
-                info!("Abort");
-                return Ok(());
-            }
             let mut version_manager =
                 git::TagGenerator::new(self.tag_options.clone(), self.bump_config_files);
-            version_manager.run()?;
+            let Some(mut plan) = version_manager.plan()? else {
+                if self.output == "json" {
+                    Envelope::new("tag", TagOutput::from_generator(&version_manager)).print();
+                } else {
+                    println!("No releasable commits since the previous tag, nothing to release.");
+                }
+                return Ok(());
+            };
+            // Show what would be released and let the user adjust it, unless -y was given
+            if !self.validate {
+                loop {
+                    println!("Previous tag:  {}", plan.previous_tag);
+                    println!("Computed bump: {}", plan.bump);
+                    println!("New tag:       {}", plan.new_tag);
+                    match input::select_tag_bump(&plan.new_tag)?.as_str() {
+                        "create" => break,
+                        "abort" => {
+                            info!("Abort");
+                            return Ok(());
+                        }
+                        "custom" => {
+                            let version = input::input_release_version()?;
+                            // Ask again rather than abort on a version the prompt let through
+                            if let Err(e) = version_manager.set_release_as(&version) {
+                                println!("{e}");
+                                continue;
+                            }
+                        }
+                        level => version_manager.set_bump(level),
+                    }
+                    let next = match version_manager.plan() {
+                        Ok(next) => next,
+                        // A custom version at or below the current one: ask again
+                        Err(e @ CliError::SemVerError(_)) => {
+                            println!("{e}");
+                            continue;
+                        }
+                        Err(e) => return Err(e),
+                    };
+                    let Some(next) = next else {
+                        if self.output == "json" {
+                            Envelope::new("tag", TagOutput::from_generator(&version_manager))
+                                .print();
+                        } else {
+                            println!("Nothing to release with this choice.");
+                        }
+                        return Ok(());
+                    };
+                    plan = next;
+                }
+            }
+            version_manager.apply()?;
             if self.output == "json" {
-                let payload = serde_json::json!({
-                    "ok": true,
-                    "old_tag": version_manager.current_tag,
-                    "new_tag": version_manager.new_tag,
-                    "pre_release": version_manager.is_pre_release,
-                });
-                println!("{}", serde_json::to_string(&payload).unwrap());
+                Envelope::new("tag", TagOutput::from_generator(&version_manager)).print();
             } else {
                 println!("Tag {} created successfully!", version_manager.new_tag);
             }
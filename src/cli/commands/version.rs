@@ -0,0 +1,71 @@
+use crate::cli::output::Envelope;
+use crate::cli::Command;
+use crate::error::CliError;
+use crate::git;
+use schemars::JsonSchema;
+use serde::Serialize;
+use structopt::StructOpt;
+
+/// `version --output json` payload.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct VersionOutput {
+    #[serde(flatten)]
+    pub dev: git::DevVersion,
+    /// Version files written with `--bump-files`
+    pub updated_files: Vec<String>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct VersionCommand {
+    #[structopt(
+        long,
+        default_value = "{version}-dev.{commits}+g{sha}{dirty}",
+        help = "Development version format ({version}, {commits}, {sha}, {branch}, {dirty})"
+    )]
+    dev_format: String,
+
+    #[structopt(
+        long,
+        help = "Append '.dirty' when the worktree has uncommitted changes"
+    )]
+    dirty: bool,
+
+    #[structopt(
+        short = "b",
+        long = "bump-files",
+        help = "Write the computed version into version files (no commit, no tag)"
+    )]
+    bump_config_files: bool,
+
+    #[structopt(flatten)]
+    tag_options: git::TagGeneratorOptions,
+
+    /// Output format: text or json
+    #[structopt(long, default_value = "text", possible_values = &["text", "json"])]
+    output: String,
+}
+
+impl Command for VersionCommand {
+    fn json_output(&self) -> bool {
+        self.output == "json"
+    }
+
+    fn execute(&self, _non_interactive: bool) -> Result<(), CliError> {
+        let mut generator =
+            git::TagGenerator::new(self.tag_options.clone(), self.bump_config_files);
+        let dev = generator.compute_dev_version(&self.dev_format, self.dirty)?;
+
+        let updated_files = if self.bump_config_files {
+            generator.update_versions(&dev.version)?
+        } else {
+            Vec::new()
+        };
+
+        if self.output == "json" {
+            Envelope::new("version", VersionOutput { dev, updated_files }).print();
+        } else {
+            println!("{}", dev.version);
+        }
+        Ok(())
+    }
+}
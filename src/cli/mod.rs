@@ -1,11 +1,20 @@
 pub mod commands;
+pub mod output;
 
-use self::commands::{amend, branch, commit, group_commit, lint, lint_message, tag};
+use self::commands::{
+    amend, branch, commit, group_commit, lint, lint_message, lsp, mcp, release, schema, tag,
+    version,
+};
 use crate::error::CliError;
 use structopt::StructOpt;
 
 pub trait Command {
     fn execute(&self, non_interactive: bool) -> Result<(), CliError>;
+
+    /// Whether the command was asked for `--output json`.
+    fn json_output(&self) -> bool {
+        false
+    }
 }
 
 #[derive(StructOpt)]
@@ -16,6 +25,8 @@ pub enum CliCommand {
     Amend(amend::AmendCommand),
     #[structopt(about = "Create a new tag")]
     Tag(tag::TagCommand),
+    #[structopt(about = "Run the full release: lint, version, bump, changelog, commit, tag, push")]
+    Release(release::ReleaseCommand),
     #[structopt(about = "Check commits since last tag for conventional format")]
     Lint(lint::LintCommand),
     #[structopt(about = "Lint a single commit message for conventional format")]
@@ -24,18 +35,46 @@ pub enum CliCommand {
     Branch(branch::BranchCommand),
     #[structopt(about = "Group changes and optionally commit/apply them (with optional AI)")]
     GroupCommit(group_commit::GroupCommitCommand),
+    #[structopt(about = "Print the development version of the current build without tagging")]
+    Version(version::VersionCommand),
+    #[structopt(about = "Print the JSON Schema of a command's --output json payload")]
+    Schema(schema::SchemaCommand),
+    #[structopt(about = "Serve the committy tools over the Model Context Protocol (stdio)")]
+    Mcp(mcp::McpCommand),
+    #[structopt(
+        about = "Serve diagnostics, completion and fixes for commit messages over LSP (stdio)"
+    )]
+    Lsp(lsp::LspCommand),
 }
 
 impl CliCommand {
-    pub fn execute(&self, non_interactive: bool) -> Result<(), CliError> {
+    fn command(&self) -> (&'static str, &dyn Command) {
         match self {
-            CliCommand::Commit(cmd) => cmd.execute(non_interactive),
-            CliCommand::Amend(cmd) => cmd.execute(non_interactive),
-            CliCommand::Tag(cmd) => cmd.execute(non_interactive),
-            CliCommand::Lint(cmd) => cmd.execute(non_interactive),
-            CliCommand::LintMessage(cmd) => cmd.execute(non_interactive),
-            CliCommand::Branch(cmd) => cmd.execute(non_interactive),
-            CliCommand::GroupCommit(cmd) => cmd.execute(non_interactive),
+            CliCommand::Commit(cmd) => ("commit", cmd),
+            CliCommand::Amend(cmd) => ("amend", cmd),
+            CliCommand::Tag(cmd) => ("tag", cmd),
+            CliCommand::Release(cmd) => ("release", cmd),
+            CliCommand::Lint(cmd) => ("lint", cmd),
+            CliCommand::LintMessage(cmd) => ("lint-message", cmd),
+            CliCommand::Branch(cmd) => ("branch", cmd),
+            CliCommand::GroupCommit(cmd) => ("group-commit", cmd),
+            CliCommand::Version(cmd) => ("version", cmd),
+            CliCommand::Schema(cmd) => ("schema", cmd),
+            CliCommand::Mcp(cmd) => ("mcp", cmd),
+            CliCommand::Lsp(cmd) => ("lsp", cmd),
+        }
+    }
+
+    /// Run the command. With `--output json`, an error the command did not report in
+    /// its own output is printed as a failed envelope.
+    pub fn execute(&self, non_interactive: bool) -> Result<(), CliError> {
+        let (name, cmd) = self.command();
+        let result = cmd.execute(non_interactive);
+        if let Err(e) = &result {
+            if cmd.json_output() && !output::printed() {
+                output::Envelope::failure(name, e).print();
+            }
         }
+        result
     }
 }
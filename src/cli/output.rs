@@ -0,0 +1,84 @@
+use crate::error::{CliError, ErrorKind};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PRINTED: AtomicBool = AtomicBool::new(false);
+
+/// What every command prints with `--output json`: the command name, whether it
+/// succeeded, its payload, and the errors and warnings met on the way.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Envelope<T> {
+    pub command: String,
+    pub ok: bool,
+    /// The command's payload, null when it failed before producing one
+    pub data: Option<T>,
+    pub errors: Vec<ErrorInfo>,
+    pub warnings: Vec<String>,
+}
+
+/// A machine-readable error.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ErrorInfo {
+    /// Stable error code, also reflected in the exit code
+    pub code: ErrorKind,
+    pub message: String,
+    pub hint: Option<String>,
+}
+
+impl From<&CliError> for ErrorInfo {
+    fn from(error: &CliError) -> Self {
+        ErrorInfo {
+            code: error.kind(),
+            message: error.to_string(),
+            hint: error.hint(),
+        }
+    }
+}
+
+impl<T: Serialize> Envelope<T> {
+    pub fn new(command: &str, data: T) -> Self {
+        Envelope {
+            command: command.to_string(),
+            ok: true,
+            data: Some(data),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Record errors; the command is only `ok` without any.
+    pub fn with_errors(mut self, errors: Vec<ErrorInfo>) -> Self {
+        self.ok = errors.is_empty();
+        self.errors = errors;
+        self
+    }
+
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    pub fn print(&self) {
+        println!("{}", serde_json::to_string(self).unwrap());
+        PRINTED.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Envelope<()> {
+    /// The envelope of a command that failed with `error` before printing its own.
+    pub fn failure(command: &str, error: &CliError) -> Self {
+        Envelope {
+            command: command.to_string(),
+            ok: false,
+            data: None,
+            errors: vec![error.into()],
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Whether an envelope was already printed by this process.
+pub fn printed() -> bool {
+    PRINTED.load(Ordering::Relaxed)
+}
@@ -0,0 +1,378 @@
+use super::{Config, RepoConfig};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// Version bump a commit type triggers on its own. Breaking changes always bump major.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum BumpLevel {
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+impl BumpLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BumpLevel::None => "none",
+            BumpLevel::Patch => "patch",
+            BumpLevel::Minor => "minor",
+            BumpLevel::Major => "major",
+        }
+    }
+}
+
+/// A commit type as used by the prompts, the linter, release notes and version bumps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitType {
+    pub name: String,
+    pub description: String,
+    pub emoji: Option<String>,
+    /// Release notes section title; types without a section are left out of the notes
+    pub section: Option<String>,
+    pub bump: BumpLevel,
+    /// Hidden types stay valid but are not offered in prompts nor listed in release notes
+    pub hidden: bool,
+}
+
+impl fmt::Display for CommitType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.emoji {
+            Some(emoji) => write!(f, "{:<9} {emoji} {}", self.name, self.description),
+            None => write!(f, "{:<9} {}", self.name, self.description),
+        }
+    }
+}
+
+/// A `[[commit_types]]` entry in `config.toml` or `.committy.toml`. Entries named after an
+/// existing type only override the fields they set; other names add a new type.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct CommitTypeConfig {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bump: Option<BumpLevel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+}
+
+// name, description, emoji, section, bump
+const BUILTIN_TYPES: &[(&str, &str, &str, Option<&str>, BumpLevel)] = &[
+    (
+        "feat",
+        "A new feature",
+        "✨",
+        Some("Features"),
+        BumpLevel::Minor,
+    ),
+    (
+        "fix",
+        "A bug fix",
+        "🐛",
+        Some("Bug Fixes"),
+        BumpLevel::Patch,
+    ),
+    (
+        "build",
+        "Changes to the build system or external dependencies",
+        "📦",
+        None,
+        BumpLevel::Patch,
+    ),
+    (
+        "chore",
+        "Other changes that don't modify source or test files",
+        "🔧",
+        None,
+        BumpLevel::Patch,
+    ),
+    (
+        "ci",
+        "Changes to CI configuration files and scripts",
+        "👷",
+        None,
+        BumpLevel::Patch,
+    ),
+    (
+        "cd",
+        "Changes to deployment configuration and scripts",
+        "🚀",
+        None,
+        BumpLevel::Patch,
+    ),
+    (
+        "docs",
+        "Documentation only changes",
+        "📝",
+        Some("Documentation"),
+        BumpLevel::Patch,
+    ),
+    (
+        "perf",
+        "A code change that improves performance",
+        "⚡",
+        Some("Performance Improvements"),
+        BumpLevel::Patch,
+    ),
+    (
+        "refactor",
+        "A code change that neither fixes a bug nor adds a feature",
+        "♻️",
+        Some("Code Refactoring"),
+        BumpLevel::Patch,
+    ),
+    (
+        "revert",
+        "Reverts a previous commit",
+        "⏪",
+        Some("Reverts"),
+        BumpLevel::Patch,
+    ),
+    (
+        "style",
+        "Formatting and other changes that do not affect the meaning of the code",
+        "💄",
+        None,
+        BumpLevel::Patch,
+    ),
+    (
+        "test",
+        "Adding missing tests or correcting existing tests",
+        "✅",
+        None,
+        BumpLevel::Patch,
+    ),
+    (
+        "security",
+        "Fixes and hardening for security issues",
+        "🔒",
+        Some("Security"),
+        BumpLevel::Patch,
+    ),
+    (
+        "config",
+        "Changes to configuration files",
+        "⚙️",
+        None,
+        BumpLevel::Patch,
+    ),
+];
+
+/// The commit types known to committy: the built-in ones, overridden or extended by
+/// `commit_types` in the global config and then in `.committy.toml`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitTypeRegistry {
+    types: Vec<CommitType>,
+}
+
+impl Default for CommitTypeRegistry {
+    fn default() -> Self {
+        let types = BUILTIN_TYPES
+            .iter()
+            .map(|(name, description, emoji, section, bump)| CommitType {
+                name: name.to_string(),
+                description: description.to_string(),
+                emoji: Some(emoji.to_string()),
+                section: section.map(String::from),
+                bump: *bump,
+                hidden: false,
+            })
+            .collect();
+        CommitTypeRegistry { types }
+    }
+}
+
+impl CommitTypeRegistry {
+    /// Registry for the repository in the current directory.
+    pub fn load() -> Self {
+        Self::load_for(Path::new("."))
+    }
+
+    /// Registry for the repository containing `path`. Unreadable configuration is
+    /// reported in the debug log and skipped.
+    pub fn load_for(path: &Path) -> Self {
+        let mut registry = Self::default();
+        registry.apply(&Config::load().unwrap_or_default().commit_types);
+        match RepoConfig::load_for(path) {
+            Ok(repo_config) => registry.apply(&repo_config.commit_types),
+            Err(e) => debug!("Ignoring repository configuration: {e}"),
+        }
+        registry
+    }
+
+    /// Layer configured entries over the registry.
+    pub fn apply(&mut self, overrides: &[CommitTypeConfig]) {
+        for entry in overrides {
+            let name = entry.name.trim().to_lowercase();
+            if name.is_empty() {
+                continue;
+            }
+            let index = match self.types.iter().position(|t| t.name == name) {
+                Some(index) => index,
+                None => {
+                    self.types.push(CommitType {
+                        name: name.clone(),
+                        description: String::new(),
+                        emoji: None,
+                        section: None,
+                        bump: BumpLevel::None,
+                        hidden: false,
+                    });
+                    self.types.len() - 1
+                }
+            };
+            let commit_type = &mut self.types[index];
+            if let Some(description) = &entry.description {
+                commit_type.description = description.clone();
+            }
+            if let Some(emoji) = &entry.emoji {
+                commit_type.emoji = Some(emoji.clone()).filter(|e| !e.is_empty());
+            }
+            if let Some(section) = &entry.section {
+                commit_type.section = Some(section.clone()).filter(|s| !s.is_empty());
+            }
+            if let Some(bump) = entry.bump {
+                commit_type.bump = bump;
+            }
+            if let Some(hidden) = entry.hidden {
+                commit_type.hidden = hidden;
+            }
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CommitType> {
+        self.types.iter().find(|t| t.name == name)
+    }
+
+    /// All type names, hidden ones included, in registry order.
+    pub fn names(&self) -> Vec<&str> {
+        self.types.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    /// Types offered in prompts.
+    pub fn visible(&self) -> Vec<&CommitType> {
+        self.types.iter().filter(|t| !t.hidden).collect()
+    }
+
+    /// Release notes sections in display order, each with the types listed under it.
+    pub fn sections(&self) -> Vec<(&str, Vec<&str>)> {
+        let mut sections: Vec<(&str, Vec<&str>)> = Vec::new();
+        for commit_type in self.types.iter().filter(|t| !t.hidden) {
+            let Some(title) = commit_type.section.as_deref() else {
+                continue;
+            };
+            match sections.iter_mut().find(|(t, _)| *t == title) {
+                Some((_, types)) => types.push(&commit_type.name),
+                None => sections.push((title, vec![&commit_type.name])),
+            }
+        }
+        sections
+    }
+
+    /// Regex matching commit headers that trigger exactly `level`, in the same form as
+    /// the `major_regex`/`minor_regex`/`patch_regex` settings. `None` if no type does.
+    pub fn bump_regex(&self, level: BumpLevel) -> Option<String> {
+        if level == BumpLevel::Major {
+            return Some(format!(
+                r"(?im)^(breaking[ -]change:|(?:{})(?:\s*\([^)]*\))?!:)",
+                alternation(self.types.iter())
+            ));
+        }
+        let types: Vec<&CommitType> = self.types.iter().filter(|t| t.bump == level).collect();
+        if types.is_empty() {
+            return None;
+        }
+        Some(format!(
+            r"(?im)^(?:{})(?:\s*\([^)]*\))?:",
+            alternation(types.into_iter())
+        ))
+    }
+}
+
+fn alternation<'a>(types: impl Iterator<Item = &'a CommitType>) -> String {
+    types
+        .map(|t| regex::escape(&t.name))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    #[test]
+    fn test_builtin_registry() {
+        let registry = CommitTypeRegistry::default();
+        assert_eq!(
+            registry.names(),
+            [
+                "feat", "fix", "build", "chore", "ci", "cd", "docs", "perf", "refactor", "revert",
+                "style", "test", "security", "config",
+            ]
+        );
+        for name in registry.names() {
+            let commit_type = registry.get(name).unwrap();
+            assert!(!commit_type.description.is_empty() && commit_type.emoji.is_some());
+        }
+        assert_eq!(registry.get("feat").unwrap().bump, BumpLevel::Minor);
+        assert_eq!(
+            registry.sections().first(),
+            Some(&("Features", vec!["feat"]))
+        );
+
+        let minor = Regex::new(&registry.bump_regex(BumpLevel::Minor).unwrap()).unwrap();
+        assert!(minor.is_match("feat(cli): add flag"));
+        assert!(!minor.is_match("fix: typo"));
+        let major = Regex::new(&registry.bump_regex(BumpLevel::Major).unwrap()).unwrap();
+        assert!(major.is_match("fix(api)!: drop field"));
+        assert!(major.is_match("body\nBREAKING CHANGE: dropped"));
+        assert!(registry.bump_regex(BumpLevel::None).is_none());
+    }
+
+    #[test]
+    fn test_overrides_merge_by_name() {
+        let mut registry = CommitTypeRegistry::default();
+        let repo_config: RepoConfig = toml::from_str(
+            r#"
+            [[commit_types]]
+            name = "docs"
+            bump = "none"
+            hidden = true
+
+            [[commit_types]]
+            name = "deps"
+            description = "Dependency updates"
+            section = "Dependencies"
+            bump = "patch"
+            "#,
+        )
+        .unwrap();
+        registry.apply(&repo_config.commit_types);
+
+        let docs = registry.get("docs").unwrap();
+        assert_eq!(docs.bump, BumpLevel::None);
+        assert_eq!(docs.description, "Documentation only changes");
+        assert!(registry.visible().iter().all(|t| t.name != "docs"));
+        assert!(registry
+            .sections()
+            .iter()
+            .all(|(s, _)| *s != "Documentation"));
+
+        assert_eq!(registry.names().last(), Some(&"deps"));
+        assert!(registry
+            .sections()
+            .contains(&("Dependencies", vec!["deps"])));
+        let none = Regex::new(&registry.bump_regex(BumpLevel::None).unwrap()).unwrap();
+        assert!(none.is_match("docs: typo"));
+    }
+}
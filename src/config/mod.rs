@@ -1,27 +1,29 @@
-pub const COMMIT_TYPES: &[&str] = &[
-    "feat", "fix", "build", "chore", "ci", "cd", "docs", "perf", "refactor", "revert", "style",
-    "test", "security", "config",
-];
+pub mod commit_types;
 
 pub const BRANCH_TYPES: &[&str] = &[
     "feat", "fix", "refactor", "test", "docs", "perf", "security", "hotfix", "release", "spike",
     "tooling",
 ];
 
+/// Name of the per-repository configuration file, at the root of the worktree.
+pub const REPO_CONFIG_FILE: &str = ".committy.toml";
+
 pub const MAX_SHORT_DESCRIPTION_LENGTH: usize = 150;
 pub const MAX_TICKET_NAME_LENGTH: usize = 10;
 pub const MAX_SCOPE_NAME_LENGTH: usize = 15;
 
+// Bump regexes written to config files before bump levels moved to the commit type registry
 pub const MAJOR_REGEX: &str = r"(?im)^(breaking change:|feat(?:\s*\([^)]*\))?!:)";
 pub const MINOR_REGEX: &str = r"(?im)^feat(?:\s*\([^)]*\))?:";
 pub const PATCH_REGEX: &str = r"(?im)^(fix|docs|style|refactor|perf|test|chore|ci|cd|build|revert|security|config)(?:\s*\([^)]*\))?:";
 
 use anyhow::Result;
 use chrono::{DateTime, FixedOffset};
+use commit_types::{BumpLevel, CommitTypeConfig, CommitTypeRegistry};
 use log::debug;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -31,10 +33,139 @@ pub struct Config {
     pub metrics_enabled: bool,
     pub last_metrics_reminder: DateTime<FixedOffset>,
     pub user_id: String,
-    // Regex patterns for semantic version bump detection
+    // Regex patterns for semantic version bump detection, derived from the commit types when empty
     pub major_regex: String,
     pub minor_regex: String,
     pub patch_regex: String,
+    // Commit types overriding or extending the built-in ones
+    pub commit_types: Vec<CommitTypeConfig>,
+    // While major is 0, breaking changes bump minor and features bump patch
+    pub initial_development: bool,
+}
+
+/// Maps a branch (or `prefix/*` pattern) to a pre-release channel such as `beta` or `rc`.
+/// The channel may contain `{branch}`, replaced by the sanitized branch name.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PrereleaseChannel {
+    pub branch: String,
+    pub channel: String,
+}
+
+/// Settings read from `.committy.toml` in the repository, layered over the global config.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct RepoConfig {
+    pub commit_types: Vec<CommitTypeConfig>,
+    /// Branch to pre-release channel mapping, first match wins
+    pub prerelease_channels: Vec<PrereleaseChannel>,
+    /// Tag name template, e.g. `release-{version}` or `{package}@{version}`
+    pub tag_format: String,
+    /// Annotated tag message template (`{tag}`, `{version}`, `{previous_tag}`, `{date}`,
+    /// `{notes}`)
+    pub tag_message_template: String,
+    /// Checks that must pass before tagging: `lint`, `clean`, `release-branch`, `upstream`
+    pub release_guards: Vec<String>,
+    /// Commits containing one of these markers do not count towards a release
+    pub release_skip_markers: Vec<String>,
+    /// Commits touching only these paths (e.g. `docs/`, `*.md`) do not count towards a
+    /// release
+    pub release_ignore_paths: Vec<String>,
+    pub hooks: ReleaseHooks,
+    pub publish: PublishConfig,
+    pub ai: AiConfig,
+}
+
+/// The `[ai]` section: what never leaves the machine when asking an AI provider.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct AiConfig {
+    /// Files never sent, as patterns like `release_ignore_paths` (`secrets/`, `*.tfvars`),
+    /// on top of the built-in ones such as `.env` and `*.pem`
+    pub deny_paths: Vec<String>,
+}
+
+/// Shell commands run around the release steps of `committy tag`, in order. A failing
+/// command aborts the release.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct ReleaseHooks {
+    pub pre_bump: Vec<String>,
+    pub post_bump: Vec<String>,
+    pub pre_tag: Vec<String>,
+    pub post_tag: Vec<String>,
+    pub post_push: Vec<String>,
+}
+
+/// Forge hosting the releases.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeProvider {
+    Github,
+    Gitlab,
+}
+
+impl ForgeProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ForgeProvider::Github => "GitHub",
+            ForgeProvider::Gitlab => "GitLab",
+        }
+    }
+}
+
+/// The `[publish]` section: the GitHub or GitLab release created after the tag is pushed.
+/// Unset fields are derived from the `origin` remote.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct PublishConfig {
+    /// Publish on every release, not only with `--publish-release`
+    pub enabled: bool,
+    pub provider: Option<ForgeProvider>,
+    /// API base URL, e.g. `https://ghe.example.com/api/v3` or `https://gitlab.example.com/api/v4`
+    pub api_url: Option<String>,
+    /// `owner/name` on GitHub, the full project path on GitLab
+    pub repository: Option<String>,
+    /// Environment variable holding the API token (`GITHUB_TOKEN` or `GITLAB_TOKEN` by default)
+    pub token_env: Option<String>,
+    /// Files attached to the release, as globs relative to the repository root
+    pub assets: Vec<String>,
+}
+
+impl Default for RepoConfig {
+    fn default() -> Self {
+        Self {
+            commit_types: Vec::new(),
+            prerelease_channels: Vec::new(),
+            tag_format: String::new(),
+            tag_message_template: String::new(),
+            release_guards: Vec::new(),
+            release_skip_markers: vec!["[skip release]".to_string(), "[release skip]".to_string()],
+            release_ignore_paths: Vec::new(),
+            hooks: ReleaseHooks::default(),
+            publish: PublishConfig::default(),
+            ai: AiConfig::default(),
+        }
+    }
+}
+
+impl RepoConfig {
+    /// Read `.committy.toml` from the worktree of the repository containing `path`.
+    /// Returns the defaults outside a repository or when the file does not exist.
+    pub fn load_for(path: &Path) -> Result<Self, String> {
+        let Some(workdir) = git2::Repository::discover(path)
+            .ok()
+            .and_then(|repo| repo.workdir().map(Path::to_path_buf))
+        else {
+            return Ok(Self::default());
+        };
+        let file = workdir.join(REPO_CONFIG_FILE);
+        if !file.exists() {
+            return Ok(Self::default());
+        }
+        debug!("Loading repository configuration from {file:?}");
+        let content = fs::read_to_string(&file).map_err(|e| format!("{}: {e}", file.display()))?;
+        toml::from_str(&content).map_err(|e| format!("{}: {e}", file.display()))
+    }
 }
 
 impl Default for Config {
@@ -46,9 +177,11 @@ impl Default for Config {
             last_metrics_reminder: DateTime::parse_from_rfc3339("2006-01-01T00:00:00+01:00")
                 .unwrap(),
             user_id: "".to_string(),
-            major_regex: MAJOR_REGEX.to_string(),
-            minor_regex: MINOR_REGEX.to_string(),
-            patch_regex: PATCH_REGEX.to_string(),
+            major_regex: String::new(),
+            minor_regex: String::new(),
+            patch_regex: String::new(),
+            commit_types: Vec::new(),
+            initial_development: false,
         }
     }
 }
@@ -99,6 +232,22 @@ impl Config {
         Ok(())
     }
 
+    /// The configured bump regex for `level`, or the one derived from `types` when it is
+    /// not set (or still the value written before the commit type registry existed).
+    pub fn bump_regex(&self, level: BumpLevel, types: &CommitTypeRegistry) -> Option<String> {
+        let (configured, legacy) = match level {
+            BumpLevel::Major => (&self.major_regex, MAJOR_REGEX),
+            BumpLevel::Minor => (&self.minor_regex, MINOR_REGEX),
+            BumpLevel::Patch => (&self.patch_regex, PATCH_REGEX),
+            BumpLevel::None => return types.bump_regex(level),
+        };
+        if configured.is_empty() || configured == legacy {
+            types.bump_regex(level)
+        } else {
+            Some(configured.clone())
+        }
+    }
+
     fn get_config_path() -> Result<PathBuf> {
         if let Ok(dir) = std::env::var("COMMITTY_CONFIG_DIR") {
             let path = PathBuf::from(dir).join("config.toml");
@@ -142,6 +291,12 @@ mod tests {
             major_regex: MAJOR_REGEX.to_string(),
             minor_regex: MINOR_REGEX.to_string(),
             patch_regex: PATCH_REGEX.to_string(),
+            commit_types: vec![CommitTypeConfig {
+                name: "deps".to_string(),
+                bump: Some(BumpLevel::Patch),
+                ..Default::default()
+            }],
+            initial_development: true,
         };
 
         (temp_dir, config)
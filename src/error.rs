@@ -1,4 +1,7 @@
+use crate::ai::LlmError;
 use git2::Error as Git2Error;
+use schemars::JsonSchema;
+use serde::Serialize;
 use std::io::Error as IoError;
 use structopt::clap;
 use thiserror::Error;
@@ -14,17 +17,47 @@ pub enum CliError {
     #[error("Input error: {0}")]
     InputError(String),
 
-    #[error("No staged changes found\nFor help, run 'committy --help'")]
+    #[error("No staged changes found")]
     NoStagedChanges,
 
-    #[error(
-        "Please commit your staged changes before doing that\nFor help, run 'committy tag --help'"
-    )]
+    #[error("Please commit your staged changes before doing that")]
     StagedChanges,
 
-    #[error("Git user configuration is missing: {0}\nFor help, run 'git config --global user.name \"Your Name\"' and 'git config --global user.email \"your.email@example.com\"'")]
+    #[error("Git user configuration is missing: {0}")]
     GitConfigError(String),
 
+    #[error("Invalid configuration: {0}")]
+    ConfigError(String),
+
+    #[error("Authentication failed: {0}")]
+    AuthError(String),
+
+    #[error("{0}")]
+    RemoteNotFound(String),
+
+    #[error("Tag {0} already exists")]
+    TagExists(String),
+
+    #[error("AI error: {0}")]
+    AiError(String),
+
+    #[error("Release guards failed:\n{0}")]
+    ReleaseGuards(String),
+
+    #[error("{0}")]
+    ReleaseState(String),
+
+    #[error("Release step '{step}' failed: {source}")]
+    ReleaseStep { step: String, source: Box<CliError> },
+
+    #[error("{0}")]
+    HookFailed(String),
+
+    #[error("{0}")]
+    PublishError(String),
+
+    // Kept for library callers; committy itself reports a specific kind
+    #[allow(dead_code)]
     #[error("{0}")]
     Generic(String),
 
@@ -38,8 +71,135 @@ pub enum CliError {
     LintIssues(usize),
 }
 
+/// The stable category of a [`CliError`]: its `code` in JSON output and its exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// Anything not covered by a more specific kind (exit code 1)
+    Error,
+    /// Invalid arguments or answers (exit code 2)
+    InvalidInput,
+    /// Commits or a message do not follow the conventions (exit code 3)
+    LintIssues,
+    /// Nothing is staged to commit (exit code 4)
+    NoStagedChanges,
+    /// Staged changes are in the way (exit code 5)
+    DirtyWorktree,
+    /// Git user or signing configuration is missing (exit code 6)
+    GitConfig,
+    /// `config.toml` or `.committy.toml` is invalid (exit code 7)
+    InvalidConfig,
+    /// Credentials are missing or were rejected (exit code 8)
+    AuthFailed,
+    /// There is no remote to push or publish to (exit code 9)
+    RemoteNotFound,
+    /// The tag to create already exists (exit code 10)
+    TagExists,
+    /// The AI provider failed (exit code 11)
+    AiFailed,
+    /// A git operation failed (exit code 12)
+    Git,
+    /// A release guard refused the release (exit code 13)
+    ReleaseGuards,
+    /// The release in progress does not allow this (exit code 14)
+    ReleaseState,
+    /// A release hook failed (exit code 15)
+    HookFailed,
+    /// Publishing the forge release failed (exit code 16)
+    PublishFailed,
+}
+
+impl ErrorKind {
+    /// The process exit code. These never change; 0 is success.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorKind::Error => 1,
+            ErrorKind::InvalidInput => 2,
+            ErrorKind::LintIssues => 3,
+            ErrorKind::NoStagedChanges => 4,
+            ErrorKind::DirtyWorktree => 5,
+            ErrorKind::GitConfig => 6,
+            ErrorKind::InvalidConfig => 7,
+            ErrorKind::AuthFailed => 8,
+            ErrorKind::RemoteNotFound => 9,
+            ErrorKind::TagExists => 10,
+            ErrorKind::AiFailed => 11,
+            ErrorKind::Git => 12,
+            ErrorKind::ReleaseGuards => 13,
+            ErrorKind::ReleaseState => 14,
+            ErrorKind::HookFailed => 15,
+            ErrorKind::PublishFailed => 16,
+        }
+    }
+}
+
+impl CliError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            CliError::GitError(e) if e.code() == git2::ErrorCode::Auth => ErrorKind::AuthFailed,
+            CliError::GitError(_) => ErrorKind::Git,
+            CliError::InputError(_) => ErrorKind::InvalidInput,
+            CliError::NoStagedChanges => ErrorKind::NoStagedChanges,
+            CliError::StagedChanges => ErrorKind::DirtyWorktree,
+            CliError::GitConfigError(_) => ErrorKind::GitConfig,
+            CliError::ConfigError(_) => ErrorKind::InvalidConfig,
+            CliError::AuthError(_) => ErrorKind::AuthFailed,
+            CliError::RemoteNotFound(_) => ErrorKind::RemoteNotFound,
+            CliError::TagExists(_) => ErrorKind::TagExists,
+            CliError::AiError(_) => ErrorKind::AiFailed,
+            CliError::ReleaseGuards(_) => ErrorKind::ReleaseGuards,
+            CliError::ReleaseState(_) => ErrorKind::ReleaseState,
+            // A failed release step keeps the kind of what made it fail
+            CliError::ReleaseStep { source, .. } => source.kind(),
+            CliError::HookFailed(_) => ErrorKind::HookFailed,
+            CliError::PublishError(_) => ErrorKind::PublishFailed,
+            CliError::LintIssues(_) => ErrorKind::LintIssues,
+            CliError::IoError(_)
+            | CliError::Generic(_)
+            | CliError::SemVerError(_)
+            | CliError::RegexError(_) => ErrorKind::Error,
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        self.kind().exit_code()
+    }
+
+    /// What the user can do about the error, if there is something to suggest.
+    pub fn hint(&self) -> Option<String> {
+        let hint = match self {
+            CliError::NoStagedChanges => "For help, run 'committy --help'",
+            CliError::StagedChanges => "For help, run 'committy tag --help'",
+            CliError::GitConfigError(_) => "For help, run 'git config --global user.name \"Your Name\"' and 'git config --global user.email \"your.email@example.com\"'",
+            CliError::GitError(e) if e.code() == git2::ErrorCode::Auth => {
+                "For SSH, add your key to the ssh-agent or ~/.ssh/id_rsa; for HTTPS, check your git credential helper"
+            }
+            CliError::RemoteNotFound(_) => {
+                "Add an origin remote with 'git remote add origin <url>'"
+            }
+            CliError::TagExists(_) => {
+                "Delete the tag, or use --on-tag-collision skip to release the next free version"
+            }
+            CliError::ReleaseGuards(_) => {
+                "Fix the listed failures, or choose the guards to run with --release-guards"
+            }
+            CliError::ReleaseStep { .. } => {
+                "Fix the problem and run `committy release --resume` to continue from this step"
+            }
+            _ => return None,
+        };
+        Some(hint.to_string())
+    }
+}
+
 impl From<clap::Error> for CliError {
     fn from(error: clap::Error) -> Self {
-        CliError::Generic(error.to_string())
+        CliError::InputError(error.to_string())
+    }
+}
+
+impl From<LlmError> for CliError {
+    fn from(error: LlmError) -> Self {
+        CliError::AiError(error.to_string())
     }
 }
@@ -3,12 +3,18 @@ use git2::{build::CheckoutBuilder, BranchType, Error as GitError};
 use super::repository::discover_repository;
 use crate::error::CliError;
 
-pub fn create_branch(name: &str, force: bool) -> Result<(), CliError> {
+/// Create `name` at HEAD and return what it was based on: the current branch, or the
+/// commit id when HEAD is detached.
+pub fn create_branch(name: &str, force: bool) -> Result<String, CliError> {
     let repo = discover_repository()?;
     let head = repo.head()?;
     let head_commit = head.peel_to_commit()?;
     repo.branch(name, &head_commit, force)?;
-    Ok(())
+    let base = match head.shorthand() {
+        Some(branch) if head.is_branch() => branch.to_string(),
+        _ => head_commit.id().to_string(),
+    };
+    Ok(base)
 }
 
 pub fn checkout_branch(name: &str) -> Result<(), CliError> {
@@ -1,8 +1,16 @@
 use super::repository::discover_repository;
 use crate::error::CliError;
+use git2::{Oid, Repository};
+use regex::Regex;
+use std::collections::HashMap;
 
+#[allow(dead_code)]
 pub fn commit_changes(message: &str, amend: bool) -> Result<(), CliError> {
-    let repo = discover_repository()?;
+    commit_index(&discover_repository()?, message, amend).map(|_| ())
+}
+
+/// Commit the index of `repo` (or amend HEAD with it) and return the new commit id.
+pub fn commit_index(repo: &Repository, message: &str, amend: bool) -> Result<Oid, CliError> {
     let signature = repo.signature()?;
     let mut index = repo.index()?;
     let oid = index.write_tree()?;
@@ -11,14 +19,14 @@ pub fn commit_changes(message: &str, amend: bool) -> Result<(), CliError> {
     if amend {
         let head = repo.head()?;
         let parent_commit = head.peel_to_commit()?;
-        parent_commit.amend(
+        Ok(parent_commit.amend(
             Some("HEAD"),
             Some(&signature),
             Some(&signature),
             None,
             Some(message),
             Some(&tree),
-        )?;
+        )?)
     } else {
         let parents = match repo.head() {
             Ok(head) => {
@@ -31,17 +39,15 @@ pub fn commit_changes(message: &str, amend: bool) -> Result<(), CliError> {
         let parents_refs: Vec<&git2::Commit> = parents.iter().collect();
 
         // Create the commit
-        repo.commit(
+        Ok(repo.commit(
             Some("HEAD"),
             &signature,
             &signature,
             message,
             &tree,
             &parents_refs,
-        )?;
+        )?)
     }
-
-    Ok(())
 }
 
 pub fn format_commit_message(
@@ -64,3 +70,23 @@ pub fn format_commit_message(
 
     full_message
 }
+
+/// Scopes of the last `limit` commits of HEAD, the most used first.
+pub fn recent_scopes(repo: &Repository, limit: usize) -> Result<Vec<String>, CliError> {
+    let mut revwalk = repo.revwalk()?;
+    if revwalk.push_head().is_err() {
+        // No commits yet
+        return Ok(Vec::new());
+    }
+    let scope = Regex::new(r"^[A-Za-z]+\(([a-z0-9-]+)\)!?: ").unwrap();
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for oid in revwalk.take(limit) {
+        let commit = repo.find_commit(oid?)?;
+        if let Some(captures) = commit.summary().and_then(|s| scope.captures(s)) {
+            *counts.entry(captures[1].to_string()).or_default() += 1;
+        }
+    }
+    let mut scopes: Vec<(String, usize)> = counts.into_iter().collect();
+    scopes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(scopes.into_iter().map(|(scope, _)| scope).collect())
+}
@@ -0,0 +1,363 @@
+use crate::error::CliError;
+use git2::{AttrCheckFlags, AttrValue, DiffOptions, Patch, Repository};
+
+/// Lock files and build outputs, whose diffs say nothing about the change.
+const GENERATED_FILES: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "poetry.lock",
+    "Pipfile.lock",
+    "composer.lock",
+    "Gemfile.lock",
+    "go.sum",
+];
+const GENERATED_SUFFIXES: &[&str] = &[".min.js", ".min.css", ".map", ".snap", ".pb.go"];
+const GENERATED_DIRS: &[&str] = &["dist/", "node_modules/", "target/", "vendor/"];
+
+/// Where [`collect_diffs`] reads the changes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffSource {
+    /// The staged changes
+    Index,
+    /// The staged and unstaged changes, untracked files included
+    Workdir,
+}
+
+/// How much of the diffs [`collect_diffs`] keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffBudget {
+    /// Lines kept of each file's diff, file and hunk headers excluded
+    pub lines_per_file: usize,
+    /// Estimated tokens of all the diffs together
+    pub max_tokens: usize,
+}
+
+/// Why the diff of a file was left out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Omission {
+    Binary,
+    Generated,
+    /// The token budget was spent on the files before it
+    Budget,
+}
+
+impl Omission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Omission::Binary => "binary",
+            Omission::Generated => "generated",
+            Omission::Budget => "over budget",
+        }
+    }
+}
+
+/// A changed file with its unified diff.
+#[derive(Debug, Clone)]
+pub struct FileDiff {
+    pub path: String,
+    pub insertions: usize,
+    pub deletions: usize,
+    /// The diff, cut to the budget; empty when omitted
+    pub patch: String,
+    /// Whether lines were cut from the end of the diff
+    pub truncated: bool,
+    pub omitted: Option<Omission>,
+}
+
+/// Estimate the tokens of `text` for a language model, at about four characters each.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Collect the diffs of the changes of `repo` from `source`, limited to `paths` when
+/// given, file by file and within `budget`.
+pub fn collect_diffs(
+    repo: &Repository,
+    source: DiffSource,
+    paths: Option<&[String]>,
+    budget: DiffBudget,
+) -> Result<Vec<FileDiff>, CliError> {
+    let head = match repo.head() {
+        Ok(head) => Some(head.peel_to_tree()?),
+        // No commits yet: everything is new
+        Err(_) => None,
+    };
+    let mut options = DiffOptions::new();
+    options.disable_pathspec_match(true);
+    for path in paths.unwrap_or_default() {
+        options.pathspec(path);
+    }
+    let diff = match source {
+        DiffSource::Index => repo.diff_tree_to_index(head.as_ref(), None, Some(&mut options))?,
+        DiffSource::Workdir => {
+            options
+                .include_untracked(true)
+                .recurse_untracked_dirs(true)
+                .show_untracked_content(true);
+            repo.diff_tree_to_workdir_with_index(head.as_ref(), Some(&mut options))?
+        }
+    };
+
+    let mut tokens_left = budget.max_tokens;
+    let mut files = Vec::new();
+    for index in 0..diff.deltas().len() {
+        let Some(mut patch) = Patch::from_diff(&diff, index)? else {
+            continue;
+        };
+        let delta = patch.delta();
+        let path = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let binary = delta.flags().is_binary()
+            || delta.new_file().is_binary()
+            || delta.old_file().is_binary();
+        let (_, insertions, deletions) = patch.line_stats()?;
+        let mut file = FileDiff {
+            path,
+            insertions,
+            deletions,
+            patch: String::new(),
+            truncated: false,
+            omitted: None,
+        };
+
+        if binary {
+            file.omitted = Some(Omission::Binary);
+        } else if is_generated(repo, &file.path) {
+            file.omitted = Some(Omission::Generated);
+        } else {
+            let text = String::from_utf8_lossy(&patch.to_buf()?).into_owned();
+            match truncate_patch(&text, budget.lines_per_file, tokens_left) {
+                Some((text, truncated)) => {
+                    tokens_left -= estimate_tokens(&text);
+                    file.patch = text;
+                    file.truncated = truncated;
+                }
+                None => file.omitted = Some(Omission::Budget),
+            }
+        }
+        files.push(file);
+    }
+    Ok(files)
+}
+
+/// Whether `path` is a lock file or build output, or marked `linguist-generated` in
+/// `.gitattributes`.
+fn is_generated(repo: &Repository, path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    if GENERATED_FILES.contains(&name)
+        || GENERATED_SUFFIXES
+            .iter()
+            .any(|suffix| name.ends_with(suffix))
+        || GENERATED_DIRS
+            .iter()
+            .any(|dir| path.starts_with(dir) || path.contains(&format!("/{dir}")))
+    {
+        return true;
+    }
+    let attribute = repo
+        .get_attr(
+            std::path::Path::new(path),
+            "linguist-generated",
+            AttrCheckFlags::FILE_THEN_INDEX,
+        )
+        .ok()
+        .flatten();
+    matches!(AttrValue::from_string(attribute), AttrValue::True)
+        || attribute.is_some_and(|value| value == "true")
+}
+
+/// Keep the headers of a file's diff and as many of its first `max_lines` lines as fit
+/// in `max_tokens`; hunk headers do not count as lines. `None` when not even the file
+/// headers fit.
+fn truncate_patch(patch: &str, max_lines: usize, max_tokens: usize) -> Option<(String, bool)> {
+    let lines: Vec<&str> = patch.lines().collect();
+    // The hunks start at the first `@@` line
+    let body = lines
+        .iter()
+        .position(|line| line.starts_with("@@"))
+        .unwrap_or(lines.len());
+    let marker = |keep: usize| match lines.len() - keep {
+        0 => String::new(),
+        cut => format!("[{cut} more line(s)]\n"),
+    };
+    let fits = |chars: usize, keep: usize| {
+        estimate_tokens(&marker(keep)) + chars.div_ceil(4) <= max_tokens
+    };
+
+    let mut chars: usize = lines[..body].iter().map(|l| l.chars().count() + 1).sum();
+    if !fits(chars, body) {
+        return None;
+    }
+    let mut keep = body;
+    let mut counted = 0;
+    for line in &lines[body..] {
+        let next = chars + line.chars().count() + 1;
+        if counted == max_lines || !fits(next, keep + 1) {
+            break;
+        }
+        chars = next;
+        keep += 1;
+        if !line.starts_with("@@") {
+            counted += 1;
+        }
+    }
+    // A hunk header without any of its lines says nothing
+    while keep > body && lines[keep - 1].starts_with("@@") {
+        keep -= 1;
+    }
+
+    let mut text = lines[..keep].join("\n");
+    text.push('\n');
+    text.push_str(&marker(keep));
+    Some((text, keep < lines.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const BUDGET: DiffBudget = DiffBudget {
+        lines_per_file: 80,
+        max_tokens: 10_000,
+    };
+
+    fn repo_with_changes() -> (TempDir, Repository) {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        fs::write(
+            dir.path().join(".gitattributes"),
+            "schema.rs linguist-generated\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("main.rs"),
+            (1..=10).map(|i| format!("line {i}\n")).collect::<String>(),
+        )
+        .unwrap();
+        fs::write(dir.path().join("logo.png"), [0u8, 159, 146, 150, 0, 1]).unwrap();
+        fs::write(dir.path().join("Cargo.lock"), "version = 3\n").unwrap();
+        fs::write(dir.path().join("schema.rs"), "// generated\n").unwrap();
+        let mut index = repo.index().unwrap();
+        for path in [
+            "main.rs",
+            "logo.png",
+            "Cargo.lock",
+            "schema.rs",
+            ".gitattributes",
+        ] {
+            index.add_path(std::path::Path::new(path)).unwrap();
+        }
+        index.write().unwrap();
+        fs::write(dir.path().join("notes.txt"), "unstaged\n").unwrap();
+        (dir, repo)
+    }
+
+    fn find<'a>(files: &'a [FileDiff], path: &str) -> &'a FileDiff {
+        files.iter().find(|f| f.path == path).unwrap()
+    }
+
+    #[test]
+    fn test_binary_and_generated_files_are_skipped() {
+        let (_dir, repo) = repo_with_changes();
+        let files = collect_diffs(&repo, DiffSource::Index, None, BUDGET).unwrap();
+
+        assert_eq!(files.len(), 5);
+        assert_eq!(find(&files, "logo.png").omitted, Some(Omission::Binary));
+        assert_eq!(
+            find(&files, "Cargo.lock").omitted,
+            Some(Omission::Generated)
+        );
+        assert_eq!(find(&files, "schema.rs").omitted, Some(Omission::Generated));
+        let main = find(&files, "main.rs");
+        assert_eq!(main.omitted, None);
+        assert_eq!(main.insertions, 10);
+        assert!(main.patch.contains("+line 10"));
+        assert!(!main.truncated);
+    }
+
+    #[test]
+    fn test_the_workdir_includes_untracked_files() {
+        let (_dir, repo) = repo_with_changes();
+        let paths = ["notes.txt".to_string()];
+        let files = collect_diffs(&repo, DiffSource::Workdir, Some(&paths), BUDGET).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].patch.contains("+unstaged"));
+
+        assert!(
+            collect_diffs(&repo, DiffSource::Index, Some(&paths), BUDGET)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_diffs_are_cut_to_the_budget() {
+        let (_dir, repo) = repo_with_changes();
+        let budget = DiffBudget {
+            lines_per_file: 3,
+            max_tokens: 10_000,
+        };
+        let files = collect_diffs(&repo, DiffSource::Index, None, budget).unwrap();
+        let main = find(&files, "main.rs");
+        assert!(main.truncated);
+        assert!(main.patch.contains("+line 3\n"));
+        assert!(!main.patch.contains("+line 4\n"));
+        assert!(main.patch.ends_with("[7 more line(s)]\n"));
+
+        let budget = DiffBudget {
+            lines_per_file: 80,
+            max_tokens: 5,
+        };
+        let files = collect_diffs(&repo, DiffSource::Index, None, budget).unwrap();
+        assert_eq!(find(&files, "main.rs").omitted, Some(Omission::Budget));
+        assert!(find(&files, "main.rs").patch.is_empty());
+    }
+
+    #[test]
+    fn test_hunk_headers_do_not_count_as_lines() {
+        let patch = "--- a/a.rs\n+++ b/a.rs\n@@ -1 +1 @@\n-one\n+uno\n@@ -9 +9 @@\n-nine\n+nueve\n";
+        let (text, truncated) = truncate_patch(patch, 3, 10_000).unwrap();
+        assert!(truncated);
+        assert!(text.contains("@@ -9 +9 @@\n-nine\n"));
+        assert!(text.ends_with("[1 more line(s)]\n"));
+        assert_eq!(
+            truncate_patch(patch, 4, 10_000).unwrap(),
+            (patch.to_string(), false)
+        );
+
+        // The next hunk's header is not kept without its lines
+        let (text, _) = truncate_patch(patch, 2, 10_000).unwrap();
+        assert!(!text.contains("@@ -9"));
+        assert!(text.ends_with("+uno\n[3 more line(s)]\n"));
+    }
+
+    #[test]
+    fn test_the_last_file_is_cut_to_the_tokens_left() {
+        let (_dir, repo) = repo_with_changes();
+        let paths = ["main.rs".to_string()];
+        let full = collect_diffs(&repo, DiffSource::Index, Some(&paths), BUDGET).unwrap();
+        let tokens = estimate_tokens(&full[0].patch);
+
+        let budget = DiffBudget {
+            lines_per_file: 80,
+            max_tokens: tokens - 10,
+        };
+        let files = collect_diffs(&repo, DiffSource::Index, Some(&paths), budget).unwrap();
+        let main = &files[0];
+        assert_eq!(main.omitted, None);
+        assert!(main.truncated);
+        assert!(main.patch.contains("+line 1\n"));
+        assert!(!main.patch.contains("+line 10\n"));
+        assert!(main.patch.ends_with("more line(s)]\n"));
+        assert!(estimate_tokens(&main.patch) <= budget.max_tokens);
+    }
+}
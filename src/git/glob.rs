@@ -0,0 +1,69 @@
+use crate::error::CliError;
+use regex::Regex;
+
+/// Compile a glob over a whole repository path: `*` and `?` match within a path segment,
+/// `**` across segments, and `**/` also matches no directory at all.
+pub fn glob_regex(pattern: &str) -> Result<Regex, CliError> {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            other => regex.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).map_err(|e| CliError::RegexError(e.to_string()))
+}
+
+/// Match a repository path against a path pattern: `docs/` matches everything below
+/// `docs`, and patterns without a `/` (e.g. `*.md`) match the file name anywhere.
+/// Otherwise the pattern is a [`glob_regex`] over the whole path.
+pub fn path_matches(pattern: &str, path: &str) -> bool {
+    if let Some(dir) = pattern.strip_suffix('/') {
+        return path.starts_with(&format!("{dir}/"));
+    }
+    let target = if pattern.contains('/') {
+        path
+    } else {
+        path.rsplit('/').next().unwrap_or(path)
+    };
+    glob_regex(pattern).is_ok_and(|re| re.is_match(target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_matches() {
+        assert!(path_matches("docs/", "docs/guide/intro.md"));
+        assert!(!path_matches("docs/", "src/docs.rs"));
+        assert!(path_matches("*.md", "README.md"));
+        assert!(path_matches("*.md", "crates/core/CHANGELOG.md"));
+        assert!(path_matches("tests/*.rs", "tests/tag_tests.rs"));
+        assert!(!path_matches("tests/*.rs", "tests/fixtures/a.rs"));
+        assert!(path_matches("**/fixtures/**", "crates/a/fixtures/b/c.json"));
+        assert!(path_matches("**/fixtures/**", "fixtures/c.json"));
+    }
+
+    #[test]
+    fn test_glob_regex() {
+        let regex = glob_regex("dist/**/*.tar.gz").unwrap();
+        assert!(regex.is_match("dist/app.tar.gz"));
+        assert!(regex.is_match("dist/linux/x64/app.tar.gz"));
+        assert!(!regex.is_match("dist/app.zip"));
+        assert!(glob_regex("v?.txt").unwrap().is_match("v1.txt"));
+        assert!(!glob_regex("*.txt").unwrap().is_match("notes/a.txt"));
+    }
+}
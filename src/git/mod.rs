@@ -1,9 +1,22 @@
 mod branch;
 mod commit;
+mod diff;
+mod glob;
 mod repository;
+mod scheme;
+mod sign;
 mod tag;
+mod tag_format;
 
 pub use branch::{checkout_branch, create_branch};
-pub use commit::{commit_changes, format_commit_message};
-pub use repository::{has_staged_changes, list_changed_files, validate_git_config};
-pub use tag::{TagGenerator, TagGeneratorOptions};
+// Kept for library callers; the commands commit through `api`
+#[allow(unused_imports)]
+pub use commit::commit_changes;
+pub use commit::{commit_index, format_commit_message, recent_scopes};
+pub use diff::{collect_diffs, DiffBudget, DiffSource, FileDiff};
+pub(crate) use glob::{glob_regex, path_matches};
+pub use repository::{
+    changed_files, check_git_config, has_staged_changes, index_has_changes, open_repository,
+    validate_git_config,
+};
+pub use tag::{DevVersion, TagGenerator, TagGeneratorOptions, TagPlan};
@@ -1,12 +1,17 @@
 use crate::error::CliError;
 use git2::{Config, Repository, StatusOptions, StatusShow};
 use std::env;
+use std::path::Path;
 
 pub fn discover_repository() -> Result<Repository, CliError> {
-    let current_dir = env::current_dir()?;
-    log::debug!("Starting repository discovery from: {current_dir:?}");
+    open_repository(&env::current_dir()?)
+}
+
+/// Open the repository containing `path`, rooted at its top-level directory.
+pub fn open_repository(path: &Path) -> Result<Repository, CliError> {
+    log::debug!("Starting repository discovery from: {path:?}");
 
-    match Repository::discover(&current_dir) {
+    match Repository::discover(path) {
         Ok(repo) => {
             // Get the absolute path to the repository root
             let repo_path = repo
@@ -29,7 +34,7 @@ pub fn discover_repository() -> Result<Repository, CliError> {
             }
         }
         Err(e) => {
-            log::error!("Failed to discover repository from {current_dir:?}: {e}");
+            log::error!("Failed to discover repository from {path:?}: {e}");
             Err(CliError::GitError(git2::Error::from_str(
                 "Could not find Git repository in current directory or any parent directories",
             )))
@@ -38,7 +43,11 @@ pub fn discover_repository() -> Result<Repository, CliError> {
 }
 
 pub fn has_staged_changes() -> Result<bool, CliError> {
-    let repo = discover_repository()?;
+    index_has_changes(&discover_repository()?)
+}
+
+/// Whether the index of `repo` differs from HEAD.
+pub fn index_has_changes(repo: &Repository) -> Result<bool, CliError> {
     let mut opts = StatusOptions::new();
     opts.include_ignored(false)
         .include_untracked(false)
@@ -67,8 +76,7 @@ pub fn has_staged_changes() -> Result<bool, CliError> {
 
 /// List changed files in the repository. If `include_unstaged` is true,
 /// include workdir modifications in addition to index changes.
-pub fn list_changed_files(include_unstaged: bool) -> Result<Vec<String>, CliError> {
-    let repo = discover_repository()?;
+pub fn changed_files(repo: &Repository, include_unstaged: bool) -> Result<Vec<String>, CliError> {
     let mut opts = StatusOptions::new();
     opts.include_ignored(false)
         .include_untracked(true)
@@ -110,7 +118,11 @@ fn get_config_value(config: &Config, key: &str) -> Option<String> {
 }
 
 pub fn validate_git_config() -> Result<(), CliError> {
-    let repo = discover_repository()?;
+    check_git_config(&discover_repository()?)
+}
+
+/// Fail unless `user.name` and `user.email` are set for `repo`.
+pub fn check_git_config(repo: &Repository) -> Result<(), CliError> {
     let config = repo.config()?;
 
     // Try to get user.name from local or global config
@@ -0,0 +1,382 @@
+use std::cmp::Ordering;
+
+use crate::error::CliError;
+use chrono::{Datelike, NaiveDate};
+
+/// Versioning scheme used to parse, order and compute tags.
+#[derive(Clone, Debug)]
+pub enum VersionScheme {
+    Semver,
+    Calver(CalverFormat),
+}
+
+impl VersionScheme {
+    /// Regex source (without anchors) matching a release version of this scheme.
+    pub fn version_pattern(&self) -> String {
+        match self {
+            VersionScheme::Semver => r"[0-9]+\.[0-9]+\.[0-9]+".to_string(),
+            VersionScheme::Calver(format) => format.pattern(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CalverToken {
+    FullYear,
+    ShortYear,
+    ZeroPaddedYear,
+    ShortMonth,
+    ZeroPaddedMonth,
+    ShortWeek,
+    ZeroPaddedWeek,
+    ShortDay,
+    ZeroPaddedDay,
+    Micro,
+}
+
+impl CalverToken {
+    fn from_str(token: &str) -> Option<Self> {
+        Some(match token {
+            "YYYY" => CalverToken::FullYear,
+            "YY" => CalverToken::ShortYear,
+            "0Y" => CalverToken::ZeroPaddedYear,
+            "MM" => CalverToken::ShortMonth,
+            "0M" => CalverToken::ZeroPaddedMonth,
+            "WW" => CalverToken::ShortWeek,
+            "0W" => CalverToken::ZeroPaddedWeek,
+            "DD" => CalverToken::ShortDay,
+            "0D" => CalverToken::ZeroPaddedDay,
+            "MICRO" | "N" => CalverToken::Micro,
+            _ => return None,
+        })
+    }
+
+    fn pattern(self) -> &'static str {
+        match self {
+            CalverToken::FullYear => r"[0-9]{4}",
+            CalverToken::ShortYear => r"[0-9]{1,3}",
+            CalverToken::ZeroPaddedYear => r"[0-9]{2,3}",
+            CalverToken::ShortMonth | CalverToken::ShortWeek | CalverToken::ShortDay => {
+                r"[0-9]{1,2}"
+            }
+            CalverToken::ZeroPaddedMonth
+            | CalverToken::ZeroPaddedWeek
+            | CalverToken::ZeroPaddedDay => r"[0-9]{2}",
+            CalverToken::Micro => r"[0-9]+",
+        }
+    }
+
+    fn is_week_based(self) -> bool {
+        matches!(self, CalverToken::ShortWeek | CalverToken::ZeroPaddedWeek)
+    }
+
+    fn render(self, value: u64) -> String {
+        match self {
+            CalverToken::ZeroPaddedYear
+            | CalverToken::ZeroPaddedMonth
+            | CalverToken::ZeroPaddedWeek
+            | CalverToken::ZeroPaddedDay => format!("{value:02}"),
+            _ => value.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Segment {
+    Token(CalverToken),
+    Separator(char),
+}
+
+/// A calendar versioning format such as `YYYY.MM.MICRO` or `YY.0W.MICRO`
+/// (see calver.org). `N` is accepted as an alias of `MICRO`.
+#[derive(Clone, Debug)]
+pub struct CalverFormat {
+    segments: Vec<Segment>,
+}
+
+impl CalverFormat {
+    pub fn parse(format: &str) -> Result<Self, CliError> {
+        let mut segments = Vec::new();
+        let mut current = String::new();
+        for c in format.chars() {
+            if matches!(c, '.' | '-' | '_') {
+                push_token(&mut current, &mut segments, format)?;
+                segments.push(Segment::Separator(c));
+            } else {
+                current.push(c);
+            }
+        }
+        push_token(&mut current, &mut segments, format)?;
+
+        let tokens: Vec<CalverToken> = segments
+            .iter()
+            .filter_map(|s| match s {
+                Segment::Token(t) => Some(*t),
+                Segment::Separator(_) => None,
+            })
+            .collect();
+        if tokens.is_empty() {
+            return Err(CliError::InputError(format!(
+                "CalVer format '{format}' has no tokens"
+            )));
+        }
+        if tokens.iter().filter(|t| **t == CalverToken::Micro).count() > 1 {
+            return Err(CliError::InputError(format!(
+                "CalVer format '{format}' can only contain one MICRO counter"
+            )));
+        }
+        if tokens
+            .iter()
+            .skip_while(|t| **t != CalverToken::Micro)
+            .count()
+            > 1
+        {
+            return Err(CliError::InputError(format!(
+                "CalVer format '{format}' must end with its MICRO counter"
+            )));
+        }
+
+        Ok(CalverFormat { segments })
+    }
+
+    fn tokens(&self) -> impl Iterator<Item = CalverToken> + '_ {
+        self.segments.iter().filter_map(|s| match s {
+            Segment::Token(t) => Some(*t),
+            Segment::Separator(_) => None,
+        })
+    }
+
+    /// Regex source (without anchors) matching versions of this format.
+    pub fn pattern(&self) -> String {
+        self.segments
+            .iter()
+            .map(|s| match s {
+                Segment::Token(t) => t.pattern().to_string(),
+                Segment::Separator(c) => regex::escape(&c.to_string()),
+            })
+            .collect()
+    }
+
+    /// Parse a version (without prefix or pre-release part) into its numeric components.
+    pub fn parse_version(&self, version: &str) -> Option<Vec<u64>> {
+        let re = regex::Regex::new(&format!("^{}$", self.pattern_with_groups())).ok()?;
+        let captures = re.captures(version)?;
+        captures
+            .iter()
+            .skip(1)
+            .map(|m| m.and_then(|m| m.as_str().parse().ok()))
+            .collect()
+    }
+
+    fn pattern_with_groups(&self) -> String {
+        self.segments
+            .iter()
+            .map(|s| match s {
+                Segment::Token(t) => format!("({})", t.pattern()),
+                Segment::Separator(c) => regex::escape(&c.to_string()),
+            })
+            .collect()
+    }
+
+    /// Order two versions of this format; pre-release versions sort before their release.
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        let (a_base, a_pre) = split_pre_release(a);
+        let (b_base, b_pre) = split_pre_release(b);
+        match (self.parse_version(a_base), self.parse_version(b_base)) {
+            (Some(a_values), Some(b_values)) => {
+                a_values.cmp(&b_values).then_with(|| match (a_pre, b_pre) {
+                    (None, None) => Ordering::Equal,
+                    (None, Some(_)) => Ordering::Greater,
+                    (Some(_), None) => Ordering::Less,
+                    (Some(a_pre), Some(b_pre)) => match (
+                        semver::Prerelease::new(a_pre),
+                        semver::Prerelease::new(b_pre),
+                    ) {
+                        (Ok(a_pre), Ok(b_pre)) => a_pre.cmp(&b_pre),
+                        _ => a_pre.cmp(b_pre),
+                    },
+                })
+            }
+            (Some(_), None) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Less,
+            (None, None) => a.cmp(b),
+        }
+    }
+
+    /// Whether two versions belong to the same calendar period (every component but MICRO).
+    pub fn same_period(&self, a: &str, b: &str) -> bool {
+        match (self.parse_version(a), self.parse_version(b)) {
+            (Some(a_values), Some(b_values)) => self.period(&a_values) == self.period(&b_values),
+            _ => false,
+        }
+    }
+
+    fn period(&self, values: &[u64]) -> Vec<u64> {
+        self.tokens()
+            .zip(values)
+            .filter(|(t, _)| *t != CalverToken::Micro)
+            .map(|(_, v)| *v)
+            .collect()
+    }
+
+    /// Compute the next version for `today`: the MICRO counter continues when `latest`
+    /// is in the current period and resets to 0 when the period changed.
+    pub fn next(&self, latest: Option<&str>, today: NaiveDate) -> Result<String, CliError> {
+        let week_based = self.tokens().any(CalverToken::is_week_based);
+        let year = if week_based {
+            today.iso_week().year()
+        } else {
+            today.year()
+        } as u64;
+
+        let mut values: Vec<u64> = self
+            .tokens()
+            .map(|t| match t {
+                CalverToken::FullYear => year,
+                CalverToken::ShortYear | CalverToken::ZeroPaddedYear => year.saturating_sub(2000),
+                CalverToken::ShortMonth | CalverToken::ZeroPaddedMonth => today.month() as u64,
+                CalverToken::ShortWeek | CalverToken::ZeroPaddedWeek => {
+                    today.iso_week().week() as u64
+                }
+                CalverToken::ShortDay | CalverToken::ZeroPaddedDay => today.day() as u64,
+                CalverToken::Micro => 0,
+            })
+            .collect();
+
+        if let Some(latest_values) = latest.and_then(|l| self.parse_version(l)) {
+            let latest_period = self.period(&latest_values);
+            let current_period = self.period(&values);
+            match latest_period.cmp(&current_period) {
+                Ordering::Greater => {
+                    return Err(CliError::InputError(format!(
+                        "Latest CalVer tag {} is ahead of the current date",
+                        latest.unwrap_or_default()
+                    )))
+                }
+                Ordering::Equal => {
+                    let micro = self.tokens().position(|t| t == CalverToken::Micro).ok_or_else(
+                        || {
+                            CliError::InputError(format!(
+                                "Version {} already released for this period and the CalVer format has no MICRO counter",
+                                latest.unwrap_or_default()
+                            ))
+                        },
+                    )?;
+                    values[micro] = latest_values[micro] + 1;
+                }
+                Ordering::Less => {}
+            }
+        }
+
+        Ok(self.render(&values))
+    }
+
+    fn render(&self, values: &[u64]) -> String {
+        let mut values = values.iter();
+        self.segments
+            .iter()
+            .map(|s| match s {
+                Segment::Token(t) => t.render(*values.next().unwrap_or(&0)),
+                Segment::Separator(c) => c.to_string(),
+            })
+            .collect()
+    }
+}
+
+fn push_token(
+    current: &mut String,
+    segments: &mut Vec<Segment>,
+    format: &str,
+) -> Result<(), CliError> {
+    if current.is_empty() {
+        return Ok(());
+    }
+    let token = CalverToken::from_str(current).ok_or_else(|| {
+        CliError::InputError(format!("Unknown CalVer token '{current}' in '{format}'"))
+    })?;
+    segments.push(Segment::Token(token));
+    current.clear();
+    Ok(())
+}
+
+/// Split `2024.6.1-beta.2` into (`2024.6.1`, Some(`beta.2`)).
+/// Only the first `-` followed by a non-digit starts the pre-release part, so
+/// `-` can still be used as a CalVer separator.
+pub fn split_pre_release(version: &str) -> (&str, Option<&str>) {
+    let bytes = version.as_bytes();
+    for (i, b) in bytes.iter().enumerate() {
+        if *b == b'-' && bytes.get(i + 1).is_some_and(|n| !n.is_ascii_digit()) {
+            return (&version[..i], Some(&version[i + 1..]));
+        }
+    }
+    (version, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_calver_next_resets_micro_per_period() {
+        let format = CalverFormat::parse("YYYY.MM.MICRO").unwrap();
+        let today = date(2024, 6, 15);
+
+        assert_eq!(format.next(None, today).unwrap(), "2024.6.0");
+        assert_eq!(format.next(Some("2024.6.0"), today).unwrap(), "2024.6.1");
+        assert_eq!(format.next(Some("2024.6.9"), today).unwrap(), "2024.6.10");
+        assert_eq!(format.next(Some("2024.5.3"), today).unwrap(), "2024.6.0");
+        assert_eq!(format.next(Some("2023.12.7"), today).unwrap(), "2024.6.0");
+        assert!(format.next(Some("2024.7.0"), today).is_err());
+    }
+
+    #[test]
+    fn test_calver_week_format() {
+        let format = CalverFormat::parse("YY.0W.N").unwrap();
+        // 2024-12-30 belongs to ISO week 1 of 2025
+        assert_eq!(format.next(None, date(2024, 12, 30)).unwrap(), "25.01.0");
+        assert_eq!(
+            format.next(Some("24.52.3"), date(2024, 12, 30)).unwrap(),
+            "25.01.0"
+        );
+        assert_eq!(
+            format.next(Some("25.01.0"), date(2025, 1, 2)).unwrap(),
+            "25.01.1"
+        );
+        assert!(format.parse_version("25.1.0").is_none());
+    }
+
+    #[test]
+    fn test_calver_without_micro() {
+        let format = CalverFormat::parse("YYYY.0M.0D").unwrap();
+        assert_eq!(format.next(None, date(2024, 3, 5)).unwrap(), "2024.03.05");
+        assert!(format.next(Some("2024.03.05"), date(2024, 3, 5)).is_err());
+    }
+
+    #[test]
+    fn test_calver_ordering() {
+        let format = CalverFormat::parse("YYYY.MM.MICRO").unwrap();
+        assert_eq!(format.compare("2024.10.0", "2024.9.5"), Ordering::Greater);
+        assert_eq!(format.compare("2024.9.10", "2024.9.9"), Ordering::Greater);
+        assert_eq!(
+            format.compare("2024.9.1-beta.2", "2024.9.1"),
+            Ordering::Less
+        );
+        assert_eq!(
+            format.compare("2024.9.1-beta.10", "2024.9.1-beta.2"),
+            Ordering::Greater
+        );
+        assert!(format.same_period("2024.9.1", "2024.9.7"));
+        assert!(!format.same_period("2024.9.1", "2024.10.1"));
+    }
+
+    #[test]
+    fn test_calver_invalid_formats() {
+        assert!(CalverFormat::parse("YYYY.QQ.MICRO").is_err());
+        assert!(CalverFormat::parse("MICRO.YYYY").is_err());
+        assert!(CalverFormat::parse("").is_err());
+    }
+}
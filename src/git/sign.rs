@@ -0,0 +1,217 @@
+use crate::error::CliError;
+use git2::{Object, Oid, Repository, Signature};
+use log::debug;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use uuid::Uuid;
+
+/// Signature backend, following git's `gpg.format`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SigningFormat {
+    OpenPgp,
+    X509,
+    Ssh,
+}
+
+/// Signs tag payloads the same way `git tag -s` does, driven by git configuration
+/// (`gpg.format`, `gpg.<format>.program`, `user.signingkey`).
+#[derive(Debug, Clone)]
+pub struct TagSigner {
+    format: SigningFormat,
+    program: String,
+    key: Option<String>,
+}
+
+impl TagSigner {
+    pub fn from_config(config: &git2::Config) -> Result<Self, CliError> {
+        let format = match config.get_string("gpg.format").ok().as_deref() {
+            None | Some("openpgp") => SigningFormat::OpenPgp,
+            Some("x509") => SigningFormat::X509,
+            Some("ssh") => SigningFormat::Ssh,
+            Some(other) => {
+                return Err(CliError::GitConfigError(format!(
+                    "unsupported gpg.format '{other}'"
+                )))
+            }
+        };
+        let program = match format {
+            SigningFormat::OpenPgp => config
+                .get_string("gpg.openpgp.program")
+                .or_else(|_| config.get_string("gpg.program"))
+                .unwrap_or_else(|_| "gpg".to_string()),
+            SigningFormat::X509 => config
+                .get_string("gpg.x509.program")
+                .unwrap_or_else(|_| "gpgsm".to_string()),
+            SigningFormat::Ssh => config
+                .get_string("gpg.ssh.program")
+                .unwrap_or_else(|_| "ssh-keygen".to_string()),
+        };
+        let key = config
+            .get_string("user.signingkey")
+            .ok()
+            .filter(|k| !k.trim().is_empty());
+        Ok(TagSigner {
+            format,
+            program,
+            key,
+        })
+    }
+
+    /// Whether annotated tags should be signed by default (`tag.gpgSign`).
+    pub fn enabled_in_config(config: &git2::Config) -> bool {
+        config.get_bool("tag.gpgSign").unwrap_or(false)
+    }
+
+    /// Produce an armored detached signature for `payload`. GPG falls back to the
+    /// tagger identity when no signing key is configured, as git does.
+    pub fn sign(&self, payload: &str, tagger: &Signature) -> Result<String, CliError> {
+        debug!(
+            "Signing tag with {:?} using '{}'",
+            self.format, self.program
+        );
+        let signature = match self.format {
+            SigningFormat::OpenPgp | SigningFormat::X509 => {
+                let key = self.key.clone().unwrap_or_else(|| {
+                    format!(
+                        "{} <{}>",
+                        tagger.name().unwrap_or_default(),
+                        tagger.email().unwrap_or_default()
+                    )
+                });
+                self.run_program(&["--status-fd=2", "-bsau", &key], Some(payload))?
+            }
+            SigningFormat::Ssh => self.sign_ssh(payload)?,
+        };
+        if signature.trim().is_empty() {
+            return Err(CliError::GitError(git2::Error::from_str(&format!(
+                "Signing with '{}' produced no signature",
+                self.program
+            ))));
+        }
+        Ok(signature)
+    }
+
+    fn sign_ssh(&self, payload: &str) -> Result<String, CliError> {
+        let key = self.key.as_deref().ok_or_else(|| {
+            CliError::GitConfigError(
+                "user.signingkey is required to sign tags with gpg.format=ssh".to_string(),
+            )
+        })?;
+        let scratch = std::env::temp_dir().join(format!("committy-tag-{}", Uuid::new_v4()));
+        let payload_file = scratch.with_extension("payload");
+        let signature_file = scratch.with_extension("payload.sig");
+        let literal_key_file = scratch.with_extension("pub");
+
+        // A literal public key means the private key lives in the ssh-agent
+        let key = key.strip_prefix("key::").unwrap_or(key);
+        let mut args: Vec<String> = ["-Y", "sign", "-n", "git"].map(String::from).to_vec();
+        if key.starts_with("ssh-") || key.starts_with("ecdsa-") || key.starts_with("sk-") {
+            fs::write(&literal_key_file, key)?;
+            args.push("-U".to_string());
+            args.push("-f".to_string());
+            args.push(literal_key_file.to_string_lossy().to_string());
+        } else {
+            args.push("-f".to_string());
+            args.push(expand_home(key).to_string_lossy().to_string());
+        }
+        args.push(payload_file.to_string_lossy().to_string());
+
+        fs::write(&payload_file, payload)?;
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        let result = self
+            .run_program(&arg_refs, None)
+            .and_then(|_| fs::read_to_string(&signature_file).map_err(CliError::from));
+        for file in [&payload_file, &signature_file, &literal_key_file] {
+            let _ = fs::remove_file(file);
+        }
+        result
+    }
+
+    fn run_program(&self, args: &[&str], stdin: Option<&str>) -> Result<String, CliError> {
+        let mut child = Command::new(&self.program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                CliError::GitConfigError(format!(
+                    "signing program '{}' cannot be run: {e}",
+                    self.program
+                ))
+            })?;
+        if let Some(input) = stdin {
+            if let Some(mut pipe) = child.stdin.take() {
+                pipe.write_all(input.as_bytes())?;
+            }
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(CliError::GitError(git2::Error::from_str(&format!(
+                "Failed to sign tag with '{}': {}",
+                self.program,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+/// Write a signed annotated tag object and create `refs/tags/<name>` pointing to it.
+/// Fails if the tag already exists, like `Repository::tag` with `force = false`.
+pub fn create_signed_tag(
+    repo: &Repository,
+    name: &str,
+    target: &Object,
+    tagger: &Signature,
+    message: &str,
+    signer: &TagSigner,
+) -> Result<Oid, CliError> {
+    let target_type = target.kind().map(|kind| kind.str()).unwrap_or("commit");
+    let mut payload = format!(
+        "object {}\ntype {target_type}\ntag {name}\ntagger {}\n\n{message}",
+        target.id(),
+        format_signature(tagger)
+    );
+    if !payload.ends_with('\n') {
+        payload.push('\n');
+    }
+
+    let signature = signer.sign(&payload, tagger)?;
+    payload.push_str(&signature);
+
+    let oid = repo
+        .odb()?
+        .write(git2::ObjectType::Tag, payload.as_bytes())?;
+    repo.reference(
+        &format!("refs/tags/{name}"),
+        oid,
+        false,
+        &format!("tag: signed {name}"),
+    )?;
+    Ok(oid)
+}
+
+/// `Name <email> <seconds> <+hhmm>`, the identity line used in raw git objects.
+fn format_signature(signature: &Signature) -> String {
+    let when = signature.when();
+    let offset = when.offset_minutes();
+    format!(
+        "{} <{}> {} {}{:02}{:02}",
+        signature.name().unwrap_or_default(),
+        signature.email().unwrap_or_default(),
+        when.seconds(),
+        if offset < 0 { '-' } else { '+' },
+        offset.abs() / 60,
+        offset.abs() % 60
+    )
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
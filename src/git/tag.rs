@@ -1,11 +1,29 @@
+use std::collections::HashSet;
 use std::env;
-
+use std::path::Path;
+
+use super::glob::path_matches;
+use super::scheme::{split_pre_release, CalverFormat, VersionScheme};
+use super::sign::{create_signed_tag, TagSigner};
+use super::tag_format::TagFormat;
+use crate::config::commit_types::{BumpLevel, CommitTypeRegistry};
+use crate::config::{PrereleaseChannel, ReleaseHooks, RepoConfig};
+use crate::linter::{CommitIssue, CommitLinter};
+use crate::release::changelog::{ChangelogEntry, ReleaseNotes};
+use crate::release::hooks::{run_hooks, HookContext, HookStage};
+use crate::release::publish::{PublishTarget, PublishedRelease, ReleaseRequest};
 use crate::version::VersionManager;
 use crate::{config, error::CliError};
-use git2::{FetchOptions, Oid, PushOptions, RemoteCallbacks, Repository};
+use chrono::Utc;
+use git2::{
+    Direction, FetchOptions, Oid, PushOptions, RemoteCallbacks, Repository, StatusOptions,
+    StatusShow,
+};
 use log::{debug, error, info};
 use regex::Regex;
+use schemars::JsonSchema;
 use semver::Version;
+use serde::{Deserialize, Serialize};
 use structopt::StructOpt;
 
 #[derive(Clone, Debug, StructOpt)]
@@ -16,6 +34,15 @@ pub struct TagGeneratorOptions {
     #[structopt(long, help = "Without the prefix 'v'")]
     not_with_v: bool,
 
+    #[structopt(
+        long,
+        help = "Tag name template, e.g. 'release-{version}' or '{package}@{version}'"
+    )]
+    tag_format: Option<String>,
+
+    #[structopt(long, help = "Package name for the {package} tag format placeholder")]
+    package: Option<String>,
+
     #[structopt(
         long,
         default_value = "master,main",
@@ -23,6 +50,13 @@ pub struct TagGeneratorOptions {
     )]
     release_branches: String,
 
+    #[structopt(
+        long,
+        default_value = "release/*",
+        help = "Comma-separated maintenance branch patterns (e.g. release/1.2, release/1.x)"
+    )]
+    maintenance_branches: String,
+
     #[structopt(long, default_value = ".", help = "Source directory")]
     source: String,
 
@@ -36,12 +70,55 @@ pub struct TagGeneratorOptions {
     )]
     initial_version: String,
 
+    #[structopt(
+        long,
+        default_value = "semver",
+        possible_values = &["semver", "calver"],
+        help = "Versioning scheme"
+    )]
+    version_scheme: String,
+
+    #[structopt(
+        long,
+        default_value = "YYYY.MM.MICRO",
+        parse(try_from_str = CalverFormat::parse),
+        help = "CalVer format (YYYY, YY, 0Y, MM, 0M, WW, 0W, DD, 0D, MICRO)"
+    )]
+    calver_format: CalverFormat,
+
+    #[structopt(
+        long,
+        possible_values = &["major", "minor", "patch", "prerelease"],
+        conflicts_with = "release-as",
+        help = "Force the bump level instead of deriving it from commits"
+    )]
+    bump: Option<String>,
+
+    #[structopt(
+        long,
+        parse(try_from_str = parse_release_as),
+        help = "Release exactly this version (e.g. 1.0.0)"
+    )]
+    release_as: Option<Version>,
+
+    #[structopt(
+        long,
+        help = "While major is 0, breaking changes bump minor and features bump patch"
+    )]
+    initial_development: bool,
+
     #[structopt(long, help = "Create a pre-release version")]
     prerelease: bool,
 
     #[structopt(long, default_value = "beta", help = "Pre-release suffix")]
     prerelease_suffix: String,
 
+    #[structopt(
+        long,
+        help = "Comma-separated branch=channel pre-release mapping (e.g. 'develop=beta,next=rc,feat/*=alpha.{branch}')"
+    )]
+    prerelease_channels: Option<String>,
+
     #[structopt(
         long,
         default_value = "#none",
@@ -49,15 +126,73 @@ pub struct TagGeneratorOptions {
     )]
     none_string_token: String,
 
+    #[structopt(
+        long,
+        help = "Comma-separated commit message markers that exclude a commit from the release (e.g. '[skip release]')"
+    )]
+    release_skip_markers: Option<String>,
+
+    #[structopt(
+        long,
+        help = "Comma-separated path patterns; commits touching only these paths do not trigger a release (e.g. 'docs/,tests/,*.md')"
+    )]
+    release_ignore_paths: Option<String>,
+
     #[structopt(long, help = "Force tag creation even without changes")]
     force_without_change: bool,
 
-    #[structopt(long, help = "Custom tag message")]
+    #[structopt(
+        long,
+        help = "Custom tag message ({tag}, {version}, {previous_tag}, {date}, {notes})"
+    )]
     tag_message: Option<String>,
 
+    #[structopt(
+        long,
+        conflicts_with = "no-sign",
+        help = "Sign the tag (GPG or SSH, per git's gpg.format)"
+    )]
+    sign: bool,
+
+    #[structopt(
+        long = "no-sign",
+        help = "Do not sign the tag, even if tag.gpgSign is set"
+    )]
+    no_sign: bool,
+
+    #[structopt(
+        long,
+        default_value = "abort",
+        possible_values = &["abort", "skip"],
+        help = "When the new tag already exists locally or on the remote: abort, or skip to the next free version"
+    )]
+    on_tag_collision: String,
+
+    #[structopt(
+        long,
+        help = "Comma-separated release guards: lint, clean, release-branch, upstream (empty disables)"
+    )]
+    release_guards: Option<String>,
+
+    #[structopt(long, help = "Do not run the release hooks from .committy.toml")]
+    no_hooks: bool,
+
     #[structopt(long, help = "Do not publish the new tag")]
     not_publish: bool,
 
+    #[structopt(
+        long,
+        conflicts_with = "not-publish",
+        help = "Create or update the GitHub/GitLab release after pushing the tag (see [publish] in .committy.toml)"
+    )]
+    publish_release: bool,
+
+    #[structopt(
+        long,
+        help = "Consider all tags, not only those reachable from HEAD (previous behavior)"
+    )]
+    all_tags: bool,
+
     #[structopt(long, help = "Fetch tags from remote before calculation")]
     fetch: bool,
 
@@ -68,24 +203,64 @@ pub struct TagGeneratorOptions {
     no_fetch: bool,
 }
 
+/// The next tag as computed by [`TagGenerator::plan`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TagPlan {
+    pub previous_tag: String,
+    pub bump: String,
+    pub new_tag: String,
+    pub pre_release: bool,
+}
+
+/// A development/snapshot version computed for an untagged build.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DevVersion {
+    pub version: String,
+    pub base_tag: String,
+    pub commits_since_tag: usize,
+    pub sha: String,
+    pub dirty: bool,
+}
+
 pub struct TagGenerator {
     default_bump: String,
     not_with_v: bool,
+    tag_template: Option<String>,
+    package: Option<String>,
+    tag_format: TagFormat,
     release_branches: Vec<String>,
+    maintenance_branches: Vec<String>,
+    release_line: Option<ReleaseLine>,
     source: String,
     dry_run: bool,
     initial_version: String,
+    scheme: VersionScheme,
+    bump: Option<String>,
+    release_as: Option<Version>,
+    initial_development: bool,
     prerelease: bool,
+    /// `--prerelease` as given, restored when a regular bump is chosen interactively
+    configured_prerelease: bool,
     suffix: String,
+    prerelease_channels: Option<String>,
     none_string_token: String,
+    release_skip_markers: Option<String>,
+    release_ignore_paths: Option<String>,
     force_without_change: bool,
     tag_message: String,
+    sign: Option<bool>,
     not_publish: bool,
+    publish_release: bool,
+    no_hooks: bool,
+    release_guards: Option<String>,
+    skip_tag_collisions: bool,
+    all_tags: bool,
     fetch: bool,
     bump_config_files: bool,
     pub current_tag: String,
     pub new_tag: String,
     pub is_pre_release: bool,
+    previous_tag: Option<String>,
 }
 
 impl TagGenerator {
@@ -93,20 +268,55 @@ impl TagGenerator {
         TagGenerator {
             default_bump: options.default_bump,
             not_with_v: options.not_with_v,
+            tag_template: options.tag_format,
+            package: options.package,
+            tag_format: TagFormat::legacy(!options.not_with_v),
             release_branches: options
                 .release_branches
                 .split(',')
                 .map(String::from)
                 .collect(),
+            maintenance_branches: options
+                .maintenance_branches
+                .split(',')
+                .map(|b| b.trim().to_string())
+                .filter(|b| !b.is_empty())
+                .collect(),
+            release_line: None,
             source: options.source,
             dry_run: options.dry_run,
             initial_version: options.initial_version,
-            prerelease: options.prerelease,
+            scheme: match options.version_scheme.as_str() {
+                "calver" => VersionScheme::Calver(options.calver_format),
+                _ => VersionScheme::Semver,
+            },
+            // --bump prerelease is --prerelease with the bump derived from commits
+            bump: options.bump.clone().filter(|b| b != "prerelease"),
+            release_as: options.release_as,
+            initial_development: options.initial_development,
+            prerelease: options.prerelease || options.bump.as_deref() == Some("prerelease"),
+            configured_prerelease: options.prerelease,
             suffix: options.prerelease_suffix,
+            prerelease_channels: options.prerelease_channels,
             none_string_token: options.none_string_token,
+            release_skip_markers: options.release_skip_markers,
+            release_ignore_paths: options.release_ignore_paths,
             force_without_change: options.force_without_change,
             tag_message: options.tag_message.unwrap_or_default(),
+            // None defers to git's tag.gpgSign
+            sign: if options.sign {
+                Some(true)
+            } else if options.no_sign {
+                Some(false)
+            } else {
+                None
+            },
             not_publish: options.not_publish,
+            publish_release: options.publish_release,
+            no_hooks: options.no_hooks,
+            release_guards: options.release_guards,
+            skip_tag_collisions: options.on_tag_collision == "skip",
+            all_tags: options.all_tags,
             // default to fetching unless --no-fetch is explicitly passed; --fetch enforces true
             fetch: if options.fetch {
                 true
@@ -117,6 +327,7 @@ impl TagGenerator {
             current_tag: String::new(),
             new_tag: String::new(),
             is_pre_release: false,
+            previous_tag: None,
         }
     }
 
@@ -124,17 +335,616 @@ impl TagGenerator {
         self.fetch
     }
 
+    /// Whether the new tag is pushed to `origin`: not with `--dry-run` or `--not-publish`.
+    fn will_push(&self) -> bool {
+        !self.dry_run && !self.not_publish
+    }
+
     pub fn run(&mut self) -> Result<(), CliError> {
         info!("🚀 Starting tag generation process");
+        match self.plan()? {
+            Some(_) => self.apply(),
+            None => Ok(()),
+        }
+    }
+
+    /// Compute the next tag without creating anything. Returns `None` when there are
+    /// no new commits since the previous tag. Can be called again after changing the
+    /// bump with [`TagGenerator::set_bump`] or [`TagGenerator::set_release_as`].
+    pub fn plan(&mut self) -> Result<Option<TagPlan>, CliError> {
         let repo = self.open_repository()?;
-        let current_branch = self.get_current_branch(&repo)?;
+        let (tag, pre_tag, pre_release) = self.prepare(&repo)?;
+        let tag_commit = self.get_commit_for_tag(&repo, &tag)?;
+        let current_commit = self.get_current_commit(&repo)?;
+
+        info!("📌 Latest tag: {tag}, Latest pre-release tag: {pre_tag}");
+
+        if self.should_skip_tagging(tag_commit, current_commit) {
+            info!("⏭️ No new commits since previous tag. Skipping...");
+            return Ok(None);
+        }
+
+        let since = self.release_base(&repo, &tag, &pre_tag, pre_release)?;
+
+        if !self.has_explicit_version()
+            && !self.force_without_change
+            && self.releasable_commits(&repo, &since)?.is_empty()
+        {
+            info!("⏭️ No releasable commits since {since}. Skipping...");
+            return Ok(None);
+        }
+
+        self.check_release_guards(&repo, &since)?;
+
+        self.new_tag = self.calculate_new_tag(&repo, &tag, &pre_tag, pre_release)?;
+        info!("🆕 Calculated new tag: {}", self.new_tag);
+        if let Some(line) = &self.release_line {
+            if !self.in_release_line(&self.new_tag) {
+                return Err(CliError::InputError(format!(
+                    "{} would leave maintenance line {line}; {} only allows {} releases",
+                    self.new_tag,
+                    self.current_tag,
+                    line.allowed_bumps()
+                )));
+            }
+        }
+        self.new_tag = self.resolve_tag_collision(&repo, &self.new_tag)?;
+        self.previous_tag = Some(since.clone());
+
+        Ok(Some(TagPlan {
+            bump: self.describe_bump(&since, &self.new_tag),
+            previous_tag: since,
+            new_tag: self.new_tag.clone(),
+            pre_release,
+        }))
+    }
+
+    /// The tag the next release starts from. Release notes and checks of a pre-release
+    /// start at the previous pre-release.
+    fn release_base(
+        &self,
+        repo: &Repository,
+        tag: &str,
+        pre_tag: &str,
+        pre_release: bool,
+    ) -> Result<String, CliError> {
+        if pre_release
+            && self.compare_versions(pre_tag, tag).is_gt()
+            && self.get_commit_for_tag(repo, pre_tag)?.is_some()
+        {
+            Ok(pre_tag.to_string())
+        } else {
+            Ok(tag.to_string())
+        }
+    }
+
+    /// Lint the commits the next release would contain. Returns the tag the release
+    /// starts from along with the issues found.
+    pub fn lint_release(&mut self) -> Result<(String, Vec<CommitIssue>), CliError> {
+        let repo = self.open_repository()?;
+        let (tag, pre_tag, pre_release) = self.prepare(&repo)?;
+        let since = self.release_base(&repo, &tag, &pre_tag, pre_release)?;
+        let issues = CommitLinter::new(&self.source)?.check_range(
+            self.get_commit_for_tag(&repo, &since)?,
+            self.get_current_commit(&repo)?,
+        )?;
+        Ok((since, issues))
+    }
+
+    /// Continue a release planned earlier (e.g. by an interrupted `committy release`)
+    /// without computing the version again.
+    pub fn resume(&mut self, plan: &TagPlan) -> Result<(), CliError> {
+        let repo = self.open_repository()?;
+        self.tag_format = self.resolve_tag_format()?;
+        self.current_tag = self.get_current_branch(&repo)?;
+        self.new_tag = plan.new_tag.clone();
+        self.previous_tag = Some(plan.previous_tag.clone());
+        self.is_pre_release = plan.pre_release;
+        Ok(())
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// The tag the last [`TagGenerator::plan`] started from.
+    pub fn previous_tag(&self) -> Option<&str> {
+        self.previous_tag.as_deref()
+    }
+
+    /// Whether the release is pushed to `origin` (not with `--not-publish`).
+    pub fn publishes(&self) -> bool {
+        !self.not_publish
+    }
+
+    /// Create (and unless disabled, push) the tag computed by [`TagGenerator::plan`],
+    /// bumping and committing version files first when enabled. Release hooks from
+    /// `.committy.toml` run around each step; files they change go into the bump commit.
+    pub fn apply(&mut self) -> Result<(), CliError> {
+        if self.dry_run {
+            info!("🧪 Dry run: New tag would be {}", self.new_tag);
+            return Ok(());
+        }
+        let repo = self.open_repository()?;
+        let workdir = repo.workdir().map(Path::to_path_buf).unwrap_or_default();
+        let hooks = self.release_hooks(&repo)?;
+        let context = self.hook_context(&repo)?;
+        let new_version = context.new_version.clone();
+        let dirty_before = self.dirty_paths(&repo)?;
+        // Fail before changing anything when the release cannot be published
+        let publish_target = self.publish_target(&repo)?;
+        if let Some(target) = &publish_target {
+            target.token()?;
+        }
+
+        run_hooks(&hooks, HookStage::PreBump, &context, &workdir)?;
+        let mut changed_files = Vec::new();
+        if self.bump_config_files {
+            changed_files = self.update_versions(&new_version)?;
+            if !changed_files.is_empty() {
+                info!("📝 Updated version in files: {}", changed_files.join(", "));
+            }
+        }
+        run_hooks(&hooks, HookStage::PostBump, &context, &workdir)?;
+
+        // Files changed by hooks go into the bump commit, changes made beforehand do not
+        for path in self.dirty_paths(&repo)? {
+            if !dirty_before.contains(&path) && !changed_files.contains(&path) {
+                changed_files.push(path);
+            }
+        }
+        if self
+            .commit_version_changes(&repo, &new_version, &changed_files)?
+            .is_some()
+        {
+            info!("✅ Committed version changes");
+            self.push_branch(&repo)?;
+        }
+
+        run_hooks(&hooks, HookStage::PreTag, &context, &workdir)?;
+        self.create_tag(&repo, &self.new_tag)?;
+        run_hooks(&hooks, HookStage::PostTag, &context, &workdir)?;
+        if self.push_tag(&repo, &self.new_tag)? {
+            run_hooks(&hooks, HookStage::PostPush, &context, &workdir)?;
+            if let Some(target) = &publish_target {
+                self.publish_release(&repo, target)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Where the forge release is published: `None` unless enabled with
+    /// `--publish-release` or `enabled` in the `[publish]` section, or with `--not-publish`.
+    pub fn publish_target(&self, repo: &Repository) -> Result<Option<PublishTarget>, CliError> {
+        if self.not_publish {
+            return Ok(None);
+        }
+        let workdir = repo.workdir().map(Path::to_path_buf).unwrap_or_default();
+        let config = RepoConfig::load_for(&workdir)
+            .map_err(CliError::ConfigError)?
+            .publish;
+        if !self.publish_release && !config.enabled {
+            return Ok(None);
+        }
+        let origin = repo.find_remote("origin").ok();
+        PublishTarget::resolve(&config, origin.as_ref().and_then(|r| r.url())).map(Some)
+    }
+
+    /// Create or update the forge release of the new tag, with the release notes as body.
+    pub fn publish_release(
+        &self,
+        repo: &Repository,
+        target: &PublishTarget,
+    ) -> Result<PublishedRelease, CliError> {
+        let request = ReleaseRequest {
+            tag: self.new_tag.clone(),
+            version: self.tag_format.version_of(&self.new_tag).to_string(),
+            name: self.new_tag.clone(),
+            body: self.release_notes(repo)?,
+            prerelease: self.is_pre_release,
+        };
+        let workdir = repo.workdir().unwrap_or_else(|| Path::new("."));
+        target.publish(&request, workdir)
+    }
+
+    /// Release hooks from `.committy.toml`, none with `--no-hooks`.
+    pub fn release_hooks(&self, repo: &Repository) -> Result<ReleaseHooks, CliError> {
+        if self.no_hooks {
+            return Ok(ReleaseHooks::default());
+        }
+        let workdir = repo.workdir().map(Path::to_path_buf).unwrap_or_default();
+        Ok(RepoConfig::load_for(&workdir)
+            .map_err(CliError::ConfigError)?
+            .hooks)
+    }
+
+    pub fn hook_context(&self, repo: &Repository) -> Result<HookContext, CliError> {
+        let previous_tag = self.previous_tag.clone().unwrap_or_default();
+        Ok(HookContext {
+            previous_version: self.tag_format.version_of(&previous_tag).to_string(),
+            previous_tag,
+            new_version: self.tag_format.version_of(&self.new_tag).to_string(),
+            new_tag: self.new_tag.clone(),
+            branch: self.get_current_branch(repo)?,
+            pre_release: self.is_pre_release,
+        })
+    }
+
+    /// Paths with uncommitted changes, untracked files included.
+    pub fn dirty_paths(&self, repo: &Repository) -> Result<HashSet<String>, CliError> {
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .include_ignored(false)
+            .exclude_submodules(true);
+        Ok(repo
+            .statuses(Some(&mut opts))?
+            .iter()
+            .filter_map(|entry| entry.path().map(String::from))
+            .collect())
+    }
+
+    /// `--release-guards`, falling back to `release_guards` from `.committy.toml`.
+    fn release_guard_names(&self) -> Result<Vec<String>, CliError> {
+        let guards = match &self.release_guards {
+            Some(spec) => split_list(spec),
+            None => self.repo_config()?.release_guards,
+        };
+        for guard in &guards {
+            if !RELEASE_GUARDS.contains(&guard.as_str()) {
+                return Err(CliError::InputError(format!(
+                    "Unknown release guard '{guard}', expected one of: {}",
+                    RELEASE_GUARDS.join(", ")
+                )));
+            }
+        }
+        Ok(guards)
+    }
+
+    /// Refuse to release when an enabled guard fails. All failures are reported at once.
+    fn check_release_guards(&self, repo: &Repository, since: &str) -> Result<(), CliError> {
+        let guards = self.release_guard_names()?;
+        let mut failures = Vec::new();
+
+        for guard in &guards {
+            debug!("Checking release guard: {guard}");
+            match guard.as_str() {
+                "lint" => {
+                    let issues = CommitLinter::new(&self.source)?.check_range(
+                        self.get_commit_for_tag(repo, since)?,
+                        self.get_current_commit(repo)?,
+                    )?;
+                    if !issues.is_empty() {
+                        let details: Vec<String> = issues
+                            .iter()
+                            .map(|i| format!("  {} {}", &i.commit_id[..7], i.issue))
+                            .collect();
+                        failures.push(format!(
+                            "lint: {} commit(s) since {since} have lint issues\n{}",
+                            issues.len(),
+                            details.join("\n")
+                        ));
+                    }
+                }
+                "clean" => {
+                    if self.is_worktree_dirty(repo)? {
+                        failures.push("clean: the worktree has uncommitted changes".to_string());
+                    }
+                }
+                "release-branch" => {
+                    if self.release_line.is_none() && self.is_pre_release(&self.current_tag) {
+                        failures.push(format!(
+                            "release-branch: {} is not a release branch ({})",
+                            self.current_tag,
+                            self.release_branches.join(", ")
+                        ));
+                    }
+                }
+                "upstream" => {
+                    if let Some(failure) = self.check_upstream(repo)? {
+                        failures.push(format!("upstream: {failure}"));
+                    }
+                }
+                _ => unreachable!("release guards are validated"),
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(CliError::ReleaseGuards(failures.join("\n")))
+        }
+    }
+
+    /// Compare the current branch with its upstream as last fetched.
+    fn check_upstream(&self, repo: &Repository) -> Result<Option<String>, CliError> {
+        let branch = match repo.find_branch(&self.current_tag, git2::BranchType::Local) {
+            Ok(branch) => branch,
+            Err(_) => return Ok(Some("HEAD is not on a local branch".to_string())),
+        };
+        let upstream = match branch.upstream() {
+            Ok(upstream) => upstream,
+            Err(_) => return Ok(Some(format!("{} has no upstream branch", self.current_tag))),
+        };
+        let (Some(local), Some(remote)) = (branch.get().target(), upstream.get().target()) else {
+            return Ok(Some(
+                "cannot resolve the branch or its upstream".to_string(),
+            ));
+        };
+        let (ahead, behind) = repo.graph_ahead_behind(local, remote)?;
+        let upstream_name = upstream.name()?.unwrap_or_default().to_string();
+        Ok(match (ahead, behind) {
+            (0, 0) => None,
+            (0, behind) => Some(format!(
+                "{} is {behind} commit(s) behind {upstream_name}",
+                self.current_tag
+            )),
+            (ahead, 0) => Some(format!(
+                "{} is {ahead} commit(s) ahead of {upstream_name}, push first",
+                self.current_tag
+            )),
+            (ahead, behind) => Some(format!(
+                "{} has diverged from {upstream_name} ({ahead} ahead, {behind} behind)",
+                self.current_tag
+            )),
+        })
+    }
+
+    /// Make sure the new tag exists neither locally nor on `origin` (when it is going to
+    /// be pushed). With `--on-tag-collision skip`, move on to the next free version.
+    fn resolve_tag_collision(&self, repo: &Repository, new_tag: &str) -> Result<String, CliError> {
+        let mut taken: HashSet<String> = repo
+            .tag_names(None)?
+            .iter()
+            .flatten()
+            .map(String::from)
+            .collect();
+        // Only a tag that is pushed can collide with the remote's, fetched or not
+        if self.will_push() {
+            taken.extend(self.remote_tag_names(repo)?);
+        }
+
+        let mut candidate = new_tag.to_string();
+        while taken.contains(&candidate) {
+            if !self.skip_tag_collisions {
+                return Err(CliError::TagExists(candidate));
+            }
+            let next = self.next_free_candidate(&candidate)?;
+            info!("⏭️ Tag {candidate} already exists, trying {next}");
+            candidate = next;
+        }
+        Ok(candidate)
+    }
+
+    /// The version right after `tag`: the next pre-release number for pre-releases,
+    /// otherwise the next patch (or the next MICRO for CalVer).
+    fn next_free_candidate(&self, tag: &str) -> Result<String, CliError> {
+        let version = self.tag_format.version_of(tag);
+        let (base, pre) = split_pre_release(version);
+        if let Some(pre) = pre {
+            let next = Regex::new(r"(\d+)$")
+                .unwrap()
+                .replace(pre, |caps: &regex::Captures| {
+                    (caps[1].parse::<u64>().unwrap_or(0) + 1).to_string()
+                });
+            return Ok(self.tag_format.format(&format!("{base}-{next}")));
+        }
+        let next = match &self.scheme {
+            VersionScheme::Calver(format) => format.next(Some(base), Utc::now().date_naive())?,
+            VersionScheme::Semver => {
+                let mut next =
+                    Version::parse(base).map_err(|e| CliError::SemVerError(e.to_string()))?;
+                self.apply_bump(&mut next, "patch");
+                next.to_string()
+            }
+        };
+        Ok(self.tag_format.format(&next))
+    }
+
+    /// Tag names on `origin`, listed with the credentials used to fetch and push tags. A
+    /// remote on this machine is read directly, as libgit2 cannot list an empty one.
+    fn remote_tag_names(&self, repo: &Repository) -> Result<Vec<String>, CliError> {
+        let mut remote = match repo.find_remote("origin") {
+            Ok(remote) => remote,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        if let Some(path) = remote.url().and_then(local_remote_path) {
+            let origin = Repository::open(&path).map_err(|e| {
+                CliError::RemoteNotFound(format!("Cannot open origin at {}: {e}", path.display()))
+            })?;
+            return Ok(origin
+                .tag_names(None)?
+                .iter()
+                .flatten()
+                .map(String::from)
+                .collect());
+        }
+        let connection = remote
+            .connect_auth(Direction::Fetch, Some(remote_callbacks()), None)
+            .map_err(|e| match (e.code(), e.class()) {
+                (git2::ErrorCode::Auth, _) => {
+                    CliError::AuthError(format!("Cannot list the tags of origin: {e}"))
+                }
+                (git2::ErrorCode::NotFound, _) | (_, git2::ErrorClass::Net) => {
+                    CliError::RemoteNotFound(format!("Cannot reach origin to list its tags: {e}"))
+                }
+                _ => CliError::from(e),
+            })?;
+        Ok(connection
+            .list()?
+            .iter()
+            .filter_map(|head| head.name().strip_prefix("refs/tags/"))
+            .map(|name| name.trim_end_matches("^{}").to_string())
+            .collect())
+    }
+
+    /// Force the bump level (`major`, `minor`, `patch`) or, with `prerelease`, a
+    /// pre-release of the computed version. Clears `--release-as`; other levels undo an
+    /// earlier `prerelease` choice.
+    pub fn set_bump(&mut self, level: &str) {
+        self.release_as = None;
+        if level == "prerelease" {
+            self.prerelease = true;
+            self.bump = None;
+        } else {
+            self.prerelease = self.configured_prerelease;
+            self.bump = Some(level.to_string());
+        }
+    }
+
+    /// Release exactly `version` (a leading `v` is accepted). Clears `--bump` and an
+    /// earlier `prerelease` choice.
+    pub fn set_release_as(&mut self, version: &str) -> Result<(), CliError> {
+        self.release_as =
+            Some(parse_release_as(version).map_err(|e| CliError::SemVerError(e.to_string()))?);
+        self.prerelease = self.configured_prerelease;
+        self.bump = None;
+        Ok(())
+    }
+
+    /// Human readable level between two tags: `major`, `minor`, `patch`, `prerelease`,
+    /// `calendar` for CalVer, or `none`.
+    fn describe_bump(&self, previous_tag: &str, new_tag: &str) -> String {
+        if let VersionScheme::Calver(_) = self.scheme {
+            return "calendar".to_string();
+        }
+        let (Ok(previous), Ok(new)) = (
+            Version::parse(self.tag_format.version_of(previous_tag)),
+            Version::parse(self.tag_format.version_of(new_tag)),
+        ) else {
+            return "none".to_string();
+        };
+        let level = if !new.pre.is_empty() {
+            "prerelease"
+        } else if new.major != previous.major {
+            "major"
+        } else if new.minor != previous.minor {
+            "minor"
+        } else if new.patch != previous.patch || previous.pre != new.pre {
+            "patch"
+        } else {
+            "none"
+        };
+        level.to_string()
+    }
+
+    /// Compute a `git describe`-style development version for untagged builds,
+    /// e.g. `1.3.0-dev.4+g1a2b3c4`. When HEAD is exactly on the latest tag and the
+    /// tree is clean, the tag's version is returned as is. Nothing is written or tagged.
+    ///
+    /// `format` accepts `{version}`, `{commits}`, `{sha}`, `{branch}` and `{dirty}`
+    /// (`.dirty` when `mark_dirty` is set and the worktree has uncommitted changes).
+    pub fn compute_dev_version(
+        &mut self,
+        format: &str,
+        mark_dirty: bool,
+    ) -> Result<DevVersion, CliError> {
+        let repo = self.open_repository()?;
+        let (tag, pre_tag, pre_release) = self.prepare(&repo)?;
+
+        // Count from whichever tag the next version would be calculated from
+        let base_tag = if self.compare_versions(&pre_tag, &tag).is_gt()
+            && self.get_commit_for_tag(&repo, &pre_tag)?.is_some()
+        {
+            pre_tag.clone()
+        } else {
+            tag.clone()
+        };
+        let commits = self.commits_since_tag(&repo, &base_tag)?.len();
+        let head = self.get_current_commit(&repo)?;
+        let sha = repo
+            .find_object(head, None)?
+            .short_id()?
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let dirty = mark_dirty && self.is_worktree_dirty(&repo)?;
+        debug!("Dev version base: {base_tag}, commits since: {commits}, dirty: {dirty}");
+
+        let version = if commits == 0 && !dirty {
+            self.tag_format.version_of(&base_tag).to_string()
+        } else {
+            let next = self.next_dev_core_version(&repo, &tag, &pre_tag, pre_release, commits)?;
+            let branch = self.get_current_branch(&repo)?;
+            let rendered = format
+                .replace("{version}", &next)
+                .replace("{commits}", &commits.to_string())
+                .replace("{sha}", &sha)
+                .replace("{branch}", &sanitize_prerelease_identifier(&branch))
+                .replace("{dirty}", if dirty { ".dirty" } else { "" });
+            if let VersionScheme::Semver = self.scheme {
+                Version::parse(&rendered).map_err(|e| {
+                    CliError::SemVerError(format!(
+                        "Dev version format produced an invalid version '{rendered}': {e}"
+                    ))
+                })?;
+            }
+            rendered
+        };
+
+        info!("🧪 Development version: {version}");
+        Ok(DevVersion {
+            version,
+            base_tag,
+            commits_since_tag: commits,
+            sha,
+            dirty,
+        })
+    }
+
+    /// The release the development build leads up to, without any pre-release part.
+    fn next_dev_core_version(
+        &self,
+        repo: &Repository,
+        tag: &str,
+        pre_tag: &str,
+        pre_release: bool,
+        commits: usize,
+    ) -> Result<String, CliError> {
+        if let VersionScheme::Calver(format) = &self.scheme {
+            let latest = self.tag_format.version_of(tag);
+            return format.next(Some(latest), Utc::now().date_naive());
+        }
+
+        let base_version = Version::parse(self.tag_format.version_of(tag))
+            .map_err(|e| CliError::SemVerError(e.to_string()))?;
+        let mut next = if commits == 0 {
+            base_version.clone()
+        } else {
+            let next_tag = self.calculate_new_tag(repo, tag, pre_tag, pre_release)?;
+            Version::parse(self.tag_format.version_of(&next_tag))
+                .map_err(|e| CliError::SemVerError(e.to_string()))?
+        };
+        // Dev versions describe the upcoming release line, not a pre-release of it
+        next.pre = semver::Prerelease::EMPTY;
+        next.build = semver::BuildMetadata::EMPTY;
+        if next <= base_version {
+            self.apply_bump(&mut next, "patch");
+        }
+        Ok(next.to_string())
+    }
+
+    /// Resolve branch, pre-release mode and channel, fetch tags if needed and return
+    /// the latest regular and pre-release tags.
+    fn prepare(&mut self, repo: &Repository) -> Result<(String, String, bool), CliError> {
+        self.tag_format = self.resolve_tag_format()?;
+        let current_branch = self.get_current_branch(repo)?;
+        if let VersionScheme::Semver = self.scheme {
+            self.release_line =
+                ReleaseLine::from_branch(&self.maintenance_branches, &current_branch);
+        }
+        // Maintenance branches release like release branches, within their line
         let pre_release = if !self.prerelease {
-            self.is_pre_release(&current_branch)
+            self.release_line.is_none() && self.is_pre_release(&current_branch)
         } else {
             self.prerelease
         };
 
         info!("📊 Current branch: {current_branch}");
+        if let Some(line) = &self.release_line {
+            info!("🛠️ Maintenance release line: {line}");
+        }
         info!(
             "🏷️ Pre-release mode: {}",
             if pre_release { "Yes" } else { "No" }
@@ -145,81 +955,107 @@ impl TagGenerator {
         self.current_tag = current_branch.clone();
         self.is_pre_release = pre_release;
 
+        if pre_release {
+            self.suffix = self.resolve_prerelease_channel(&current_branch)?;
+            info!("🧭 Pre-release channel: {}", self.suffix);
+        }
+
         if self.should_fetch() {
             info!("🔄 Fetching tags from remote");
-            self.fetch_tags(&repo)?;
+            self.fetch_tags(repo)?;
+            // Tags are up to date for any further planning with this generator
+            self.fetch = false;
         } else {
             debug!("Skipping remote tag fetch (fetch flag not set)");
         }
 
-        let (tag, pre_tag) = self.get_latest_tags(&repo)?;
-        let tag_commit = self.get_commit_for_tag(&repo, &tag)?;
-        let current_commit = self.get_current_commit(&repo)?;
-
-        info!("📌 Latest tag: {tag}, Latest pre-release tag: {pre_tag}");
-
-        if self.should_skip_tagging(tag_commit, current_commit) {
-            info!("⏭️ No new commits since previous tag. Skipping...");
-            return Ok(());
-        }
-
-        self.new_tag = self.calculate_new_tag(&repo, &tag, &pre_tag, pre_release)?;
-        info!("🆕 Calculated new tag: {}", self.new_tag);
-
-        if self.dry_run {
-            info!("🧪 Dry run: New tag would be {}", self.new_tag);
-            return Ok(());
-        }
+        let (tag, pre_tag) = self.get_latest_tags(repo)?;
+        Ok((tag, pre_tag, pre_release))
+    }
 
-        // Update version files and commit changes
-        if self.bump_config_files {
-            let updated_files = self.update_versions(&self.new_tag)?;
-            if !updated_files.is_empty() {
-                info!("📝 Updated version in files: {}", updated_files.join(", "));
-                self.commit_version_changes(&repo, &self.new_tag, &updated_files)?;
-                info!("✅ Committed version changes");
-            }
+    /// `--tag-format`, then `tag_format` from `.committy.toml`, then `v{version}` (or
+    /// `{version}` with `--not-with-v`).
+    fn resolve_tag_format(&self) -> Result<TagFormat, CliError> {
+        let template = match &self.tag_template {
+            Some(template) => template.clone(),
+            None => self.repo_config()?.tag_format,
+        };
+        if template.is_empty() {
+            Ok(TagFormat::legacy(!self.not_with_v))
+        } else {
+            TagFormat::parse(&template, self.package.as_deref())
         }
-
-        self.create_and_push_tag(&repo, &self.new_tag)?;
-        Ok(())
     }
 
     pub fn open_repository(&self) -> Result<Repository, CliError> {
         Repository::open(&self.source).map_err(CliError::from)
     }
 
+    /// `.committy.toml` of the repository.
+    fn repo_config(&self) -> Result<RepoConfig, CliError> {
+        RepoConfig::load_for(Path::new(&self.source)).map_err(CliError::ConfigError)
+    }
+
+    fn workdir(&self) -> Result<std::path::PathBuf, CliError> {
+        let repo = self.open_repository()?;
+        repo.workdir().map(Path::to_path_buf).ok_or_else(|| {
+            CliError::GitError(git2::Error::new(
+                git2::ErrorCode::NotFound,
+                git2::ErrorClass::Repository,
+                "Repository has no working directory",
+            ))
+        })
+    }
+
     fn get_current_branch(&self, repo: &Repository) -> Result<String, CliError> {
-        repo.head()?
-            .shorthand()
-            .map(String::from)
-            .ok_or_else(|| CliError::Generic("Failed to get current branch".to_string()))
+        repo.head()?.shorthand().map(String::from).ok_or_else(|| {
+            CliError::GitError(git2::Error::from_str("Failed to get current branch"))
+        })
     }
 
     fn is_pre_release(&self, current_branch: &str) -> bool {
-        !self.release_branches.iter().any(|b| {
-            current_branch == b
-                || (b.contains('*') && current_branch.starts_with(b.trim_end_matches('*')))
-        })
+        !self
+            .release_branches
+            .iter()
+            .any(|b| branch_matches(b, current_branch))
+    }
+
+    fn channel_mapping(&self) -> Result<Vec<PrereleaseChannel>, CliError> {
+        match &self.prerelease_channels {
+            Some(spec) => parse_prerelease_channels(spec),
+            None => Ok(self.repo_config()?.prerelease_channels),
+        }
+    }
+
+    /// Pick the pre-release channel for a branch: the first matching mapping entry,
+    /// otherwise the `--prerelease-suffix` value.
+    fn resolve_prerelease_channel(&self, current_branch: &str) -> Result<String, CliError> {
+        let mapping = self.channel_mapping()?;
+        let channel = match mapping
+            .iter()
+            .find(|c| branch_matches(&c.branch, current_branch))
+        {
+            Some(entry) => entry
+                .channel
+                .replace("{branch}", &sanitize_prerelease_identifier(current_branch)),
+            None => return Ok(self.suffix.clone()),
+        };
+
+        let valid = Regex::new(r"^[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*$").unwrap();
+        if !valid.is_match(&channel) {
+            return Err(CliError::ConfigError(format!(
+                "Invalid pre-release channel '{channel}' for branch '{current_branch}'"
+            )));
+        }
+        debug!("Branch {current_branch} maps to pre-release channel {channel}");
+        Ok(channel)
     }
 
     fn fetch_tags(&self, repo: &Repository) -> Result<(), CliError> {
         debug!("Fetching tags from remote");
         match repo.find_remote("origin") {
             Ok(mut remote) => {
-                let mut callbacks = RemoteCallbacks::new();
-
-                callbacks.credentials(|_url, username_from_url, _allowed_types| {
-                    git2::Cred::ssh_key(
-                        username_from_url.unwrap_or("git"),
-                        None,
-                        std::path::Path::new(&format!(
-                            "{}/.ssh/id_rsa",
-                            std::env::var("HOME").unwrap()
-                        )),
-                        None,
-                    )
-                });
+                let callbacks = remote_callbacks();
 
                 let mut fetch_options = FetchOptions::new();
                 fetch_options.remote_callbacks(callbacks);
@@ -250,19 +1086,39 @@ impl TagGenerator {
 
     fn get_latest_tags(&self, repo: &Repository) -> Result<(String, String), CliError> {
         debug!("Getting latest tags");
-        let tag_regex = regex::Regex::new(r"^v?[0-9]+\.[0-9]+\.[0-9]+$").unwrap();
-        let pre_tag_regex = regex::Regex::new(&format!(
-            r"^v?[0-9]+\.[0-9]+\.[0-9]+(-{}\.{{0,1}}[0-9]+)$",
-            self.suffix
-        ))
+        let version_pattern = self.scheme.version_pattern();
+        let tag_regex = regex::Regex::new(&self.tag_format.pattern(&version_pattern)).unwrap();
+        let pre_tag_regex = regex::Regex::new(&self.tag_format.pattern(&format!(
+            r"{version_pattern}(-{}\.{{0,1}}[0-9]+)",
+            regex::escape(&self.suffix)
+        )))
         .unwrap();
 
+        // Tags on unmerged branches must not influence the version of HEAD
+        let head = if self.all_tags {
+            None
+        } else {
+            repo.head()
+                .ok()
+                .and_then(|head| head.peel_to_commit().ok())
+                .map(|commit| commit.id())
+        };
         let mut tags = repo
             .tag_names(None)?
             .iter()
             .filter_map(|t| t.map(String::from))
+            .filter(|t| self.in_release_line(t))
+            .filter(|t| head.is_none_or(|head| self.is_reachable(repo, head, t)))
             .collect::<Vec<_>>();
 
+        if let Some(line) = &self.release_line {
+            if !tags.iter().any(|t| tag_regex.is_match(t)) {
+                return Err(CliError::InputError(format!(
+                    "No release tag found on maintenance line {line}; tag its first release before releasing from this branch"
+                )));
+            }
+        }
+
         tags.sort_by(|a, b| self.compare_versions(b, a)); // Reverse the comparison order
 
         let tag = tags
@@ -282,14 +1138,35 @@ impl TagGenerator {
         Ok((tag, pre_tag))
     }
 
+    /// Whether the tag's commit is HEAD or one of its ancestors.
+    fn is_reachable(&self, repo: &Repository, head: Oid, tag: &str) -> bool {
+        match self.get_commit_for_tag(repo, tag) {
+            Ok(Some(commit)) => {
+                commit == head || repo.graph_descendant_of(head, commit).unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether a tag belongs to the current maintenance line (always true outside one).
+    fn in_release_line(&self, tag: &str) -> bool {
+        let Some(line) = &self.release_line else {
+            return true;
+        };
+        Version::parse(self.tag_format.version_of(tag)).is_ok_and(|v| line.contains(&v))
+    }
+
     fn compare_versions(&self, a: &str, b: &str) -> std::cmp::Ordering {
         debug!("Comparing versions: {a} and {b}");
         if a.contains("none") || b.contains("none") {
             return a.cmp(b);
         }
+        if let VersionScheme::Calver(format) = &self.scheme {
+            return format.compare(self.tag_format.version_of(a), self.tag_format.version_of(b));
+        }
         match (
-            Version::parse(a.trim_start_matches('v')),
-            Version::parse(b.trim_start_matches('v')),
+            Version::parse(self.tag_format.version_of(a)),
+            Version::parse(self.tag_format.version_of(b)),
         ) {
             (Ok(a_version), Ok(b_version)) => a_version.cmp(&b_version),
             _ => a.cmp(b),
@@ -311,8 +1188,20 @@ impl TagGenerator {
             .map_err(CliError::from)
     }
 
+    fn is_worktree_dirty(&self, repo: &Repository) -> Result<bool, CliError> {
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(false)
+            .include_ignored(false)
+            .exclude_submodules(true)
+            .show(StatusShow::IndexAndWorkdir);
+        Ok(!repo.statuses(Some(&mut opts))?.is_empty())
+    }
+
     fn should_skip_tagging(&self, tag_commit: Option<Oid>, current_commit: Oid) -> bool {
-        tag_commit.is_some_and(|commit| commit == current_commit && !self.force_without_change)
+        // An explicit --release-as may re-version an already tagged commit (e.g. graduating to 1.0.0)
+        tag_commit.is_some_and(|commit| {
+            commit == current_commit && !self.force_without_change && self.release_as.is_none()
+        })
     }
 
     fn calculate_new_tag(
@@ -327,19 +1216,37 @@ impl TagGenerator {
         );
         use semver::Version as SemverVersion;
 
+        if let VersionScheme::Calver(format) = &self.scheme {
+            if self.has_explicit_version() {
+                return Err(CliError::InputError(
+                    "--bump and --release-as are only supported with the semver scheme".to_string(),
+                ));
+            }
+            return self.calculate_calver_tag(repo, format, tag, pre_tag, pre_release);
+        }
+
         if pre_release {
             // Parse both tags
-            let reg_ver = SemverVersion::parse(tag.trim_start_matches('v'))
+            let reg_ver = SemverVersion::parse(self.tag_format.version_of(tag))
                 .unwrap_or_else(|_| SemverVersion::new(0, 0, 0));
             let pre_ver = SemverVersion::parse(
-                pre_tag
-                    .trim_start_matches('v')
+                self.tag_format
+                    .version_of(pre_tag)
                     .split('-')
                     .next()
                     .unwrap_or(""),
             )
             .unwrap_or_else(|_| SemverVersion::new(0, 0, 0));
 
+            // An explicit --bump/--release-as beyond the running pre-release starts a new one
+            if self.has_explicit_version() {
+                let target = self.bumped_version(repo, tag)?;
+                if target > pre_ver {
+                    let new_tag = self.calculate_pre_release_tag(&target, pre_tag);
+                    return Ok(self.tag_format.format(&new_tag));
+                }
+            }
+
             // If pre_tag version is higher than regular tag, we're already on a pre-release
             // In this case, only increment the pre-release counter, don't apply bump
             if pre_ver > reg_ver {
@@ -348,93 +1255,168 @@ impl TagGenerator {
 
                 // Check if there are any commits - if not, no new tag needed
                 if log.trim().is_empty() {
-                    return Err(CliError::Generic(
+                    return Err(CliError::InputError(
                         "No new commits since last pre-release tag".to_string(),
                     ));
                 }
 
                 // Just increment the pre-release counter
                 let new_tag = self.calculate_pre_release_tag(&pre_ver, pre_tag);
-                return Ok(if !self.not_with_v {
-                    format!("v{new_tag}")
-                } else {
-                    new_tag
-                });
+                return Ok(self.tag_format.format(&new_tag));
             }
 
             // Pre-release is not ahead, apply bump from regular tag
             debug!("Starting new pre-release from regular tag {tag}");
-            let log = self.get_commit_log(repo, tag)?;
-            let bump: &str = self.determine_bump(&log)?;
-            let mut new_version = SemverVersion::parse(tag.trim_start_matches('v'))
-                .map_err(|e| CliError::SemVerError(e.to_string()))?;
-            self.apply_bump(&mut new_version, bump);
+            let new_version = self.bumped_version(repo, tag)?;
 
             let new_tag = self.calculate_pre_release_tag(&new_version, pre_tag);
-            Ok(if !self.not_with_v {
-                format!("v{new_tag}")
-            } else {
-                new_tag
-            })
+            Ok(self.tag_format.format(&new_tag))
         } else {
             // Regular release
             // Parse both tags to compare versions
-            let reg_ver = SemverVersion::parse(tag.trim_start_matches('v'))
+            let reg_ver = SemverVersion::parse(self.tag_format.version_of(tag))
                 .unwrap_or_else(|_| SemverVersion::new(0, 0, 0));
             let pre_ver = SemverVersion::parse(
-                pre_tag
-                    .trim_start_matches('v')
+                self.tag_format
+                    .version_of(pre_tag)
                     .split('-')
                     .next()
                     .unwrap_or(""),
             )
             .unwrap_or_else(|_| SemverVersion::new(0, 0, 0));
 
-            // If pre-release version is higher than stable tag, promote it to stable
-            if pre_ver > reg_ver {
+            // If pre-release version is higher than stable tag, promote it to stable,
+            // unless an explicit --bump/--release-as goes further
+            let explicit = if self.has_explicit_version() {
+                Some(self.bumped_version(repo, tag)?)
+            } else {
+                None
+            };
+            if let Some(new_version) = explicit.filter(|v| *v > pre_ver) {
+                Ok(self.tag_format.format(&new_version.to_string()))
+            } else if pre_ver > reg_ver {
                 debug!(
                     "Pre-release tag {pre_tag} is ahead of regular tag {tag}, promoting to stable"
                 );
                 // Just remove the pre-release suffix to promote to stable
-                Ok(if !self.not_with_v {
-                    format!("v{}", pre_ver)
-                } else {
-                    pre_ver.to_string()
-                })
+                Ok(self.tag_format.format(&pre_ver.to_string()))
             } else {
                 // Normal bump from stable tag
-                let log = self.get_commit_log(repo, tag)?;
-                let bump: &str = self.determine_bump(&log)?;
-                let mut new_version = SemverVersion::parse(tag.trim_start_matches('v'))
-                    .map_err(|e| CliError::SemVerError(e.to_string()))?;
-                self.apply_bump(&mut new_version, bump);
+                let new_version = self.bumped_version(repo, tag)?;
 
-                Ok(if !self.not_with_v {
-                    format!("v{}", new_version)
-                } else {
-                    new_version.to_string()
-                })
+                Ok(self.tag_format.format(&new_version.to_string()))
+            }
+        }
+    }
+
+    /// CalVer releases are driven by the calendar, not by commit types: the MICRO
+    /// counter continues within a period and resets when the period changes.
+    fn calculate_calver_tag(
+        &self,
+        repo: &Repository,
+        format: &CalverFormat,
+        tag: &str,
+        pre_tag: &str,
+        pre_release: bool,
+    ) -> Result<String, CliError> {
+        let latest = self.tag_format.version_of(tag);
+        let next = format.next(Some(latest), Utc::now().date_naive())?;
+        let (pre_base, _) = split_pre_release(self.tag_format.version_of(pre_tag));
+
+        // A pre-release of the current period ahead of the latest release is continued or promoted
+        let pre_ahead = format.parse_version(pre_base).is_some()
+            && format.compare(pre_base, latest).is_gt()
+            && format.same_period(pre_base, &next);
+
+        let new_tag = if pre_release {
+            if pre_ahead {
+                debug!("Pre-release tag {pre_tag} is ahead of regular tag {tag}, incrementing pre-release counter only");
+                if self.get_commit_log(repo, pre_tag)?.trim().is_empty() {
+                    return Err(CliError::InputError(
+                        "No new commits since last pre-release tag".to_string(),
+                    ));
+                }
+                self.calculate_pre_release_tag(&pre_base, pre_tag)
+            } else {
+                self.calculate_pre_release_tag(&next, pre_tag)
             }
+        } else if pre_ahead {
+            debug!("Pre-release tag {pre_tag} is ahead of regular tag {tag}, promoting to stable");
+            pre_base.to_string()
+        } else {
+            next
+        };
+
+        Ok(self.tag_format.format(&new_tag))
+    }
+
+    fn has_explicit_version(&self) -> bool {
+        self.bump.is_some() || self.release_as.is_some()
+    }
+
+    /// The next release version after `tag`: `--release-as` if given, otherwise `tag`
+    /// bumped by `--bump` or by the level derived from the commits since `tag`.
+    fn bumped_version(&self, repo: &Repository, tag: &str) -> Result<Version, CliError> {
+        let current = Version::parse(self.tag_format.version_of(tag))
+            .map_err(|e| CliError::SemVerError(e.to_string()))?;
+
+        if let Some(target) = &self.release_as {
+            if *target <= current {
+                return Err(CliError::SemVerError(format!(
+                    "--release-as {target} must be greater than the current version {current}"
+                )));
+            }
+            return Ok(target.clone());
         }
+
+        let bump = match &self.bump {
+            Some(bump) => bump.as_str(),
+            None => {
+                let log = self.get_commit_log(repo, tag)?;
+                self.initial_development_bump(&current, self.determine_bump(&log)?)
+            }
+        };
+        let mut new_version = current;
+        self.apply_bump(&mut new_version, bump);
+        Ok(new_version)
+    }
+
+    /// In initial development (major 0), breaking changes bump minor and features bump
+    /// patch, so `0.x` never graduates to `1.0.0` by accident.
+    fn initial_development_bump<'a>(&self, current: &Version, bump: &'a str) -> &'a str {
+        if current.major != 0
+            || !(self.initial_development
+                || config::Config::load()
+                    .unwrap_or_default()
+                    .initial_development)
+        {
+            return bump;
+        }
+        let demoted = match bump {
+            "major" => "minor",
+            "minor" => "patch",
+            other => other,
+        };
+        debug!("Initial development mode: {bump} bump applied as {demoted}");
+        demoted
     }
 
     fn determine_bump(&self, log: &str) -> Result<&str, CliError> {
         debug!("Determining bump from commit log");
         let cfg = config::Config::load().unwrap_or_default();
-        let major_pattern =
-            Regex::new(&cfg.major_regex).map_err(|e| CliError::RegexError(e.to_string()))?;
-        let minor_pattern =
-            Regex::new(&cfg.minor_regex).map_err(|e| CliError::RegexError(e.to_string()))?;
-        let patch_pattern =
-            Regex::new(&cfg.patch_regex).map_err(|e| CliError::RegexError(e.to_string()))?;
-
-        if major_pattern.is_match(log) {
-            Ok("major")
-        } else if minor_pattern.is_match(log) {
-            Ok("minor")
-        } else if patch_pattern.is_match(log) {
-            Ok("patch")
-        } else if log.contains(&self.none_string_token) {
+        let types = self.commit_types();
+
+        for level in [BumpLevel::Major, BumpLevel::Minor, BumpLevel::Patch] {
+            if let Some(pattern) = cfg.bump_regex(level, &types) {
+                let pattern =
+                    Regex::new(&pattern).map_err(|e| CliError::RegexError(e.to_string()))?;
+                if pattern.is_match(log) {
+                    return Ok(level.as_str());
+                }
+            }
+        }
+
+        if log.contains(&self.none_string_token) {
             Ok("none")
         } else {
             Ok(&self.default_bump)
@@ -459,30 +1441,26 @@ impl TagGenerator {
         debug!("New version after bump: {version}");
     }
 
-    fn update_versions(&self, new_version: &str) -> Result<Vec<String>, CliError> {
-        let repo = self.open_repository()?;
-        let repo_path = repo.workdir().ok_or_else(|| {
-            let err = git2::Error::new(
-                git2::ErrorCode::NotFound,
-                git2::ErrorClass::Repository,
-                "Repository has no working directory",
-            );
-            CliError::GitError(err)
-        })?;
-
-        // Change to the repository directory
-        let old_dir = std::env::current_dir().map_err(CliError::IoError)?;
-        std::env::set_current_dir(repo_path).map_err(CliError::IoError)?;
+    /// Version files [`TagGenerator::update_versions`] would update.
+    pub fn version_files(&self) -> Result<Vec<String>, CliError> {
+        let mut version_manager = VersionManager::with_root(self.workdir()?);
+        version_manager.register_common_files()?;
+        Ok(version_manager
+            .existing_files()
+            .into_iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect())
+    }
 
-        let mut version_manager = VersionManager::new();
+    /// Bump the registered version files of the repository's worktree. Returns the updated
+    /// files relative to the worktree root.
+    pub fn update_versions(&self, new_version: &str) -> Result<Vec<String>, CliError> {
+        let mut version_manager = VersionManager::with_root(self.workdir()?);
         version_manager.register_common_files()?;
 
         // Update all version files
         let updated_files = version_manager.update_all_versions(new_version)?;
 
-        // Change back to the original directory
-        std::env::set_current_dir(old_dir).map_err(CliError::IoError)?;
-
         // Convert PathBuf to String
         let updated_files: Vec<String> = updated_files
             .into_iter()
@@ -491,7 +1469,11 @@ impl TagGenerator {
         Ok(updated_files)
     }
 
-    fn calculate_pre_release_tag(&self, new_version: &Version, pre_tag: &str) -> String {
+    fn calculate_pre_release_tag(
+        &self,
+        new_version: &dyn std::fmt::Display,
+        pre_tag: &str,
+    ) -> String {
         debug!(
             "Calculating pre-release tag. New version: {new_version}, Previous pre-tag: {pre_tag}"
         );
@@ -499,11 +1481,11 @@ impl TagGenerator {
         debug!("{pre_tag}");
 
         let version_string = new_version.to_string();
-        let pre_tag_without_v = pre_tag.trim_start_matches('v');
+        let pre_tag_without_v = self.tag_format.version_of(pre_tag);
 
         if pre_tag_without_v.starts_with(&version_string) {
             let pre_release_regex =
-                regex::Regex::new(&format!(r"-{}\.(\d+)$", self.suffix)).unwrap();
+                regex::Regex::new(&format!(r"-{}\.(\d+)$", regex::escape(&self.suffix))).unwrap();
             if let Some(captures) = pre_release_regex.captures(pre_tag_without_v) {
                 if let Some(pre_release_num) = captures.get(1) {
                     let next_num = pre_release_num.as_str().parse::<u64>().unwrap_or(0) + 1;
@@ -514,8 +1496,7 @@ impl TagGenerator {
         format!("{}-{}.0", new_version, self.suffix)
     }
 
-    fn get_commit_log(&self, repo: &Repository, tag: &str) -> Result<String, CliError> {
-        debug!("Getting commit log since tag: {tag}");
+    fn commits_since_tag(&self, repo: &Repository, tag: &str) -> Result<Vec<Oid>, CliError> {
         let tag_commit = self.get_commit_for_tag(repo, tag)?;
         let head_commit = self.get_current_commit(repo)?;
 
@@ -525,8 +1506,69 @@ impl TagGenerator {
             revwalk.hide(commit)?; // Only hide if we have a commit
         }
 
-        let log = revwalk
-            .filter_map(|oid| oid.ok())
+        Ok(revwalk.filter_map(|oid| oid.ok()).collect())
+    }
+
+    fn commit_types(&self) -> CommitTypeRegistry {
+        CommitTypeRegistry::load_for(std::path::Path::new(&self.source))
+    }
+
+    /// Commits since `tag` that count towards a release: commits carrying a skip marker,
+    /// commits of a type with `bump = "none"` and commits touching only ignored paths are
+    /// left out.
+    fn releasable_commits(&self, repo: &Repository, tag: &str) -> Result<Vec<Oid>, CliError> {
+        let repo_config = self.repo_config()?;
+        let types = self.commit_types();
+        let markers = match &self.release_skip_markers {
+            Some(spec) => split_list(spec),
+            None => repo_config.release_skip_markers,
+        };
+        let ignored_paths = match &self.release_ignore_paths {
+            Some(spec) => split_list(spec),
+            None => repo_config.release_ignore_paths,
+        };
+
+        let mut releasable = Vec::new();
+        for oid in self.commits_since_tag(repo, tag)? {
+            let commit = repo.find_commit(oid)?;
+            let message = commit.message().unwrap_or("");
+            if let Some(marker) = markers
+                .iter()
+                .find(|m| !m.is_empty() && message.contains(m.as_str()))
+            {
+                debug!("Commit {oid} is marked with '{marker}', not releasable");
+                continue;
+            }
+            if ChangelogEntry::parse(message, "").is_some_and(|entry| {
+                !entry.breaking
+                    && types
+                        .get(&entry.commit_type)
+                        .is_some_and(|t| t.bump == BumpLevel::None)
+            }) {
+                debug!("Commit {oid} has a commit type that does not release");
+                continue;
+            }
+            if !ignored_paths.is_empty() {
+                let paths = changed_paths(repo, &commit)?;
+                if !paths.is_empty()
+                    && paths
+                        .iter()
+                        .all(|path| ignored_paths.iter().any(|p| path_matches(p, path)))
+                {
+                    debug!("Commit {oid} only touches ignored paths, not releasable");
+                    continue;
+                }
+            }
+            releasable.push(oid);
+        }
+        Ok(releasable)
+    }
+
+    fn get_commit_log(&self, repo: &Repository, tag: &str) -> Result<String, CliError> {
+        debug!("Getting commit log since tag: {tag}");
+        let log = self
+            .releasable_commits(repo, tag)?
+            .into_iter()
             .filter_map(|oid| repo.find_commit(oid).ok())
             .map(|commit| commit.message().unwrap_or("").to_string())
             .collect::<Vec<_>>()
@@ -537,21 +1579,29 @@ impl TagGenerator {
         Ok(log)
     }
 
-    fn commit_version_changes(
+    /// Commit `updated_files` as `chore: bump version to <version>`. Returns the new
+    /// commit, or `None` when there is nothing to commit.
+    pub fn commit_version_changes(
         &self,
         repo: &Repository,
         new_version: &str,
         updated_files: &[String],
-    ) -> Result<(), CliError> {
+    ) -> Result<Option<Oid>, CliError> {
         if updated_files.is_empty() {
-            return Ok(());
+            return Ok(None);
         }
 
         let signature = repo.signature()?;
         let tree_id = {
             let mut index = repo.index()?;
+            let workdir = repo.workdir().unwrap_or_else(|| Path::new("."));
             for file in updated_files {
-                index.add_path(std::path::Path::new(file))?;
+                // Hooks may also delete files
+                if workdir.join(file).exists() {
+                    index.add_path(Path::new(file))?;
+                } else {
+                    index.remove_path(Path::new(file))?;
+                }
             }
             index.write()?;
             index.write_tree()?
@@ -562,7 +1612,7 @@ impl TagGenerator {
         let version_without_v = new_version.trim_start_matches('v');
         let message = format!("chore: bump version to {version_without_v}");
 
-        repo.commit(
+        let oid = repo.commit(
             Some("HEAD"),
             &signature,
             &signature,
@@ -570,24 +1620,18 @@ impl TagGenerator {
             &tree,
             &[&parent_commit],
         )?;
+        Ok(Some(oid))
+    }
 
+    /// Push the current branch to `origin`. Returns whether it was pushed: nothing is
+    /// pushed in dry run mode, with `--not-publish` or without an `origin` remote.
+    pub fn push_branch(&self, repo: &Repository) -> Result<bool, CliError> {
         // Push the commit to remote if we're not in dry run mode and not set to not publish
         if !self.dry_run && !self.not_publish {
             info!("🔄 Pushing version bump commit to remote");
             match repo.find_remote("origin") {
                 Ok(mut remote) => {
-                    let mut callbacks = RemoteCallbacks::new();
-                    callbacks.credentials(|_url, username_from_url, _allowed_types| {
-                        git2::Cred::ssh_key(
-                            username_from_url.unwrap_or("git"),
-                            None,
-                            std::path::Path::new(&format!(
-                                "{}/.ssh/id_rsa",
-                                std::env::var("HOME").unwrap()
-                            )),
-                            None,
-                        )
-                    });
+                    let callbacks = remote_callbacks();
 
                     let mut push_options = PushOptions::new();
                     push_options.remote_callbacks(callbacks);
@@ -601,6 +1645,7 @@ impl TagGenerator {
                             info!(
                                 "✅ Pushed version bump commit to remote branch {current_branch}"
                             );
+                            return Ok(true);
                         }
                         Err(e) => {
                             error!("Failed to push commit to remote: {e}");
@@ -621,46 +1666,112 @@ impl TagGenerator {
             }
         }
 
-        Ok(())
+        Ok(false)
+    }
+
+    /// Expand `{tag}`, `{version}`, `{previous_tag}`, `{date}` and `{notes}` in a tag
+    /// message template. Notes cover the commits since the previous tag.
+    fn render_tag_message(
+        &self,
+        repo: &Repository,
+        template: &str,
+        new_tag: &str,
+        target: Oid,
+    ) -> Result<String, CliError> {
+        let previous_tag = match &self.previous_tag {
+            Some(tag) => tag.clone(),
+            None => self.get_latest_tags(repo)?.0,
+        };
+        let mut message = template
+            .replace("{tag}", new_tag)
+            .replace("{version}", self.tag_format.version_of(new_tag))
+            .replace("{previous_tag}", &previous_tag)
+            .replace("{date}", &Utc::now().format("%Y-%m-%d").to_string());
+        if message.contains("{notes}") {
+            let from = self
+                .get_commit_for_tag(repo, &previous_tag)?
+                .filter(|commit| *commit != target);
+            let notes = ReleaseNotes::from_range(repo, from, target)?;
+            message = message.replace("{notes}", &notes.render(&self.commit_types()));
+        }
+        Ok(message.trim().to_string())
+    }
+
+    /// Release notes for the commits since the previous tag, as markdown sections.
+    pub fn release_notes(&self, repo: &Repository) -> Result<String, CliError> {
+        let previous_tag = match &self.previous_tag {
+            Some(tag) => tag.clone(),
+            None => self.get_latest_tags(repo)?.0,
+        };
+        let from = self.get_commit_for_tag(repo, &previous_tag)?;
+        let notes = ReleaseNotes::from_range(repo, from, self.get_current_commit(repo)?)?;
+        Ok(notes.render(&self.commit_types()))
     }
 
     pub fn create_and_push_tag(&self, repo: &Repository, new_tag: &str) -> Result<(), CliError> {
-        debug!("Creating and pushing new tag: {new_tag}");
+        self.create_tag(repo, new_tag)?;
+        self.push_tag(repo, new_tag)?;
+        Ok(())
+    }
+
+    pub fn create_tag(&self, repo: &Repository, new_tag: &str) -> Result<(), CliError> {
+        debug!("Creating new tag: {new_tag}");
         let head = repo.head()?.peel_to_commit()?;
         let signature = repo.signature()?;
+        if repo.find_reference(&format!("refs/tags/{new_tag}")).is_ok() {
+            return Err(CliError::TagExists(new_tag.to_string()));
+        }
 
-        let tag_message = if !self.tag_message.is_empty() {
-            &self.tag_message
+        let template = if !self.tag_message.is_empty() {
+            self.tag_message.clone()
         } else {
-            new_tag
+            self.repo_config()?.tag_message_template
+        };
+        let tag_message = if template.is_empty() {
+            new_tag.to_string()
+        } else {
+            self.render_tag_message(repo, &template, new_tag, head.id())?
         };
 
-        // Create tag
-        repo.tag(new_tag, head.as_object(), &signature, tag_message, false)?;
+        // Create tag, signed when requested or when git's tag.gpgSign is set
+        let git_config = repo.config()?;
+        if self
+            .sign
+            .unwrap_or_else(|| TagSigner::enabled_in_config(&git_config))
+        {
+            let signer = TagSigner::from_config(&git_config)?;
+            create_signed_tag(
+                repo,
+                new_tag,
+                head.as_object(),
+                &signature,
+                &tag_message,
+                &signer,
+            )?;
+            info!("🔏 Signed tag {new_tag}");
+        } else {
+            repo.tag(new_tag, head.as_object(), &signature, &tag_message, false)?;
+        }
+        Ok(())
+    }
 
-        // Only try to push if not in dry run mode and not explicitly set to not publish
-        if !self.dry_run && !self.not_publish {
+    /// Push the tag to `origin`. Returns whether it was pushed: nothing is pushed in dry
+    /// run mode, with `--not-publish` or without an `origin` remote.
+    pub fn push_tag(&self, repo: &Repository, new_tag: &str) -> Result<bool, CliError> {
+        if self.will_push() {
             match repo.find_remote("origin") {
                 Ok(mut remote) => {
-                    let mut callbacks = RemoteCallbacks::new();
-                    callbacks.credentials(|_url, username_from_url, _allowed_types| {
-                        git2::Cred::ssh_key(
-                            username_from_url.unwrap_or("git"),
-                            None,
-                            std::path::Path::new(&format!(
-                                "{}/.ssh/id_rsa",
-                                std::env::var("HOME").unwrap()
-                            )),
-                            None,
-                        )
-                    });
+                    let callbacks = remote_callbacks();
 
                     let mut push_options = PushOptions::new();
                     push_options.remote_callbacks(callbacks);
 
                     let refspec = format!("refs/tags/{new_tag}");
                     match remote.push(&[&refspec], Some(&mut push_options)) {
-                        Ok(_) => debug!("Successfully pushed tag {new_tag} to remote"),
+                        Ok(_) => {
+                            debug!("Successfully pushed tag {new_tag} to remote");
+                            return Ok(true);
+                        }
                         Err(e) => {
                             error!("Failed to push tag {new_tag} to remote: {e}");
                             if e.code() == git2::ErrorCode::Auth {
@@ -680,10 +1791,147 @@ impl TagGenerator {
             }
         }
 
-        Ok(())
+        Ok(false)
     }
 }
 
+/// A maintenance release line taken from a branch name: `release/1.2` (or `release/1.2.x`)
+/// only receives patch releases of 1.2, `release/1.x` receives minor and patch releases of 1.
+#[derive(Debug, Clone, PartialEq)]
+struct ReleaseLine {
+    major: u64,
+    minor: Option<u64>,
+}
+
+impl ReleaseLine {
+    fn from_branch(patterns: &[String], branch: &str) -> Option<Self> {
+        let line_regex = Regex::new(r"^v?(\d+)(?:\.(\d+))?(?:\.x)?$").unwrap();
+        patterns
+            .iter()
+            .filter(|p| branch_matches(p, branch))
+            .find_map(|pattern| {
+                let line = branch.strip_prefix(pattern.trim_end_matches('*'))?;
+                let captures = line_regex.captures(line)?;
+                Some(ReleaseLine {
+                    major: captures[1].parse().ok()?,
+                    minor: match captures.get(2) {
+                        Some(minor) => Some(minor.as_str().parse().ok()?),
+                        None => None,
+                    },
+                })
+            })
+    }
+
+    fn contains(&self, version: &Version) -> bool {
+        version.major == self.major && self.minor.is_none_or(|minor| version.minor == minor)
+    }
+
+    fn allowed_bumps(&self) -> &'static str {
+        if self.minor.is_some() {
+            "patch"
+        } else {
+            "minor and patch"
+        }
+    }
+}
+
+impl std::fmt::Display for ReleaseLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.minor {
+            Some(minor) => write!(f, "{}.{minor}.x", self.major),
+            None => write!(f, "{}.x", self.major),
+        }
+    }
+}
+
+fn parse_release_as(version: &str) -> Result<Version, semver::Error> {
+    Version::parse(version.trim_start_matches('v'))
+}
+
+const RELEASE_GUARDS: &[&str] = &["lint", "clean", "release-branch", "upstream"];
+
+/// Authenticate remote operations with the default SSH key.
+fn remote_callbacks<'a>() -> RemoteCallbacks<'a> {
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(|_url, username_from_url, _allowed_types| {
+        git2::Cred::ssh_key(
+            username_from_url.unwrap_or("git"),
+            None,
+            std::path::Path::new(&format!("{}/.ssh/id_rsa", std::env::var("HOME").unwrap())),
+            None,
+        )
+    });
+    callbacks
+}
+
+/// The directory of a remote that libgit2 reaches through its local transport: a
+/// `file://` URL or a path to an existing directory.
+fn local_remote_path(url: &str) -> Option<std::path::PathBuf> {
+    let path = Path::new(url.strip_prefix("file://").unwrap_or(url));
+    path.is_dir().then(|| path.to_path_buf())
+}
+
+/// Split a comma-separated list, dropping empty items.
+fn split_list(spec: &str) -> Vec<String> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Files changed by `commit` compared to its first parent (all files for a root commit).
+fn changed_paths(repo: &Repository, commit: &git2::Commit) -> Result<Vec<String>, CliError> {
+    let parent_tree = match commit.parents().next() {
+        Some(parent) => Some(parent.tree()?),
+        None => None,
+    };
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+    Ok(diff
+        .deltas()
+        .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+        .map(|path| path.to_string_lossy().to_string())
+        .collect())
+}
+
+/// Match a branch against a name, or a `prefix*` pattern.
+fn branch_matches(pattern: &str, branch: &str) -> bool {
+    branch == pattern
+        || (pattern.contains('*') && branch.starts_with(pattern.trim_end_matches('*')))
+}
+
+/// Parse a `branch=channel` comma-separated list (order is preserved, first match wins).
+fn parse_prerelease_channels(spec: &str) -> Result<Vec<PrereleaseChannel>, CliError> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (branch, channel) = entry.split_once('=').ok_or_else(|| {
+                CliError::InputError(format!(
+                    "Invalid pre-release channel mapping '{entry}', expected branch=channel"
+                ))
+            })?;
+            Ok(PrereleaseChannel {
+                branch: branch.trim().to_string(),
+                channel: channel.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Turn a branch name into something usable inside a semver pre-release identifier.
+fn sanitize_prerelease_identifier(branch: &str) -> String {
+    let mut result = String::new();
+    for c in branch.chars() {
+        if c.is_ascii_alphanumeric() {
+            result.push(c);
+        } else if !result.is_empty() && !result.ends_with('-') {
+            result.push('-');
+        }
+    }
+    result.trim_end_matches('-').to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -713,7 +1961,7 @@ mod tests {
         // Tag v8.3.2 (regular)
         repo.tag(
             "v8.3.2",
-            &repo.head().unwrap().peel_to_commit().unwrap().as_object(),
+            repo.head().unwrap().peel_to_commit().unwrap().as_object(),
             &signature,
             "Regular release",
             false,
@@ -722,7 +1970,7 @@ mod tests {
         // Tag v10.0.0-beta.1 (pre-release)
         repo.tag(
             "v10.0.0-beta.1",
-            &repo.head().unwrap().peel_to_commit().unwrap().as_object(),
+            repo.head().unwrap().peel_to_commit().unwrap().as_object(),
             &signature,
             "Pre-release",
             false,
@@ -747,16 +1995,34 @@ mod tests {
         let opts = TagGeneratorOptions {
             default_bump: "minor".to_string(),
             not_with_v: false,
+            tag_format: None,
+            package: None,
             release_branches: "main,master".to_string(),
+            maintenance_branches: "release/*".to_string(),
             source: ".".to_string(),
             dry_run: true,
             initial_version: "0.0.0".to_string(),
+            version_scheme: "semver".to_string(),
+            calver_format: CalverFormat::parse("YYYY.MM.MICRO").unwrap(),
+            bump: None,
+            release_as: None,
+            initial_development: false,
             prerelease: true,
             prerelease_suffix: "beta".to_string(),
+            prerelease_channels: None,
             none_string_token: "#none".to_string(),
+            release_skip_markers: None,
+            release_ignore_paths: None,
             force_without_change: false,
             tag_message: None,
+            sign: false,
+            no_sign: false,
             not_publish: true,
+            publish_release: false,
+            no_hooks: false,
+            release_guards: None,
+            on_tag_collision: "abort".to_string(),
+            all_tags: false,
             fetch: false,
             no_fetch: true,
         };
@@ -770,4 +2036,73 @@ mod tests {
             new_tag
         );
     }
+
+    #[test]
+    fn test_resolve_prerelease_channel_mapping() {
+        let opts = TagGeneratorOptions::from_iter_safe(&[
+            "test",
+            "--prerelease-channels",
+            "develop=beta, next=rc ,feat/*=alpha.{branch}",
+        ])
+        .unwrap();
+        let gen = TagGenerator::new(opts, false);
+
+        assert_eq!(gen.resolve_prerelease_channel("develop").unwrap(), "beta");
+        assert_eq!(gen.resolve_prerelease_channel("next").unwrap(), "rc");
+        assert_eq!(
+            gen.resolve_prerelease_channel("feat/Login_Form").unwrap(),
+            "alpha.feat-Login-Form"
+        );
+        // Unmapped branches fall back to --prerelease-suffix
+        assert_eq!(gen.resolve_prerelease_channel("hotfix/x").unwrap(), "beta");
+    }
+
+    #[test]
+    fn test_invalid_prerelease_channel_mapping() {
+        let opts =
+            TagGeneratorOptions::from_iter_safe(&["test", "--prerelease-channels", "develop"])
+                .unwrap();
+        let gen = TagGenerator::new(opts, false);
+        assert!(gen.resolve_prerelease_channel("develop").is_err());
+
+        let opts = TagGeneratorOptions::from_iter_safe(&[
+            "test",
+            "--prerelease-channels",
+            "develop=be ta",
+        ])
+        .unwrap();
+        let gen = TagGenerator::new(opts, false);
+        assert!(gen.resolve_prerelease_channel("develop").is_err());
+    }
+
+    #[test]
+    fn test_release_line_from_branch() {
+        let patterns = vec!["release/*".to_string()];
+        let line = ReleaseLine::from_branch(&patterns, "release/1.2").unwrap();
+        assert_eq!(line.to_string(), "1.2.x");
+        assert!(line.contains(&Version::new(1, 2, 7)));
+        assert!(!line.contains(&Version::new(1, 3, 0)));
+
+        assert_eq!(
+            ReleaseLine::from_branch(&patterns, "release/1.2.x"),
+            Some(line)
+        );
+        let major_line = ReleaseLine::from_branch(&patterns, "release/v2.x").unwrap();
+        assert!(major_line.contains(&Version::new(2, 5, 0)));
+        assert!(!major_line.contains(&Version::new(3, 0, 0)));
+
+        assert!(ReleaseLine::from_branch(&patterns, "release/next").is_none());
+        assert!(ReleaseLine::from_branch(&patterns, "main").is_none());
+    }
+
+    #[test]
+    fn test_next_free_candidate() {
+        let opts = TagGeneratorOptions::from_iter_safe(&["test"]).unwrap();
+        let gen = TagGenerator::new(opts, false);
+        assert_eq!(gen.next_free_candidate("v1.2.0").unwrap(), "v1.2.1");
+        assert_eq!(
+            gen.next_free_candidate("v1.2.0-rc.3").unwrap(),
+            "v1.2.0-rc.4"
+        );
+    }
 }
@@ -0,0 +1,122 @@
+use crate::error::CliError;
+
+/// How a version is written into a tag name, e.g. `v{version}`, `release-{version}` or
+/// `{package}@{version}`. Used both to recognise existing tags and to emit new ones.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagFormat {
+    prefix: String,
+    suffix: String,
+    // The historical format accepts existing tags with or without a leading `v`
+    optional_v: bool,
+}
+
+impl TagFormat {
+    /// `v{version}`, or `{version}` without the `v`.
+    pub fn legacy(with_v: bool) -> Self {
+        TagFormat {
+            prefix: if with_v { "v" } else { "" }.to_string(),
+            suffix: String::new(),
+            optional_v: true,
+        }
+    }
+
+    /// Parse a template containing `{version}` once and, optionally, `{package}`.
+    pub fn parse(template: &str, package: Option<&str>) -> Result<Self, CliError> {
+        let mut rendered = template.to_string();
+        if rendered.contains("{package}") {
+            let package = package.filter(|p| !p.is_empty()).ok_or_else(|| {
+                CliError::InputError(format!(
+                    "Tag format '{template}' uses {{package}} but no package name was given (--package)"
+                ))
+            })?;
+            rendered = rendered.replace("{package}", package);
+        }
+
+        let (prefix, suffix) = rendered.split_once("{version}").ok_or_else(|| {
+            CliError::InputError(format!("Tag format '{template}' must contain {{version}}"))
+        })?;
+        if prefix.contains(['{', '}']) || suffix.contains(['{', '}']) {
+            return Err(CliError::InputError(format!(
+                "Tag format '{template}' may only use {{version}} once and {{package}}"
+            )));
+        }
+        if !git2::Reference::is_valid_name(&format!("refs/tags/{prefix}1.0.0{suffix}")) {
+            return Err(CliError::InputError(format!(
+                "Tag format '{template}' does not produce valid tag names"
+            )));
+        }
+
+        Ok(TagFormat {
+            prefix: prefix.to_string(),
+            suffix: suffix.to_string(),
+            optional_v: false,
+        })
+    }
+
+    /// The tag name for a version.
+    pub fn format(&self, version: &str) -> String {
+        format!("{}{version}{}", self.prefix, self.suffix)
+    }
+
+    /// The version part of a tag name. Names that do not follow the format are
+    /// returned unchanged.
+    pub fn version_of<'a>(&self, tag: &'a str) -> &'a str {
+        if self.optional_v {
+            return tag.trim_start_matches('v');
+        }
+        tag.strip_prefix(self.prefix.as_str())
+            .and_then(|rest| rest.strip_suffix(self.suffix.as_str()))
+            .unwrap_or(tag)
+    }
+
+    /// An anchored regex matching tag names whose version part matches `version_pattern`.
+    pub fn pattern(&self, version_pattern: &str) -> String {
+        if self.optional_v {
+            format!("^v?{version_pattern}$")
+        } else {
+            format!(
+                "^{}{version_pattern}{}$",
+                regex::escape(&self.prefix),
+                regex::escape(&self.suffix)
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    #[test]
+    fn test_legacy_format() {
+        let format = TagFormat::legacy(true);
+        assert_eq!(format.format("1.2.0"), "v1.2.0");
+        assert_eq!(format.version_of("v1.2.0"), "1.2.0");
+        assert_eq!(format.version_of("1.2.0"), "1.2.0");
+        assert_eq!(TagFormat::legacy(false).format("1.2.0"), "1.2.0");
+    }
+
+    #[test]
+    fn test_template_format() {
+        let format = TagFormat::parse("{package}@{version}", Some("core")).unwrap();
+        assert_eq!(format.format("1.2.0"), "core@1.2.0");
+        assert_eq!(format.version_of("core@1.2.0-beta.1"), "1.2.0-beta.1");
+        let regex = Regex::new(&format.pattern(r"[0-9]+\.[0-9]+\.[0-9]+")).unwrap();
+        assert!(regex.is_match("core@1.2.0"));
+        assert!(!regex.is_match("cli@1.2.0"));
+        assert!(!regex.is_match("v1.2.0"));
+
+        let release = TagFormat::parse("release-{version}", None).unwrap();
+        assert_eq!(release.version_of("release-2.0.0"), "2.0.0");
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        assert!(TagFormat::parse("release", None).is_err());
+        assert!(TagFormat::parse("{package}@{version}", None).is_err());
+        assert!(TagFormat::parse("{version}-{version}", None).is_err());
+        assert!(TagFormat::parse("{name}-{version}", None).is_err());
+        assert!(TagFormat::parse("bad..{version}", None).is_err());
+    }
+}
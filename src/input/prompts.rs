@@ -1,9 +1,9 @@
 use super::validation::{
     auto_correct_scope, validate_scope, validate_section, validate_short_message,
 };
+use crate::config::commit_types::{CommitType, CommitTypeRegistry};
 use crate::config::{
-    BRANCH_TYPES, COMMIT_TYPES, MAX_SCOPE_NAME_LENGTH, MAX_SHORT_DESCRIPTION_LENGTH,
-    MAX_TICKET_NAME_LENGTH,
+    BRANCH_TYPES, MAX_SCOPE_NAME_LENGTH, MAX_SHORT_DESCRIPTION_LENGTH, MAX_TICKET_NAME_LENGTH,
 };
 use crate::error::CliError;
 use inquire::{Confirm, Select, Text};
@@ -19,17 +19,28 @@ fn non_interactive_env() -> bool {
 }
 
 pub fn select_commit_type() -> Result<String, CliError> {
+    select_commit_type_with_default(None)
+}
+
+/// [`select_commit_type`] with the cursor on `default`, e.g. a type suggested by AI.
+pub fn select_commit_type_with_default(default: Option<&str>) -> Result<String, CliError> {
     if non_interactive_env() {
         return Err(CliError::InputError(
             "Non-interactive environment: cannot prompt for commit type".to_string(),
         ));
     }
-    let commit_type = Select::new("Select the type of commit:", COMMIT_TYPES.to_vec())
+    let types = CommitTypeRegistry::load();
+    let options: Vec<CommitType> = types.visible().into_iter().cloned().collect();
+    let cursor = default
+        .and_then(|default| options.iter().position(|t| t.name == default))
+        .unwrap_or(0);
+    let commit_type = Select::new("Select the type of commit:", options)
         .with_help_message("Use arrow keys to navigate, Enter to select")
+        .with_starting_cursor(cursor)
         .prompt()
         .map_err(|e| CliError::InputError(e.to_string()))?;
 
-    Ok(commit_type.to_string())
+    Ok(commit_type.name)
 }
 
 pub fn select_branch_type() -> Result<String, CliError> {
@@ -47,13 +58,17 @@ pub fn select_branch_type() -> Result<String, CliError> {
 }
 
 pub fn confirm_breaking_change() -> Result<bool, CliError> {
+    confirm_breaking_change_with_default(false)
+}
+
+pub fn confirm_breaking_change_with_default(default: bool) -> Result<bool, CliError> {
     if non_interactive_env() {
         return Err(CliError::InputError(
             "Non-interactive environment: cannot confirm breaking change".to_string(),
         ));
     }
     Confirm::new("Is this a breaking change?")
-        .with_default(false)
+        .with_default(default)
         .prompt()
         .map_err(|e| CliError::InputError(e.to_string()))
 }
@@ -158,6 +173,11 @@ pub fn validate_scope_input(scope: &str) -> Result<String, CliError> {
 }
 
 pub fn input_scope() -> Result<String, CliError> {
+    input_scope_with_default("")
+}
+
+/// [`input_scope`] starting from `default`, which the user can edit.
+pub fn input_scope_with_default(default: &str) -> Result<String, CliError> {
     if non_interactive_env() {
         return Err(CliError::InputError(
             "Non-interactive environment: cannot input scope".to_string(),
@@ -178,6 +198,7 @@ pub fn input_scope() -> Result<String, CliError> {
         Ok(inquire::validator::Validation::Valid)
     };
     let scope = Text::new("Enter the scope of the commit (optional):")
+        .with_initial_value(default)
         .with_help_message(&format!(
             "Press Enter to skip, max {MAX_SCOPE_NAME_LENGTH} characters"
         ))
@@ -193,6 +214,11 @@ pub fn input_scope() -> Result<String, CliError> {
 }
 
 pub fn input_short_message() -> Result<String, CliError> {
+    input_short_message_with_default("")
+}
+
+/// [`input_short_message`] starting from `default`, which the user can edit.
+pub fn input_short_message_with_default(default: &str) -> Result<String, CliError> {
     if non_interactive_env() {
         return Err(CliError::InputError(
             "Non-interactive environment: cannot input short message".to_string(),
@@ -231,6 +257,7 @@ pub fn input_short_message() -> Result<String, CliError> {
         };
 
         let msg = Text::new("Enter a short description:")
+            .with_initial_value(default)
             .with_help_message(&format!(
                 "Min 5, Max {MAX_SHORT_DESCRIPTION_LENGTH} characters"
             ))
@@ -266,27 +293,67 @@ pub fn input_short_message() -> Result<String, CliError> {
 }
 
 pub fn input_long_message() -> Result<String, CliError> {
+    input_long_message_with_default("")
+}
+
+/// [`input_long_message`] starting from `default`, which the user can edit.
+pub fn input_long_message_with_default(default: &str) -> Result<String, CliError> {
     if non_interactive_env() {
         return Err(CliError::InputError(
             "Non-interactive environment: cannot input long message".to_string(),
         ));
     }
     let msg = Text::new("Enter a detailed description (optional):")
+        .with_initial_value(default)
         .with_help_message("Press Enter twice to finish")
         .prompt()
         .map_err(|e| CliError::InputError(e.to_string()))?;
     Ok(msg)
 }
 
-pub fn ask_want_create_new_tag() -> Result<bool, CliError> {
+/// Let the user keep the computed tag, force a bump level, type a version or abort.
+/// Returns `create`, `major`, `minor`, `patch`, `prerelease`, `custom` or `abort`.
+pub fn select_tag_bump(new_tag: &str) -> Result<String, CliError> {
     if non_interactive_env() {
         return Err(CliError::InputError(
-            "Non-interactive environment: cannot confirm tag creation".to_string(),
+            "Non-interactive environment: cannot prompt for version bump".to_string(),
         ));
     }
-    Confirm::new("Are you sure you want to create a new tag?")
-        .with_default(false)
+    let choices = [
+        ("create", format!("Create {new_tag}")),
+        ("major", "Bump major instead".to_string()),
+        ("minor", "Bump minor instead".to_string()),
+        ("patch", "Bump patch instead".to_string()),
+        ("prerelease", "Create a pre-release instead".to_string()),
+        ("custom", "Enter a version".to_string()),
+        ("abort", "Abort".to_string()),
+    ];
+    let labels: Vec<&str> = choices.iter().map(|(_, label)| label.as_str()).collect();
+    let selected = Select::new("What do you want to do?", labels)
+        .with_help_message("Use arrow keys to navigate, Enter to select")
+        .raw_prompt()
+        .map_err(|e| CliError::InputError(e.to_string()))?;
+
+    Ok(choices[selected.index].0.to_string())
+}
+
+pub fn input_release_version() -> Result<String, CliError> {
+    if non_interactive_env() {
+        return Err(CliError::InputError(
+            "Non-interactive environment: cannot input release version".to_string(),
+        ));
+    }
+    let validator = |input: &str| match semver::Version::parse(input.trim().trim_start_matches('v'))
+    {
+        Ok(_) => Ok(inquire::validator::Validation::Valid),
+        Err(e) => Ok(inquire::validator::Validation::Invalid(
+            inquire::validator::ErrorMessage::Custom(format!("Invalid version: {e}")),
+        )),
+    };
+    Text::new("Version to release (e.g. 1.0.0):")
+        .with_validator(validator)
         .prompt()
+        .map(|version| version.trim().to_string())
         .map_err(|e| CliError::InputError(e.to_string()))
 }
 
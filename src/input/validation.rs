@@ -1,4 +1,5 @@
-use crate::config::{COMMIT_TYPES, MAX_SHORT_DESCRIPTION_LENGTH};
+use crate::config::commit_types::CommitTypeRegistry;
+use crate::config::MAX_SHORT_DESCRIPTION_LENGTH;
 use strsim;
 
 pub fn validate_short_message(input: &str) -> Result<(), String> {
@@ -31,12 +32,20 @@ pub fn validate_section(text: &str) -> Result<String, String> {
     }
 }
 
-pub fn suggest_commit_type(input: &str) -> Option<&'static str> {
+/// Suggest a registered commit type for `input`, correcting case, common variations
+/// and typos.
+pub fn suggest_commit_type(input: &str) -> Option<String> {
+    suggest_registered_type(input, &CommitTypeRegistry::load())
+}
+
+/// [`suggest_commit_type`] against the types registered in `types`.
+pub fn suggest_registered_type(input: &str, types: &CommitTypeRegistry) -> Option<String> {
+    let names = types.names();
     let input = input.trim().to_lowercase();
 
     // First try exact match
-    if let Some(&exact_match) = COMMIT_TYPES.iter().find(|&&t| t == input) {
-        return Some(exact_match);
+    if let Some(&exact_match) = names.iter().find(|&&t| t == input) {
+        return Some(exact_match.to_string());
     }
 
     // Then try common variations
@@ -50,12 +59,12 @@ pub fn suggest_commit_type(input: &str) -> Option<&'static str> {
 
     for (variation, commit_type) in variations.iter() {
         if input == *variation {
-            return Some(commit_type);
+            return Some(commit_type.to_string());
         }
     }
 
     // Finally try fuzzy matching
-    COMMIT_TYPES
+    names
         .iter()
         .min_by_key(|&&valid_type| strsim::levenshtein(&input, valid_type))
         .filter(|&&valid_type| {
@@ -63,7 +72,7 @@ pub fn suggest_commit_type(input: &str) -> Option<&'static str> {
             let max_allowed = (valid_type.len() as f32 * 0.4).ceil() as usize;
             distance <= max_allowed
         })
-        .copied()
+        .map(|t| t.to_string())
 }
 
 pub fn auto_correct_scope(input: &str) -> String {
@@ -111,16 +120,19 @@ mod tests {
     #[test]
     fn test_suggest_commit_type() {
         // Exact matches
-        assert_eq!(suggest_commit_type("feat"), Some("feat"));
-        assert_eq!(suggest_commit_type("fix"), Some("fix"));
+        assert_eq!(suggest_commit_type("feat").as_deref(), Some("feat"));
+        assert_eq!(suggest_commit_type("fix").as_deref(), Some("fix"));
 
         // Close matches with different cases and whitespace
-        assert_eq!(suggest_commit_type("  Feature  "), Some("feat"));
-        assert_eq!(suggest_commit_type("FIX"), Some("fix"));
-        assert_eq!(suggest_commit_type("fixx"), Some("fix"));
+        assert_eq!(suggest_commit_type("  Feature  ").as_deref(), Some("feat"));
+        assert_eq!(suggest_commit_type("FIX").as_deref(), Some("fix"));
+        assert_eq!(suggest_commit_type("fixx").as_deref(), Some("fix"));
 
         // No close matches
-        assert_eq!(suggest_commit_type("something_completely_different"), None);
+        assert_eq!(
+            suggest_commit_type("something_completely_different").as_deref(),
+            None
+        );
     }
 
     #[test]
@@ -1,4 +1,5 @@
 pub mod ai;
+pub mod api;
 pub mod cli;
 pub mod config;
 pub mod error;
@@ -6,6 +7,8 @@ pub mod git;
 pub mod input;
 pub mod linter;
 pub mod logger;
+pub mod lsp;
+pub mod mcp;
 pub mod release;
 pub mod telemetry;
 pub mod update;
@@ -1,13 +1,19 @@
-use anyhow::Result;
-use git2::{ObjectType, Repository, Tag};
+use crate::config::commit_types::CommitTypeRegistry;
+use git2::{Error, ObjectType, Oid, Repository, Tag};
 use regex::Regex;
+use schemars::JsonSchema;
 use serde::Serialize;
+use std::ops::Range;
+use std::path::Path;
+
+type Result<T> = std::result::Result<T, Error>;
 
 pub struct CommitLinter {
     repo: Repository,
+    types: CommitTypeRegistry,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct CommitIssue {
     pub commit_id: String,
     pub message: String,
@@ -17,12 +23,11 @@ pub struct CommitIssue {
 impl CommitLinter {
     pub fn new(repo_path: &str) -> Result<Self> {
         let repo = Repository::open(repo_path)?;
-        Ok(CommitLinter { repo })
+        let types = CommitTypeRegistry::load_for(Path::new(repo_path));
+        Ok(CommitLinter { repo, types })
     }
 
     pub fn check_commits_since_last_tag(&self) -> Result<Vec<CommitIssue>> {
-        let mut issues = Vec::new();
-
         // Get HEAD commit
         let head = match self.repo.head() {
             Ok(head) => head,
@@ -30,30 +35,46 @@ impl CommitLinter {
                 // Repository is empty, no commits to check
                 return Ok(Vec::new());
             }
-            Err(e) => return Err(e.into()),
+            Err(e) => return Err(e),
         };
 
         let head_commit = head.peel_to_commit()?;
 
-        // Create revwalk
-        let mut revwalk = self.repo.revwalk()?;
-        revwalk.push(head_commit.id())?;
-
         // If there's a tag, only check commits since that tag
-        if let Ok(Some(tag)) = self.get_last_tag() {
-            let tag_commit = tag.target()?.peel_to_commit()?;
-            revwalk.hide(tag_commit.id())?;
+        let since = match self.get_last_tag() {
+            Ok(Some(tag)) => Some(tag.target()?.peel_to_commit()?.id()),
+            _ => None,
+        };
+
+        self.check_range(since, head_commit.id())
+    }
+
+    /// Lint the commits of `from..to`, given as revisions. Without `from`, the commits
+    /// since the last tag are linted.
+    pub fn check_revisions(&self, from: Option<&str>, to: &str) -> Result<Vec<CommitIssue>> {
+        let since = match from {
+            Some(from) => Some(self.repo.revparse_single(from)?.peel_to_commit()?.id()),
+            None if to == "HEAD" => return self.check_commits_since_last_tag(),
+            None => match self.get_last_tag() {
+                Ok(Some(tag)) => Some(tag.target()?.peel_to_commit()?.id()),
+                _ => None,
+            },
+        };
+        let to = self.repo.revparse_single(to)?.peel_to_commit()?.id();
+        self.check_range(since, to)
+    }
+
+    /// Lint the commits reachable from `to` but not from `since` (all of them when `None`).
+    pub fn check_range(&self, since: Option<Oid>, to: Oid) -> Result<Vec<CommitIssue>> {
+        let mut issues = Vec::new();
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(to)?;
+        if let Some(since) = since {
+            revwalk.hide(since)?;
         }
 
-        // Conventional commit regex parts
-        let type_pattern = format!(r"(?:{})", crate::config::COMMIT_TYPES.join("|"));
-        let scope_pattern = r"(?:\([a-z0-9-]+\))?";
-        let breaking_change = r"(?:!)?"; // Optional breaking change indicator
-        let separator = r"\: ";
-        let description = r".+";
-        let full_pattern =
-            format!("^{type_pattern}{scope_pattern}{breaking_change}{separator}{description}$");
-        let commit_regex = Regex::new(&full_pattern).unwrap();
+        let commit_regex = commit_regex(&self.types);
 
         // Check each commit
         for commit_id in revwalk {
@@ -65,23 +86,7 @@ impl CommitLinter {
 
             // Check if commit message follows conventional commit format
             if !commit_regex.is_match(first_line) {
-                let issue = if !first_line.contains(": ") {
-                    "Missing ': ' separator between type/scope and description".to_string()
-                } else if !crate::config::COMMIT_TYPES
-                    .iter()
-                    .any(|t| first_line.starts_with(t))
-                {
-                    let types = crate::config::COMMIT_TYPES.join(", ");
-                    format!("Commit type must be one of: {types}")
-                } else if first_line.contains("(") && !first_line.contains(")") {
-                    "Unclosed scope parenthesis".to_string()
-                } else if first_line.contains(")") && !first_line.contains("(") {
-                    "Unopened scope parenthesis".to_string()
-                } else if first_line.contains("()") {
-                    "Empty scope parenthesis".to_string()
-                } else {
-                    "Commit message format should be: <type>(<scope>): <description>".to_string()
-                };
+                let issue = format_issue(first_line, &self.types);
 
                 issues.push(CommitIssue {
                     commit_id: commit_id.to_string(),
@@ -137,6 +142,44 @@ impl CommitLinter {
     }
 }
 
+/// Conventional commit header regex accepting the registered commit types.
+fn commit_regex(types: &CommitTypeRegistry) -> Regex {
+    let type_pattern = format!(
+        r"(?:{})",
+        types
+            .names()
+            .iter()
+            .map(|t| regex::escape(t))
+            .collect::<Vec<_>>()
+            .join("|")
+    );
+    let scope_pattern = r"(?:\([a-z0-9-]+\))?";
+    let breaking_change = r"(?:!)?"; // Optional breaking change indicator
+    let separator = r"\: ";
+    let description = r".+";
+    let full_pattern =
+        format!("^{type_pattern}{scope_pattern}{breaking_change}{separator}{description}$");
+    Regex::new(&full_pattern).unwrap()
+}
+
+/// Explain why a header does not match the conventional commit format.
+fn format_issue(first_line: &str, types: &CommitTypeRegistry) -> String {
+    let names = types.names();
+    if !first_line.contains(": ") {
+        "Missing ': ' separator between type/scope and description".to_string()
+    } else if !names.iter().any(|t| first_line.starts_with(t)) {
+        format!("Commit type must be one of: {}", names.join(", "))
+    } else if first_line.contains("(") && !first_line.contains(")") {
+        "Unclosed scope parenthesis".to_string()
+    } else if first_line.contains(")") && !first_line.contains("(") {
+        "Unopened scope parenthesis".to_string()
+    } else if first_line.contains("()") {
+        "Empty scope parenthesis".to_string()
+    } else {
+        "Commit message format should be: <type>(<scope>): <description>".to_string()
+    }
+}
+
 /// Lint a single commit message string using the same rules as repository linting.
 /// Returns a list of issue descriptions; empty if the message passes all checks.
 pub fn check_message_format(message: &str) -> Vec<String> {
@@ -145,36 +188,11 @@ pub fn check_message_format(message: &str) -> Vec<String> {
     let message = message.trim();
     let first_line = message.lines().next().unwrap_or("");
 
-    // Conventional commit regex parts
-    let type_pattern = format!(r"(?:{})", crate::config::COMMIT_TYPES.join("|"));
-    let scope_pattern = r"(?:\([a-z0-9-]+\))?";
-    let breaking_change = r"(?:!)?"; // Optional breaking change indicator
-    let separator = r"\: ";
-    let description = r".+";
-    let full_pattern =
-        format!("^{type_pattern}{scope_pattern}{breaking_change}{separator}{description}$");
-    let commit_regex = Regex::new(&full_pattern).unwrap();
+    let types = CommitTypeRegistry::load();
 
     // Check if commit message follows conventional commit format
-    if !commit_regex.is_match(first_line) {
-        let issue = if !first_line.contains(": ") {
-            "Missing ': ' separator between type/scope and description".to_string()
-        } else if !crate::config::COMMIT_TYPES
-            .iter()
-            .any(|t| first_line.starts_with(t))
-        {
-            let types = crate::config::COMMIT_TYPES.join(", ");
-            format!("Commit type must be one of: {types}")
-        } else if first_line.contains("(") && !first_line.contains(")") {
-            "Unclosed scope parenthesis".to_string()
-        } else if first_line.contains(")") && !first_line.contains("(") {
-            "Unopened scope parenthesis".to_string()
-        } else if first_line.contains("()") {
-            "Empty scope parenthesis".to_string()
-        } else {
-            "Commit message format should be: <type>(<scope>): <description>".to_string()
-        };
-        issues.push(issue);
+    if !commit_regex(&types).is_match(first_line) {
+        issues.push(format_issue(first_line, &types));
         return issues; // Match behavior of repo linting: when format is invalid, do not report length issues
     }
 
@@ -197,6 +215,215 @@ pub fn check_message_format(message: &str) -> Vec<String> {
     issues
 }
 
+/// What a problem found by [`locate_message_issues`] is about, and so how it can be fixed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    /// The commit type is not registered
+    Type,
+    /// The scope has characters other than lowercase letters, digits and hyphens
+    Scope,
+    /// Any other problem with the header
+    Header,
+    /// The body does not start after a blank line
+    BodySeparator,
+    /// A misspelled `BREAKING CHANGE` footer
+    Footer,
+}
+
+impl IssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueKind::Type => "type",
+            IssueKind::Scope => "scope",
+            IssueKind::Header => "header",
+            IssueKind::BodySeparator => "body-separator",
+            IssueKind::Footer => "footer",
+        }
+    }
+}
+
+/// A problem of a commit message, with its place in the message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageIssue {
+    pub kind: IssueKind,
+    pub issue: String,
+    /// Zero-based line of the message, comment lines included
+    pub line: usize,
+    /// Character range of the problem on its line
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Where the parts of a (possibly malformed) commit header are, in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderLayout {
+    /// The type spans from the start of the header to here
+    pub type_end: usize,
+    /// The scope, inside its parentheses
+    pub scope: Option<Range<usize>>,
+    pub scope_closed: bool,
+    /// Start of the `: ` separator
+    pub separator: Option<usize>,
+}
+
+/// Split a commit header into its type, scope and separator.
+pub fn header_layout(header: &str) -> HeaderLayout {
+    let chars: Vec<char> = header.chars().collect();
+    let type_end = chars
+        .iter()
+        .position(|c| matches!(c, '(' | '!' | ':' | ' '))
+        .unwrap_or(chars.len());
+    let mut layout = HeaderLayout {
+        type_end,
+        scope: None,
+        scope_closed: false,
+        separator: None,
+    };
+    let mut rest = type_end;
+    if chars.get(type_end) == Some(&'(') {
+        let start = type_end + 1;
+        let end = chars[start..]
+            .iter()
+            .position(|c| matches!(c, ')' | ':'))
+            .map_or(chars.len(), |i| start + i);
+        layout.scope_closed = chars.get(end) == Some(&')');
+        layout.scope = Some(start..end);
+        rest = end;
+    }
+    layout.separator =
+        (rest..chars.len().saturating_sub(1)).find(|&i| chars[i] == ':' && chars[i + 1] == ' ');
+    layout
+}
+
+/// The lines of a message as git keeps them: without comment lines nor anything below
+/// the scissors line, numbered as in the message.
+pub fn message_lines(message: &str) -> impl Iterator<Item = (usize, &str)> {
+    message
+        .lines()
+        .enumerate()
+        .take_while(|(_, line)| !line.starts_with("# ------------------------ >8"))
+        .filter(|(_, line)| !line.starts_with('#'))
+}
+
+/// Lint a commit message as edited in `COMMIT_EDITMSG`, locating every problem of its
+/// header, body and footers.
+pub fn locate_message_issues(message: &str, types: &CommitTypeRegistry) -> Vec<MessageIssue> {
+    let lines: Vec<(usize, &str)> = message_lines(message).collect();
+    let Some(header_index) = lines.iter().position(|(_, line)| !line.trim().is_empty()) else {
+        return Vec::new();
+    };
+    let (line, header) = lines[header_index];
+    let mut issues = header_issues(line, header, types);
+
+    let body = &lines[header_index + 1..];
+    if let Some(&(line, text)) = body.first() {
+        if !text.trim().is_empty() {
+            issues.push(MessageIssue {
+                kind: IssueKind::BodySeparator,
+                issue: "Missing blank line between the header and the body".to_string(),
+                line,
+                start: 0,
+                end: text.chars().count(),
+            });
+        }
+    }
+
+    let footer = Regex::new(r"(?i)^breaking[ -]change\s*:\s*").unwrap();
+    for &(line, text) in body {
+        if let Some(m) = footer.find(text) {
+            if !text.starts_with("BREAKING CHANGE: ") && !text.starts_with("BREAKING-CHANGE: ") {
+                issues.push(MessageIssue {
+                    kind: IssueKind::Footer,
+                    issue: "Breaking change footers must start with 'BREAKING CHANGE: '"
+                        .to_string(),
+                    line,
+                    start: 0,
+                    end: m.as_str().chars().count(),
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+fn header_issues(line: usize, header: &str, types: &CommitTypeRegistry) -> Vec<MessageIssue> {
+    let len = header.chars().count();
+    let issue = |kind, issue: String, range: Range<usize>| MessageIssue {
+        kind,
+        issue,
+        line,
+        start: range.start,
+        end: range.end,
+    };
+
+    if commit_regex(types).is_match(header) {
+        let mut issues = Vec::new();
+        if header.len() < 10 {
+            issues.push(issue(
+                IssueKind::Header,
+                format!(
+                    "Commit message is too short (got {} characters, minimum is 10)",
+                    header.len()
+                ),
+                0..len,
+            ));
+        }
+        if header.len() > 72 {
+            issues.push(issue(
+                IssueKind::Header,
+                format!(
+                    "First line of commit message is too long (got {} characters, maximum is 72)",
+                    header.len()
+                ),
+                len.min(72)..len,
+            ));
+        }
+        return issues;
+    }
+
+    let layout = header_layout(header);
+    let commit_type: String = header.chars().take(layout.type_end).collect();
+    let found = if layout.separator.is_none() {
+        issue(IssueKind::Header, format_issue(header, types), 0..len)
+    } else if types.get(&commit_type).is_none() {
+        issue(
+            IssueKind::Type,
+            format!("Commit type must be one of: {}", types.names().join(", ")),
+            0..layout.type_end.max(1),
+        )
+    } else if let Some(scope) = layout.scope.clone() {
+        let text: String = header.chars().skip(scope.start).take(scope.len()).collect();
+        if !layout.scope_closed {
+            issue(
+                IssueKind::Header,
+                "Unclosed scope parenthesis".to_string(),
+                scope.start - 1..scope.end,
+            )
+        } else if text.is_empty() {
+            issue(
+                IssueKind::Header,
+                "Empty scope parenthesis".to_string(),
+                scope.start - 1..scope.end + 1,
+            )
+        } else if !text
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            issue(
+                IssueKind::Scope,
+                "Scope must contain only lowercase letters, digits and hyphens".to_string(),
+                scope,
+            )
+        } else {
+            issue(IssueKind::Header, format_issue(header, types), 0..len)
+        }
+    } else {
+        issue(IssueKind::Header, format_issue(header, types), 0..len)
+    };
+    vec![found]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -314,4 +541,49 @@ mod tests {
         let issues = linter.check_commits_since_last_tag().unwrap();
         assert_eq!(issues.len(), 2);
     }
+
+    #[test]
+    fn test_header_layout() {
+        let layout = header_layout("feat(api)!: add x");
+        assert_eq!(layout.type_end, 4);
+        assert_eq!(layout.scope, Some(5..8));
+        assert!(layout.scope_closed);
+        assert_eq!(layout.separator, Some(10));
+
+        let layout = header_layout("fix(api: x");
+        assert_eq!(layout.scope, Some(4..7));
+        assert!(!layout.scope_closed);
+        assert_eq!(layout.separator, Some(7));
+    }
+
+    #[test]
+    fn test_locate_message_issues() {
+        let types = CommitTypeRegistry::default();
+        let locate = |message| locate_message_issues(message, &types);
+
+        assert!(locate("# Please enter the commit message\nfeat: add x\n\nBody\n").is_empty());
+
+        let issues = locate("feature(api): add x");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, IssueKind::Type);
+        assert_eq!((issues[0].start, issues[0].end), (0, 7));
+
+        let issues = locate("\nfix(My Scope): add x\nbody\n\nbreaking change: y");
+        let kinds: Vec<_> = issues.iter().map(|i| (i.kind, i.line)).collect();
+        assert_eq!(
+            kinds,
+            [
+                (IssueKind::Scope, 1),
+                (IssueKind::BodySeparator, 2),
+                (IssueKind::Footer, 4),
+            ]
+        );
+        assert_eq!((issues[0].start, issues[0].end), (4, 12));
+
+        // Comments and the scissors line are not part of the message
+        let issues =
+            locate("docs: x\n# ------------------------ >8 ------------------------\ndiff");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].issue.contains("too short"));
+    }
 }
@@ -0,0 +1,307 @@
+use crate::config::commit_types::CommitTypeRegistry;
+use crate::git;
+use crate::input::validation::{auto_correct_scope, suggest_registered_type};
+use crate::linter::{header_layout, locate_message_issues, message_lines, IssueKind, MessageIssue};
+use git2::Repository;
+use log::debug;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// How many commits of history are searched for scopes to complete.
+const SCOPE_HISTORY: usize = 500;
+
+// LSP enumerations
+const SEVERITY_ERROR: u8 = 1;
+const SEVERITY_WARNING: u8 = 2;
+const COMPLETION_KEYWORD: u8 = 14;
+const COMPLETION_VALUE: u8 = 12;
+
+/// The repository a document belongs to, and its commit types.
+pub struct Context {
+    types: CommitTypeRegistry,
+    repo: Option<Repository>,
+}
+
+impl Context {
+    /// The context of the repository containing `dir` (`COMMIT_EDITMSG` lives in `.git`).
+    pub fn for_dir(dir: Option<&Path>) -> Self {
+        let repo = dir.and_then(|dir| match git::open_repository(dir) {
+            Ok(repo) => Some(repo),
+            Err(e) => {
+                debug!("No repository for {dir:?}: {e}");
+                None
+            }
+        });
+        let types = match repo.as_ref().and_then(Repository::workdir) {
+            Some(workdir) => CommitTypeRegistry::load_for(workdir),
+            None => CommitTypeRegistry::load(),
+        };
+        Context { types, repo }
+    }
+}
+
+/// The local path of a `file://` URI.
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| path.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok().map(PathBuf::from)
+}
+
+/// The linter's issues with `text`, as LSP diagnostics.
+pub fn diagnostics(text: &str, context: &Context) -> Vec<Value> {
+    locate_message_issues(text, &context.types)
+        .iter()
+        .map(|issue| diagnostic(text, issue))
+        .collect()
+}
+
+fn diagnostic(text: &str, issue: &MessageIssue) -> Value {
+    let severity = match issue.kind {
+        IssueKind::BodySeparator | IssueKind::Footer => SEVERITY_WARNING,
+        _ => SEVERITY_ERROR,
+    };
+    json!({
+        "range": range(text, issue.line, issue.start, issue.end),
+        "severity": severity,
+        "source": "committy",
+        "code": issue.kind.as_str(),
+        "message": issue.issue,
+    })
+}
+
+/// Completion items at `line` and `character` (UTF-16) of `text`: commit types on the
+/// type of the header, scopes from the history inside its parentheses.
+pub fn completions(text: &str, line: usize, character: usize, context: &Context) -> Vec<Value> {
+    let mut lines = message_lines(text).peekable();
+    let first = lines.peek().map(|&(index, _)| index);
+    let header_line = lines
+        .find(|(_, text)| !text.trim().is_empty())
+        .map(|(index, _)| index)
+        .or(first)
+        .unwrap_or(line);
+    let header = line_text(text, line);
+    // Without a message yet, any line but a comment can start one
+    if header_line != line || header.starts_with('#') {
+        return Vec::new();
+    }
+    let column = char_column(header, character);
+    let layout = header_layout(header);
+
+    if column <= layout.type_end {
+        let edit = range(text, line, 0, layout.type_end);
+        context
+            .types
+            .visible()
+            .into_iter()
+            .map(|commit_type| {
+                json!({
+                    "label": commit_type.name,
+                    "kind": COMPLETION_KEYWORD,
+                    "detail": commit_type.description,
+                    "textEdit": { "range": edit, "newText": commit_type.name },
+                })
+            })
+            .collect()
+    } else if let Some(scope) = layout
+        .scope
+        .filter(|scope| scope.start <= column && column <= scope.end)
+    {
+        let Some(repo) = &context.repo else {
+            return Vec::new();
+        };
+        let scopes = git::recent_scopes(repo, SCOPE_HISTORY).unwrap_or_else(|e| {
+            debug!("Could not read scopes from the history: {e}");
+            Vec::new()
+        });
+        let edit = range(text, line, scope.start, scope.end);
+        scopes
+            .into_iter()
+            .enumerate()
+            .map(|(rank, scope)| {
+                json!({
+                    "label": scope,
+                    "kind": COMPLETION_VALUE,
+                    "sortText": format!("{rank:04}"),
+                    "textEdit": { "range": edit, "newText": scope },
+                })
+            })
+            .collect()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Quick fixes for the issues on lines `start` to `end` of the document `uri`.
+pub fn code_actions(
+    uri: &str,
+    text: &str,
+    start: usize,
+    end: usize,
+    context: &Context,
+) -> Vec<Value> {
+    locate_message_issues(text, &context.types)
+        .iter()
+        .filter(|issue| start <= issue.line && issue.line <= end)
+        .filter_map(|issue| {
+            let current: String = line_text(text, issue.line)
+                .chars()
+                .skip(issue.start)
+                .take(issue.end - issue.start)
+                .collect();
+            let (title, edit_range, new_text) = match issue.kind {
+                IssueKind::Type => {
+                    let suggestion = suggest_registered_type(&current, &context.types)?;
+                    (
+                        format!("Change the type to '{suggestion}'"),
+                        range(text, issue.line, issue.start, issue.end),
+                        suggestion,
+                    )
+                }
+                IssueKind::Scope => match auto_correct_scope(&current.to_lowercase()) {
+                    scope if scope.is_empty() => (
+                        "Remove the scope".to_string(),
+                        range(text, issue.line, issue.start - 1, issue.end + 1),
+                        String::new(),
+                    ),
+                    scope => (
+                        format!("Change the scope to '{scope}'"),
+                        range(text, issue.line, issue.start, issue.end),
+                        scope,
+                    ),
+                },
+                IssueKind::BodySeparator => (
+                    "Insert a blank line after the header".to_string(),
+                    range(text, issue.line, 0, 0),
+                    "\n".to_string(),
+                ),
+                IssueKind::Footer => (
+                    "Write 'BREAKING CHANGE: '".to_string(),
+                    range(text, issue.line, issue.start, issue.end),
+                    "BREAKING CHANGE: ".to_string(),
+                ),
+                IssueKind::Header => return None,
+            };
+            Some(json!({
+                "title": title,
+                "kind": "quickfix",
+                "diagnostics": [diagnostic(text, issue)],
+                "isPreferred": true,
+                "edit": { "changes": { uri: [{ "range": edit_range, "newText": new_text }] } },
+            }))
+        })
+        .collect()
+}
+
+fn line_text(text: &str, line: usize) -> &str {
+    text.lines().nth(line).unwrap_or_default()
+}
+
+/// An LSP range on `line`, from character `start` to `end`.
+fn range(text: &str, line: usize, start: usize, end: usize) -> Value {
+    let line_text = line_text(text, line);
+    json!({
+        "start": { "line": line, "character": utf16_column(line_text, start) },
+        "end": { "line": line, "character": utf16_column(line_text, end) },
+    })
+}
+
+/// LSP positions count UTF-16 code units.
+fn utf16_column(line: &str, chars: usize) -> usize {
+    line.chars().take(chars).map(char::len_utf16).sum()
+}
+
+fn char_column(line: &str, utf16: usize) -> usize {
+    let mut units = 0;
+    line.chars()
+        .take_while(|c| {
+            units += c.len_utf16();
+            units <= utf16
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> Context {
+        Context {
+            types: CommitTypeRegistry::default(),
+            repo: None,
+        }
+    }
+
+    #[test]
+    fn test_uri_to_path() {
+        assert_eq!(
+            uri_to_path("file:///home/me/my%20repo/.git/COMMIT_EDITMSG"),
+            Some(PathBuf::from("/home/me/my repo/.git/COMMIT_EDITMSG"))
+        );
+        assert_eq!(uri_to_path("untitled:1"), None);
+    }
+
+    #[test]
+    fn test_types_are_completed_on_the_header() {
+        let text = "fe\n\n# Please enter the commit message";
+        let items = completions(text, 0, 2, &context());
+        assert!(items.iter().any(|item| item["label"] == "feat"));
+        assert_eq!(items[0]["textEdit"]["range"]["end"]["character"], 2);
+
+        // Nothing to complete in the body, the description or comments
+        assert!(completions(text, 2, 3, &context()).is_empty());
+        assert!(completions("feat: add", 0, 7, &context()).is_empty());
+
+        // A new message starts below the comments git may put first
+        assert!(!completions("# comment\n", 1, 0, &context()).is_empty());
+    }
+
+    #[test]
+    fn test_quick_fixes_use_the_suggestions() {
+        let uri = "file:///repo/.git/COMMIT_EDITMSG";
+        let text = "feet(My_Scope): add x\nbody";
+        let actions = code_actions(uri, text, 0, 1, &context());
+        let titles: Vec<&str> = actions
+            .iter()
+            .map(|action| action["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            titles,
+            [
+                "Change the type to 'feat'",
+                "Insert a blank line after the header"
+            ]
+        );
+
+        let text = "feat(My_Scope): add x";
+        let actions = code_actions(uri, text, 0, 0, &context());
+        let edit = &actions[0]["edit"]["changes"][uri][0];
+        assert_eq!(edit["newText"], "my-scope");
+        assert_eq!(edit["range"]["start"]["character"], 5);
+        assert_eq!(edit["range"]["end"]["character"], 13);
+    }
+
+    #[test]
+    fn test_columns_count_utf16_units() {
+        assert_eq!(utf16_column("🎉 feat", 2), 3);
+        assert_eq!(char_column("🎉 feat", 3), 2);
+        assert_eq!(char_column("feat", 10), 4);
+    }
+}
@@ -0,0 +1,314 @@
+//! A language server for commit messages over stdio: diagnostics from the linter,
+//! completion of commit types and scopes, and quick fixes.
+//!
+//! Messages are JSON-RPC 2.0 objects framed by `Content-Length` headers. Only stdout
+//! carries protocol messages; logs go to stderr.
+
+mod features;
+
+use crate::error::CliError;
+use features::Context;
+use log::debug;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serve requests read from `input` until the client exits or closes it, writing
+/// responses and notifications to `output`.
+pub fn serve(mut input: impl BufRead, mut output: impl Write) -> Result<(), CliError> {
+    let mut server = Server::default();
+    while let Some(body) = read_message(&mut input)? {
+        let messages = match serde_json::from_slice::<Value>(&body) {
+            Ok(message) => server.handle(&message),
+            Err(e) => vec![error_response(
+                Value::Null,
+                PARSE_ERROR,
+                &format!("Parse error: {e}"),
+            )],
+        };
+        for message in messages {
+            write_message(&mut output, &message)?;
+        }
+        if server.exited {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Read the body of the next message, `None` once the input is closed.
+fn read_message(input: &mut impl BufRead) -> Result<Option<Vec<u8>>, CliError> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            if length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let mut body = vec![0; length.unwrap_or_default()];
+    input.read_exact(&mut body)?;
+    Ok(Some(body))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> Result<(), CliError> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    output.flush()?;
+    Ok(())
+}
+
+#[derive(Default)]
+struct Server {
+    /// Text of the open documents, by URI
+    documents: HashMap<String, String>,
+    /// Workspace root, for documents outside of any repository
+    root: Option<PathBuf>,
+    shutdown: bool,
+    exited: bool,
+}
+
+impl Server {
+    /// Answer one message, along with the notifications it triggers.
+    fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            // A response to a request we never send, or garbage
+            return message
+                .get("id")
+                .map(|id| error_response(id.clone(), INVALID_REQUEST, "Invalid request: no method"))
+                .into_iter()
+                .collect();
+        };
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let Some(id) = message.get("id").cloned() else {
+            debug!("LSP notification: {method}");
+            return self.notify(method, &params);
+        };
+        debug!("LSP request: {method}");
+
+        let result = if self.shutdown && method != "shutdown" {
+            Err((INVALID_REQUEST, "The server is shutting down".to_string()))
+        } else {
+            match method {
+                "initialize" => Ok(self.initialize(&params)),
+                "shutdown" => {
+                    self.shutdown = true;
+                    Ok(Value::Null)
+                }
+                "textDocument/completion" => self.completion(&params),
+                "textDocument/codeAction" => self.code_action(&params),
+                _ => Err((METHOD_NOT_FOUND, format!("Method not found: {method}"))),
+            }
+        };
+        vec![match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        }]
+    }
+
+    fn notify(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        match method {
+            "exit" => {
+                self.exited = true;
+                Vec::new()
+            }
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.to_string(), text.to_string());
+                vec![self.publish_diagnostics(uri)]
+            }
+            "textDocument/didChange" => {
+                // Full synchronization: the last change holds the whole text
+                let Some(text) = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                else {
+                    return Vec::new();
+                };
+                self.documents.insert(uri.to_string(), text.to_string());
+                vec![self.publish_diagnostics(uri)]
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                vec![json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/publishDiagnostics",
+                    "params": { "uri": uri, "diagnostics": [] },
+                })]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn initialize(&mut self, params: &Value) -> Value {
+        self.root = params["rootUri"]
+            .as_str()
+            .or_else(|| params["workspaceFolders"][0]["uri"].as_str())
+            .and_then(features::uri_to_path);
+        json!({
+            "capabilities": {
+                "textDocumentSync": { "openClose": true, "change": 1 },
+                "completionProvider": { "triggerCharacters": ["("] },
+                "codeActionProvider": { "codeActionKinds": ["quickfix"] },
+            },
+            "serverInfo": { "name": "committy", "version": env!("CARGO_PKG_VERSION") },
+        })
+    }
+
+    fn publish_diagnostics(&self, uri: &str) -> Value {
+        let text = self.documents.get(uri).map_or("", String::as_str);
+        let context = self.context(uri);
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": features::diagnostics(text, &context) },
+        })
+    }
+
+    fn completion(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let (uri, text) = self.document(params)?;
+        let position = &params["position"];
+        let items = features::completions(
+            text,
+            position["line"].as_u64().unwrap_or_default() as usize,
+            position["character"].as_u64().unwrap_or_default() as usize,
+            &self.context(uri),
+        );
+        Ok(Value::Array(items))
+    }
+
+    fn code_action(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let (uri, text) = self.document(params)?;
+        let range = &params["range"];
+        let actions = features::code_actions(
+            uri,
+            text,
+            range["start"]["line"].as_u64().unwrap_or_default() as usize,
+            range["end"]["line"].as_u64().unwrap_or_default() as usize,
+            &self.context(uri),
+        );
+        Ok(Value::Array(actions))
+    }
+
+    /// The URI and text of the open document a request is about.
+    fn document<'a>(&'a self, params: &'a Value) -> Result<(&'a str, &'a str), (i64, String)> {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .ok_or((INVALID_PARAMS, "Missing textDocument.uri".to_string()))?;
+        let text = self
+            .documents
+            .get(uri)
+            .ok_or((INVALID_PARAMS, format!("Document not open: {uri}")))?;
+        Ok((uri, text))
+    }
+
+    fn context(&self, uri: &str) -> Context {
+        let dir = features::uri_to_path(uri)
+            .and_then(|path| path.parent().map(PathBuf::from))
+            .or_else(|| self.root.clone());
+        Context::for_dir(dir.as_deref())
+    }
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(message: &Value) -> String {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{body}", body.len())
+    }
+
+    fn responses(output: &[u8]) -> Vec<Value> {
+        let mut input = output;
+        let mut messages = Vec::new();
+        while let Some(body) = read_message(&mut input).unwrap() {
+            messages.push(serde_json::from_slice(&body).unwrap());
+        }
+        messages
+    }
+
+    #[test]
+    fn test_serve_frames_messages_and_stops_on_exit() {
+        let input = [
+            frame(&json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} })),
+            frame(&json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} })),
+            frame(&json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" })),
+            frame(&json!({ "jsonrpc": "2.0", "method": "exit" })),
+            frame(&json!({ "jsonrpc": "2.0", "id": 3, "method": "shutdown" })),
+        ]
+        .concat();
+        let mut output = Vec::new();
+        serve(input.as_bytes(), &mut output).unwrap();
+
+        let messages = responses(&output);
+        assert_eq!(messages.len(), 2);
+        assert_eq!(
+            messages[0]["result"]["capabilities"]["textDocumentSync"]["change"],
+            1
+        );
+        assert_eq!(messages[1]["id"], 2);
+        assert_eq!(messages[1]["result"], Value::Null);
+    }
+
+    #[test]
+    fn test_documents_are_linted_as_they_change() {
+        let mut server = Server::default();
+        let uri = "untitled:COMMIT_EDITMSG";
+        let open = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": uri, "text": "feet: add x" } },
+        }));
+        assert_eq!(open[0]["method"], "textDocument/publishDiagnostics");
+        let diagnostics = &open[0]["params"]["diagnostics"];
+        assert_eq!(diagnostics[0]["code"], "type");
+        assert_eq!(diagnostics[0]["range"]["end"]["character"], 4);
+
+        let change = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": { "uri": uri, "version": 2 },
+                "contentChanges": [{ "text": "feat: add x" }],
+            },
+        }));
+        assert_eq!(change[0]["params"]["diagnostics"], json!([]));
+
+        let missing = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "textDocument/codeAction",
+            "params": { "textDocument": { "uri": "untitled:other" } },
+        }));
+        assert_eq!(missing[0]["error"]["code"], INVALID_PARAMS);
+    }
+}
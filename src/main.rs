@@ -1,6 +1,7 @@
 include!(concat!(env!("OUT_DIR"), "/sentry_dsn.rs"));
 
 mod ai;
+mod api;
 mod cli;
 mod config;
 mod error;
@@ -8,6 +9,8 @@ mod git;
 mod input;
 mod linter;
 mod logger;
+mod lsp;
+mod mcp;
 mod release;
 mod telemetry;
 mod update;
@@ -77,14 +80,18 @@ fn main() {
     });
 
     if let Err(e) = run(&mut config) {
-        // Map specific errors to exit codes
-        if let Some(CliError::LintIssues(_)) = e.downcast_ref::<CliError>() {
-            eprintln!("{e}");
-            std::process::exit(3);
-        } else {
-            eprintln!("{e}");
-            std::process::exit(1);
-        }
+        eprintln!("{e}");
+        // Each kind of error has its own stable exit code
+        let code = match e.downcast_ref::<CliError>() {
+            Some(error) => {
+                if let Some(hint) = error.hint() {
+                    eprintln!("{hint}");
+                }
+                error.exit_code()
+            }
+            None => 1,
+        };
+        std::process::exit(code);
     }
 }
 
@@ -0,0 +1,199 @@
+//! A Model Context Protocol server over stdio, exposing the [`crate::api`] operations
+//! as tools.
+//!
+//! Messages are JSON-RPC 2.0 objects, one per line. Only stdout carries protocol
+//! messages; logs go to stderr.
+
+mod tools;
+
+use crate::error::CliError;
+use log::{debug, warn};
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+
+/// The protocol revision answered when the client asks for one we do not know.
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+
+const SUPPORTED_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26", PROTOCOL_VERSION];
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serve requests read from `input` until it is closed, writing responses to `output`.
+pub fn serve(input: impl BufRead, mut output: impl Write) -> Result<(), CliError> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => handle_message(&message),
+            Err(e) => Some(error_response(
+                Value::Null,
+                PARSE_ERROR,
+                &format!("Parse error: {e}"),
+            )),
+        };
+        if let Some(response) = response {
+            writeln!(output, "{response}")?;
+            output.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Answer one JSON-RPC message (or batch). Notifications get no answer.
+pub fn handle_message(message: &Value) -> Option<Value> {
+    if let Some(batch) = message.as_array() {
+        let responses: Vec<Value> = batch.iter().filter_map(handle_message).collect();
+        return (!responses.is_empty()).then_some(Value::Array(responses));
+    }
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        // A response to a request we never send, or garbage
+        return message
+            .get("id")
+            .map(|id| error_response(id.clone(), INVALID_REQUEST, "Invalid request: no method"));
+    };
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    let Some(id) = message.get("id").cloned() else {
+        debug!("MCP notification: {method}");
+        return None;
+    };
+    debug!("MCP request: {method}");
+
+    let result = match method {
+        "initialize" => Ok(initialize(&params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tools::list() })),
+        "tools/call" => call_tool(&params),
+        _ => Err((METHOD_NOT_FOUND, format!("Method not found: {method}"))),
+    };
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, &message),
+    })
+}
+
+fn initialize(params: &Value) -> Value {
+    let requested = params
+        .get("protocolVersion")
+        .and_then(Value::as_str)
+        .unwrap_or(PROTOCOL_VERSION);
+    let version = if SUPPORTED_VERSIONS.contains(&requested) {
+        requested
+    } else {
+        warn!("Unsupported MCP protocol version {requested}, answering {PROTOCOL_VERSION}");
+        PROTOCOL_VERSION
+    };
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": { "listChanged": false } },
+        "serverInfo": { "name": "committy", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "Conventional commits, linting, versioning and tagging for git repositories. Tools that change the repository say so in their description.",
+    })
+}
+
+fn call_tool(params: &Value) -> Result<Value, (i64, String)> {
+    let Some(name) = params.get("name").and_then(Value::as_str) else {
+        return Err((INVALID_PARAMS, "Missing tool name".to_string()));
+    };
+    let arguments = params
+        .get("arguments")
+        .cloned()
+        .unwrap_or_else(|| json!({}));
+    let envelope = tools::call(name, arguments).map_err(|message| (INVALID_PARAMS, message))?;
+    Ok(json!({
+        "content": [{ "type": "text", "text": envelope.to_string() }],
+        "structuredContent": envelope,
+        "isError": !envelope["ok"].as_bool().unwrap_or(false),
+    }))
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initialize_negotiates_the_protocol_version() {
+        let response = handle_message(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": { "protocolVersion": "2024-11-05", "capabilities": {} },
+        }))
+        .unwrap();
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["protocolVersion"], "2024-11-05");
+        assert_eq!(response["result"]["serverInfo"]["name"], "committy");
+
+        let response = handle_message(&json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "initialize",
+            "params": { "protocolVersion": "1999-01-01" },
+        }))
+        .unwrap();
+        assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSION);
+    }
+
+    #[test]
+    fn test_notifications_are_not_answered() {
+        assert!(handle_message(&json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/initialized",
+        }))
+        .is_none());
+    }
+
+    #[test]
+    fn test_unknown_methods_and_tools_are_errors() {
+        let response = handle_message(&json!({
+            "jsonrpc": "2.0",
+            "id": "a",
+            "method": "resources/list",
+        }))
+        .unwrap();
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = handle_message(&json!({
+            "jsonrpc": "2.0",
+            "id": "b",
+            "method": "tools/call",
+            "params": { "name": "nope", "arguments": {} },
+        }))
+        .unwrap();
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_serve_answers_line_by_line() {
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#,
+            "\n",
+            "not json\n",
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            "\n",
+        );
+        let mut output = Vec::new();
+        serve(input.as_bytes(), &mut output).unwrap();
+        let lines: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["result"], json!({}));
+        assert_eq!(lines[1]["error"]["code"], PARSE_ERROR);
+    }
+}
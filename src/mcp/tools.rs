@@ -0,0 +1,395 @@
+use crate::api::{self, AiOptions, CommitRequest, GroupOptions, VersionOptions};
+use crate::cli::commands::lint_message::LintMessageOutput;
+use crate::cli::output::Envelope;
+use crate::error::CliError;
+use crate::git::TagGeneratorOptions;
+use crate::linter::check_message_format;
+use schemars::{schema_for, JsonSchema, Schema};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+use structopt::StructOpt;
+
+struct Tool {
+    name: &'static str,
+    description: &'static str,
+    input_schema: fn() -> Schema,
+    /// Whether the tool leaves the repository untouched
+    read_only: bool,
+}
+
+const TOOLS: &[Tool] = &[
+    Tool {
+        name: "plan_commit",
+        description: "Validate the parts of a conventional commit and format its message, without committing. Misspelled types and malformed scopes are corrected and reported.",
+        input_schema: || schema_for!(CommitArgs),
+        read_only: true,
+    },
+    Tool {
+        name: "commit",
+        description: "Commit the staged changes with a conventional commit message (or amend the last commit). Changes the repository.",
+        input_schema: || schema_for!(CommitArgs),
+        read_only: false,
+    },
+    Tool {
+        name: "lint",
+        description: "Lint the commits of a range (by default since the last tag) against the conventional commit format.",
+        input_schema: || schema_for!(LintArgs),
+        read_only: true,
+    },
+    Tool {
+        name: "lint_message",
+        description: "Lint a single commit message against the conventional commit format.",
+        input_schema: || schema_for!(LintMessageArgs),
+        read_only: true,
+    },
+    Tool {
+        name: "next_version",
+        description: "Compute the next tag from the commits since the previous one, without creating anything.",
+        input_schema: || schema_for!(VersionArgs),
+        read_only: true,
+    },
+    Tool {
+        name: "tag",
+        description: "Create the next tag (or a named one), optionally bumping version files first. Pushed to origin only with push. Changes the repository.",
+        input_schema: || schema_for!(TagArgs),
+        read_only: false,
+    },
+    Tool {
+        name: "group_commit_plan",
+        description: "Group the changed files by kind (docs, tests, ci, deps, build, chore, code) with a suggested commit message each.",
+        input_schema: || schema_for!(GroupArgs),
+        read_only: true,
+    },
+    Tool {
+        name: "group_commit_apply",
+        description: "Make one conventional commit per group of changed files, optionally staging them and pushing. Changes the repository.",
+        input_schema: || schema_for!(GroupApplyArgs),
+        read_only: false,
+    },
+];
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct CommitArgs {
+    /// Path to the git repository
+    repo_path: String,
+    /// Commit type, e.g. feat, fix, docs
+    commit_type: String,
+    #[serde(default)]
+    scope: String,
+    /// First line of the message, after the type and scope
+    short_message: String,
+    #[serde(default)]
+    long_message: String,
+    #[serde(default)]
+    breaking_change: bool,
+    /// Amend the last commit instead of creating one (commit only)
+    #[serde(default)]
+    amend: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct LintArgs {
+    /// Path to the git repository
+    repo_path: String,
+    /// Start of the range (exclusive), the last tag by default
+    from: Option<String>,
+    /// End of the range, HEAD by default
+    to: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct LintMessageArgs {
+    message: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct VersionArgs {
+    /// Path to the git repository
+    repo_path: String,
+    /// Fetch tags from origin first
+    #[serde(default)]
+    fetch: bool,
+    /// Compute a pre-release version
+    #[serde(default)]
+    prerelease: bool,
+    /// Pre-release suffix, beta by default
+    prerelease_suffix: Option<String>,
+    /// Release branches, master and main by default
+    release_branches: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct TagArgs {
+    #[serde(flatten)]
+    version: VersionArgs,
+    /// Tag name to create instead of the computed one
+    name: Option<String>,
+    /// Push the tag (and the version bump commit) to origin
+    #[serde(default)]
+    push: bool,
+    /// Write the new version into version files and commit them first
+    #[serde(default)]
+    bump_files: bool,
+    /// Tag message template ({tag}, {version}, {previous_tag}, {date}, {notes})
+    tag_message: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct GroupArgs {
+    /// Path to the git repository
+    repo_path: String,
+    /// Include unstaged and untracked changes, not only the index
+    #[serde(default)]
+    include_unstaged: bool,
+    /// Improve the suggested messages with an AI provider
+    #[serde(default)]
+    ai: bool,
+    /// openrouter (default) or ollama
+    ai_provider: Option<String>,
+    ai_model: Option<String>,
+    ai_base_url: Option<String>,
+    /// Environment variable holding the OpenRouter API key
+    ai_api_key_env: Option<String>,
+    /// Send file names to the AI provider
+    #[serde(default)]
+    ai_allow_sensitive: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct GroupApplyArgs {
+    #[serde(flatten)]
+    group: GroupArgs,
+    /// Stage each group's files before committing it
+    #[serde(default)]
+    auto_stage: bool,
+    /// Push the commits to origin
+    #[serde(default)]
+    push: bool,
+}
+
+/// The tools, as listed by `tools/list`.
+pub fn list() -> Vec<Value> {
+    TOOLS
+        .iter()
+        .map(|tool| {
+            let mut schema = (tool.input_schema)();
+            schema.remove("$schema");
+            schema.remove("title");
+            json!({
+                "name": tool.name,
+                "description": tool.description,
+                "inputSchema": schema,
+                "annotations": {
+                    "readOnlyHint": tool.read_only,
+                    "destructiveHint": false,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Run the tool `name` and return its result envelope. Unknown tools and invalid
+/// arguments are protocol errors; failures of the tool itself are reported in the
+/// envelope.
+pub fn call(name: &str, arguments: Value) -> Result<Value, String> {
+    let envelope = match name {
+        "plan_commit" => {
+            let args: CommitArgs = parse(arguments)?;
+            render(
+                name,
+                api::plan_commit(Path::new(&args.repo_path), &args.request()),
+            )
+        }
+        "commit" => {
+            let args: CommitArgs = parse(arguments)?;
+            render(
+                name,
+                api::create_commit(Path::new(&args.repo_path), &args.request()),
+            )
+        }
+        "lint" => {
+            let args: LintArgs = parse(arguments)?;
+            let to = args.to.as_deref().unwrap_or("HEAD");
+            render(
+                name,
+                api::lint_range(Path::new(&args.repo_path), args.from.as_deref(), to),
+            )
+        }
+        "lint_message" => {
+            let args: LintMessageArgs = parse(arguments)?;
+            let issues = check_message_format(&args.message);
+            let count = issues.len();
+            let errors = if count == 0 {
+                Vec::new()
+            } else {
+                vec![(&CliError::LintIssues(count)).into()]
+            };
+            to_value(Envelope::new(name, LintMessageOutput { count, issues }).with_errors(errors))
+        }
+        "next_version" => {
+            let args: VersionArgs = parse(arguments)?;
+            render(name, api::compute_next_version(&args.version_options()))
+        }
+        "tag" => {
+            let args: TagArgs = parse(arguments)?;
+            let mut extra = Vec::new();
+            if !args.push {
+                extra.push("--not-publish");
+            }
+            if let Some(message) = &args.tag_message {
+                extra.extend(["--tag-message", message]);
+            }
+            let options = args.version.tag_options(&extra)?;
+            render(
+                name,
+                api::create_tag(&options, args.name.as_deref(), args.bump_files),
+            )
+        }
+        "group_commit_plan" => {
+            let args: GroupArgs = parse(arguments)?;
+            render(
+                name,
+                api::plan_groups(Path::new(&args.repo_path), &args.options()),
+            )
+        }
+        "group_commit_apply" => {
+            let args: GroupApplyArgs = parse(arguments)?;
+            render(
+                name,
+                api::apply_groups(
+                    Path::new(&args.group.repo_path),
+                    &args.group.options(),
+                    args.auto_stage,
+                    args.push,
+                ),
+            )
+        }
+        _ => return Err(format!("Unknown tool: {name}")),
+    };
+    Ok(envelope)
+}
+
+fn parse<T: DeserializeOwned>(arguments: Value) -> Result<T, String> {
+    serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {e}"))
+}
+
+fn render<T: Serialize>(name: &str, result: Result<T, CliError>) -> Value {
+    match result {
+        Ok(data) => to_value(Envelope::new(name, data)),
+        Err(e) => to_value(Envelope::failure(name, &e)),
+    }
+}
+
+fn to_value<T: Serialize>(envelope: Envelope<T>) -> Value {
+    serde_json::to_value(envelope).unwrap()
+}
+
+impl CommitArgs {
+    fn request(&self) -> CommitRequest {
+        CommitRequest {
+            commit_type: self.commit_type.clone(),
+            scope: self.scope.clone(),
+            short_message: self.short_message.clone(),
+            long_message: self.long_message.clone(),
+            breaking_change: self.breaking_change,
+            amend: self.amend,
+        }
+    }
+}
+
+impl VersionArgs {
+    /// The options for computing the next version of the repository.
+    fn version_options(&self) -> VersionOptions {
+        let defaults = VersionOptions::default();
+        VersionOptions {
+            source: self.repo_path.clone().into(),
+            prerelease: self.prerelease,
+            prerelease_suffix: self
+                .prerelease_suffix
+                .clone()
+                .unwrap_or(defaults.prerelease_suffix),
+            release_branches: self
+                .release_branches
+                .clone()
+                .unwrap_or(defaults.release_branches),
+            fetch: self.fetch,
+            ..defaults
+        }
+    }
+
+    /// The tag options for the repository, as the `tag` command would parse them.
+    fn tag_options(&self, extra: &[&str]) -> Result<TagGeneratorOptions, String> {
+        let mut args = vec!["committy", "--source", &self.repo_path];
+        args.push(if self.fetch { "--fetch" } else { "--no-fetch" });
+        if self.prerelease {
+            args.push("--prerelease");
+        }
+        if let Some(suffix) = &self.prerelease_suffix {
+            args.extend(["--prerelease-suffix", suffix]);
+        }
+        let branches = self.release_branches.as_ref().map(|b| b.join(","));
+        if let Some(branches) = &branches {
+            args.extend(["--release-branches", branches]);
+        }
+        args.extend(extra);
+        TagGeneratorOptions::from_iter_safe(args).map_err(|e| format!("Invalid arguments: {e}"))
+    }
+}
+
+impl GroupArgs {
+    fn options(&self) -> GroupOptions {
+        let ai = self.ai.then(|| {
+            let defaults = AiOptions::default();
+            AiOptions {
+                provider: self.ai_provider.clone().unwrap_or(defaults.provider),
+                model: self.ai_model.clone(),
+                base_url: self.ai_base_url.clone(),
+                api_key_env: self.ai_api_key_env.clone().unwrap_or(defaults.api_key_env),
+                allow_sensitive: self.ai_allow_sensitive,
+                ..AiOptions::default()
+            }
+        });
+        GroupOptions {
+            include_unstaged: self.include_unstaged,
+            ai,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_tool_has_an_object_input_schema() {
+        let tools = list();
+        assert_eq!(tools.len(), TOOLS.len());
+        for tool in &tools {
+            assert_eq!(tool["inputSchema"]["type"], "object", "{}", tool["name"]);
+        }
+        let tag = tools.iter().find(|t| t["name"] == "tag").unwrap();
+        let properties = tag["inputSchema"]["properties"].as_object().unwrap();
+        assert!(properties.contains_key("repo_path"));
+        assert!(properties.contains_key("push"));
+        assert_eq!(tag["inputSchema"]["required"], json!(["repo_path"]));
+    }
+
+    #[test]
+    fn test_lint_message_reports_issues_in_the_envelope() {
+        let ok = call("lint_message", json!({ "message": "feat: add x" })).unwrap();
+        assert_eq!(ok["ok"], true);
+        assert_eq!(ok["data"]["count"], 0);
+
+        let bad = call("lint_message", json!({ "message": "add x" })).unwrap();
+        assert_eq!(bad["ok"], false);
+        assert_eq!(bad["errors"][0]["code"], "lint_issues");
+    }
+
+    #[test]
+    fn test_invalid_arguments_are_rejected() {
+        let error = call("lint_message", json!({})).unwrap_err();
+        assert!(error.starts_with("Invalid arguments"), "{error}");
+    }
+}
@@ -1 +1,197 @@
+use crate::config::commit_types::CommitTypeRegistry;
+use crate::error::CliError;
+use git2::{Oid, Repository};
+use once_cell::sync::Lazy;
+use regex::Regex;
 
+static HEADER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?P<type>[a-zA-Z]+)(?:\((?P<scope>[^)]+)\))?(?P<breaking>!)?: (?P<desc>.+)$")
+        .unwrap()
+});
+static BREAKING_FOOTER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^BREAKING[ -]CHANGE: ").unwrap());
+
+/// A single conventional commit as it appears in the release notes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangelogEntry {
+    pub commit_type: String,
+    pub scope: Option<String>,
+    pub description: String,
+    pub breaking: bool,
+    pub sha: String,
+}
+
+impl ChangelogEntry {
+    /// Parse a commit message; returns `None` for non-conventional messages.
+    pub fn parse(message: &str, sha: &str) -> Option<Self> {
+        let header = message.lines().next()?.trim();
+        let captures = HEADER_REGEX.captures(header)?;
+        Some(ChangelogEntry {
+            commit_type: captures["type"].to_lowercase(),
+            scope: captures.name("scope").map(|s| s.as_str().to_string()),
+            description: captures["desc"].trim().to_string(),
+            breaking: captures.name("breaking").is_some()
+                || BREAKING_FOOTER_REGEX.is_match(message),
+            sha: sha.to_string(),
+        })
+    }
+
+    fn render_line(&self) -> String {
+        match &self.scope {
+            Some(scope) => format!("- **{scope}:** {} ({})", self.description, self.sha),
+            None => format!("- {} ({})", self.description, self.sha),
+        }
+    }
+}
+
+/// Release notes for a range of commits, grouped by changelog section.
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseNotes {
+    pub entries: Vec<ChangelogEntry>,
+}
+
+impl ReleaseNotes {
+    /// Collect the conventional commits reachable from `to` but not from `from`,
+    /// newest first.
+    pub fn from_range(repo: &Repository, from: Option<Oid>, to: Oid) -> Result<Self, CliError> {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(to)?;
+        if let Some(from) = from {
+            revwalk.hide(from)?;
+        }
+
+        let mut entries = Vec::new();
+        for oid in revwalk {
+            let commit = repo.find_commit(oid?)?;
+            let sha = commit.as_object().short_id()?;
+            if let Some(entry) = ChangelogEntry::parse(
+                commit.message().unwrap_or(""),
+                sha.as_str().unwrap_or_default(),
+            ) {
+                entries.push(entry);
+            }
+        }
+        Ok(ReleaseNotes { entries })
+    }
+
+    /// Render the notes as markdown sections, without a version heading. Sections and
+    /// their order come from the commit types; types without a section are left out.
+    pub fn render(&self, types: &CommitTypeRegistry) -> String {
+        let mut sections = Vec::new();
+
+        let breaking: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|e| e.breaking)
+            .map(ChangelogEntry::render_line)
+            .collect();
+        if !breaking.is_empty() {
+            sections.push(format!("### ⚠ BREAKING CHANGES\n\n{}", breaking.join("\n")));
+        }
+
+        for (title, section_types) in types.sections() {
+            let lines: Vec<String> = self
+                .entries
+                .iter()
+                .filter(|e| section_types.contains(&e.commit_type.as_str()))
+                .map(ChangelogEntry::render_line)
+                .collect();
+            if !lines.is_empty() {
+                sections.push(format!("### {title}\n\n{}", lines.join("\n")));
+            }
+        }
+
+        sections.join("\n\n")
+    }
+}
+
+/// Insert a release section into the content of a changelog file, newest first: after a
+/// leading `# ...` title when there is one, otherwise under a new `# Changelog` title.
+pub fn prepend_release(changelog: &str, heading: &str, notes: &str) -> String {
+    let notes = if notes.trim().is_empty() {
+        "No notable changes."
+    } else {
+        notes.trim()
+    };
+    let section = format!("## {heading}\n\n{notes}\n");
+    let (title, rest) = match changelog.strip_prefix("# ") {
+        Some(_) => {
+            let (title, rest) = changelog.split_once('\n').unwrap_or((changelog, ""));
+            (title.to_string(), rest.trim_start_matches('\n'))
+        }
+        None => ("# Changelog".to_string(), changelog),
+    };
+    if rest.trim().is_empty() {
+        format!("{title}\n\n{section}")
+    } else {
+        format!("{title}\n\n{section}\n{rest}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_conventional_commit() {
+        let entry = ChangelogEntry::parse("feat(cli): add version command\n\nBody", "abc1234")
+            .expect("conventional commit");
+        assert_eq!(entry.commit_type, "feat");
+        assert_eq!(entry.scope.as_deref(), Some("cli"));
+        assert_eq!(entry.description, "add version command");
+        assert!(!entry.breaking);
+
+        let breaking = ChangelogEntry::parse("fix!: drop legacy flag", "abc1234").unwrap();
+        assert!(breaking.breaking);
+        let footer =
+            ChangelogEntry::parse("refactor: rework config\n\nBREAKING CHANGE: new keys", "a")
+                .unwrap();
+        assert!(footer.breaking);
+
+        assert!(ChangelogEntry::parse("Merge branch 'main'", "abc1234").is_none());
+    }
+
+    #[test]
+    fn test_render_groups_by_section() {
+        let notes = ReleaseNotes {
+            entries: vec![
+                ChangelogEntry::parse("fix: handle empty tags", "2222222").unwrap(),
+                ChangelogEntry::parse("chore: tidy up", "3333333").unwrap(),
+                ChangelogEntry::parse("feat(tag)!: sign tags", "1111111").unwrap(),
+            ],
+        };
+        let types = CommitTypeRegistry::default();
+        assert_eq!(
+            notes.render(&types),
+            "### ⚠ BREAKING CHANGES\n\n- **tag:** sign tags (1111111)\n\n\
+             ### Features\n\n- **tag:** sign tags (1111111)\n\n\
+             ### Bug Fixes\n\n- handle empty tags (2222222)"
+        );
+
+        let hidden = ReleaseNotes {
+            entries: vec![ChangelogEntry::parse("chore: tidy up", "3333333").unwrap()],
+        };
+        assert_eq!(hidden.render(&types), "");
+    }
+
+    #[test]
+    fn test_prepend_release() {
+        assert_eq!(
+            prepend_release("", "v1.0.0 (2024-01-02)", "### Features\n\n- a (1)"),
+            "# Changelog\n\n## v1.0.0 (2024-01-02)\n\n### Features\n\n- a (1)\n"
+        );
+        assert_eq!(
+            prepend_release(
+                "# Changelog\n\n## v1.0.0 (2024-01-02)\n\nNo notable changes.\n",
+                "v1.1.0 (2024-02-03)",
+                ""
+            ),
+            "# Changelog\n\n## v1.1.0 (2024-02-03)\n\nNo notable changes.\n\n\
+             ## v1.0.0 (2024-01-02)\n\nNo notable changes.\n"
+        );
+        assert_eq!(
+            prepend_release("Old notes\n", "v2.0.0 (2024-03-04)", "- b (2)"),
+            "# Changelog\n\n## v2.0.0 (2024-03-04)\n\n- b (2)\n\nOld notes\n"
+        );
+    }
+}
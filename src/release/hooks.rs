@@ -0,0 +1,112 @@
+use crate::config::ReleaseHooks;
+use crate::error::CliError;
+use log::{debug, info};
+use std::path::Path;
+use std::process::Command;
+
+/// Points of the release where hooks run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HookStage {
+    /// Before version files are bumped
+    PreBump,
+    /// After version files are bumped, before the bump commit
+    PostBump,
+    /// Before the tag is created
+    PreTag,
+    /// After the tag is created, before it is pushed
+    PostTag,
+    /// After the tag is pushed
+    PostPush,
+}
+
+impl HookStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookStage::PreBump => "pre-bump",
+            HookStage::PostBump => "post-bump",
+            HookStage::PreTag => "pre-tag",
+            HookStage::PostTag => "post-tag",
+            HookStage::PostPush => "post-push",
+        }
+    }
+
+    fn commands<'a>(&self, hooks: &'a ReleaseHooks) -> &'a [String] {
+        match self {
+            HookStage::PreBump => &hooks.pre_bump,
+            HookStage::PostBump => &hooks.post_bump,
+            HookStage::PreTag => &hooks.pre_tag,
+            HookStage::PostTag => &hooks.post_tag,
+            HookStage::PostPush => &hooks.post_push,
+        }
+    }
+}
+
+/// The release a hook runs for, exposed to hook commands as `COMMITTY_*` variables.
+#[derive(Debug, Clone)]
+pub struct HookContext {
+    pub previous_tag: String,
+    pub previous_version: String,
+    pub new_tag: String,
+    pub new_version: String,
+    pub branch: String,
+    pub pre_release: bool,
+}
+
+impl HookContext {
+    fn env(&self, stage: HookStage) -> Vec<(&'static str, String)> {
+        vec![
+            ("COMMITTY_HOOK", stage.as_str().to_string()),
+            ("COMMITTY_PREVIOUS_TAG", self.previous_tag.clone()),
+            ("COMMITTY_PREVIOUS_VERSION", self.previous_version.clone()),
+            ("COMMITTY_NEW_TAG", self.new_tag.clone()),
+            ("COMMITTY_NEW_VERSION", self.new_version.clone()),
+            ("COMMITTY_BRANCH", self.branch.clone()),
+            ("COMMITTY_PRE_RELEASE", self.pre_release.to_string()),
+        ]
+    }
+}
+
+/// Run the commands of `stage` in `workdir` with the system shell. Their output goes to
+/// stderr so it does not mix with the command's own output.
+pub fn run_hooks(
+    hooks: &ReleaseHooks,
+    stage: HookStage,
+    context: &HookContext,
+    workdir: &Path,
+) -> Result<(), CliError> {
+    for command in stage.commands(hooks) {
+        info!("🪝 Running {} hook: {command}", stage.as_str());
+        let mut shell = if cfg!(windows) {
+            let mut shell = Command::new("cmd");
+            shell.arg("/C");
+            shell
+        } else {
+            let mut shell = Command::new("sh");
+            shell.arg("-c");
+            shell
+        };
+        let status = shell
+            .arg(command)
+            .current_dir(workdir)
+            .envs(context.env(stage))
+            .stdout(std::io::stderr())
+            .status()
+            .map_err(|e| {
+                CliError::HookFailed(format!(
+                    "Failed to run {} hook '{command}': {e}",
+                    stage.as_str()
+                ))
+            })?;
+        if !status.success() {
+            let code = status
+                .code()
+                .map_or_else(|| "a signal".to_string(), |c| format!("exit code {c}"));
+            return Err(CliError::HookFailed(format!(
+                "{} hook '{command}' failed with {code}, release aborted",
+                stage.as_str()
+            )));
+        }
+        debug!("{} hook '{command}' succeeded", stage.as_str());
+    }
+    Ok(())
+}
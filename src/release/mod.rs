@@ -1 +1,4 @@
 pub mod changelog;
+pub mod hooks;
+pub mod pipeline;
+pub mod publish;
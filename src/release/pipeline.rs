@@ -0,0 +1,562 @@
+use super::changelog::prepend_release;
+use super::hooks::{run_hooks, HookStage};
+use crate::error::CliError;
+use crate::git::{TagGenerator, TagPlan};
+use chrono::Utc;
+use git2::Repository;
+use log::{debug, error, info};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Progress of an unfinished release, kept in the git directory.
+pub const RELEASE_STATE_FILE: &str = "committy-release.json";
+
+/// Steps of `committy release`, in the order they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseStep {
+    Lint,
+    Version,
+    Bump,
+    Changelog,
+    Commit,
+    Tag,
+    Push,
+    Publish,
+}
+
+impl ReleaseStep {
+    pub const ALL: [ReleaseStep; 8] = [
+        ReleaseStep::Lint,
+        ReleaseStep::Version,
+        ReleaseStep::Bump,
+        ReleaseStep::Changelog,
+        ReleaseStep::Commit,
+        ReleaseStep::Tag,
+        ReleaseStep::Push,
+        ReleaseStep::Publish,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReleaseStep::Lint => "lint",
+            ReleaseStep::Version => "version",
+            ReleaseStep::Bump => "bump",
+            ReleaseStep::Changelog => "changelog",
+            ReleaseStep::Commit => "commit",
+            ReleaseStep::Tag => "tag",
+            ReleaseStep::Push => "push",
+            ReleaseStep::Publish => "publish",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StepStatus {
+    /// The step ran
+    Done,
+    /// The step ran in an earlier, interrupted run
+    Resumed,
+    /// Dry run: what the step would do
+    Planned,
+    /// The step had nothing to do or was disabled
+    Skipped,
+}
+
+/// Outcome of one step, as printed by `committy release`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct StepReport {
+    #[serde(rename = "name")]
+    pub step: ReleaseStep,
+    pub status: StepStatus,
+    pub detail: String,
+}
+
+/// Outcome of a release run.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ReleaseReport {
+    pub previous_tag: String,
+    pub new_tag: String,
+    pub pre_release: bool,
+    pub dry_run: bool,
+    pub resumed: bool,
+    pub steps: Vec<StepReport>,
+}
+
+/// What a release has done so far, saved after every step so that a failed release can
+/// be resumed without computing the version again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseState {
+    pub plan: TagPlan,
+    pub branch: String,
+    /// Paths that were dirty before the release started; they stay out of the commit
+    pub dirty_before: Vec<String>,
+    /// Paths changed by the release so far
+    pub files: Vec<String>,
+    /// Whether a bump commit was created, so the push step pushes the branch too
+    pub committed: bool,
+    /// Whether the tag was pushed, so the forge release can be published
+    #[serde(default)]
+    pub pushed: bool,
+    pub completed: Vec<ReleaseStep>,
+}
+
+impl ReleaseState {
+    pub fn path(repo: &Repository) -> PathBuf {
+        repo.path().join(RELEASE_STATE_FILE)
+    }
+
+    pub fn load(repo: &Repository) -> Result<Option<Self>, CliError> {
+        let path = Self::path(repo);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content).map(Some).map_err(|e| {
+            CliError::ReleaseState(format!(
+                "Unreadable release state in {}: {e}; run `committy release --abort` to discard it",
+                path.display()
+            ))
+        })
+    }
+
+    fn save(&self, repo: &Repository) -> Result<(), CliError> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| CliError::ReleaseState(format!("Failed to save release state: {e}")))?;
+        fs::write(Self::path(repo), content)?;
+        Ok(())
+    }
+
+    /// Forget the release in progress. Returns whether there was one.
+    pub fn discard(repo: &Repository) -> Result<bool, CliError> {
+        let path = Self::path(repo);
+        if !path.exists() {
+            return Ok(false);
+        }
+        fs::remove_file(path)?;
+        Ok(true)
+    }
+}
+
+/// How `committy release` runs its steps.
+#[derive(Debug, Clone)]
+pub struct ReleaseOptions {
+    pub lint: bool,
+    pub bump_files: bool,
+    /// Changelog file relative to the repository root, `None` to leave it alone
+    pub changelog: Option<String>,
+    pub resume: bool,
+}
+
+/// Runs a release as explicit steps: lint, version, bump, changelog, commit, tag and
+/// push. Version bumping, tagging and hooks are done by the [`TagGenerator`].
+pub struct ReleasePipeline {
+    generator: TagGenerator,
+    options: ReleaseOptions,
+}
+
+impl ReleasePipeline {
+    pub fn new(generator: TagGenerator, options: ReleaseOptions) -> Self {
+        ReleasePipeline { generator, options }
+    }
+
+    /// Run the release, or continue the one in progress with `resume`. Returns `None`
+    /// when there is nothing to release.
+    pub fn run(&mut self) -> Result<Option<ReleaseReport>, CliError> {
+        let repo = self.generator.open_repository()?;
+        let dry_run = self.generator.is_dry_run();
+        let branch = repo.head()?.shorthand().map(String::from).ok_or_else(|| {
+            CliError::GitError(git2::Error::from_str("Failed to get current branch"))
+        })?;
+        let mut steps = Vec::new();
+
+        let (mut state, resumed) = match (ReleaseState::load(&repo)?, self.options.resume) {
+            (Some(state), true) => {
+                if state.branch != branch {
+                    return Err(CliError::ReleaseState(format!(
+                        "The release of {} was started on {}; switch back to it to resume",
+                        state.plan.new_tag, state.branch
+                    )));
+                }
+                info!("🔁 Resuming the release of {}", state.plan.new_tag);
+                self.generator.resume(&state.plan)?;
+                (state, true)
+            }
+            (Some(state), false) => {
+                return Err(CliError::ReleaseState(format!(
+                    "A release of {} is in progress; run `committy release --resume` to continue it or `committy release --abort` to discard it",
+                    state.plan.new_tag
+                )));
+            }
+            (None, true) => {
+                return Err(CliError::ReleaseState(
+                    "No release in progress to resume".to_string(),
+                ));
+            }
+            (None, false) => {
+                steps.push(self.lint()?);
+                let Some(plan) = self.generator.plan()? else {
+                    return Ok(None);
+                };
+                steps.push(StepReport {
+                    step: ReleaseStep::Version,
+                    status: StepStatus::Done,
+                    detail: format!("{} -> {} ({})", plan.previous_tag, plan.new_tag, plan.bump),
+                });
+                // Fail before changing anything when the release cannot be published
+                if !dry_run {
+                    if let Some(target) = self.generator.publish_target(&repo)? {
+                        target.token()?;
+                    }
+                }
+                let mut dirty_before: Vec<String> =
+                    self.generator.dirty_paths(&repo)?.into_iter().collect();
+                dirty_before.sort();
+                let state = ReleaseState {
+                    plan,
+                    branch,
+                    dirty_before,
+                    files: Vec::new(),
+                    committed: false,
+                    pushed: false,
+                    completed: vec![ReleaseStep::Lint, ReleaseStep::Version],
+                };
+                if !dry_run {
+                    state.save(&repo)?;
+                }
+                (state, false)
+            }
+        };
+
+        for step in ReleaseStep::ALL {
+            if state.completed.contains(&step) {
+                if resumed {
+                    steps.push(StepReport {
+                        step,
+                        status: StepStatus::Resumed,
+                        detail: "completed in a previous run".to_string(),
+                    });
+                }
+                continue;
+            }
+            debug!("Running release step: {}", step.as_str());
+            let report = self.run_step(step, &repo, &mut state).map_err(|e| {
+                if dry_run {
+                    e
+                } else {
+                    CliError::ReleaseStep {
+                        step: step.as_str().to_string(),
+                        source: Box::new(e),
+                    }
+                }
+            })?;
+            steps.push(report);
+            if !dry_run {
+                state.completed.push(step);
+                state.save(&repo)?;
+            }
+        }
+
+        if !dry_run {
+            ReleaseState::discard(&repo)?;
+            info!("🎉 Released {}", state.plan.new_tag);
+        }
+        Ok(Some(ReleaseReport {
+            previous_tag: state.plan.previous_tag,
+            new_tag: state.plan.new_tag,
+            pre_release: state.plan.pre_release,
+            dry_run,
+            resumed,
+            steps,
+        }))
+    }
+
+    fn lint(&mut self) -> Result<StepReport, CliError> {
+        if !self.options.lint {
+            return Ok(skipped(ReleaseStep::Lint, "disabled with --no-lint"));
+        }
+        let (since, issues) = self.generator.lint_release()?;
+        if !issues.is_empty() {
+            error!("Commits since {since} have lint issues, nothing was released:");
+            for issue in &issues {
+                error!("  {} {}", &issue.commit_id[..7], issue.issue);
+            }
+            // The same error as `committy lint`
+            return Err(CliError::LintIssues(issues.len()));
+        }
+        Ok(StepReport {
+            step: ReleaseStep::Lint,
+            status: StepStatus::Done,
+            detail: format!("commits since {since} follow the conventions"),
+        })
+    }
+
+    fn run_step(
+        &mut self,
+        step: ReleaseStep,
+        repo: &Repository,
+        state: &mut ReleaseState,
+    ) -> Result<StepReport, CliError> {
+        match step {
+            ReleaseStep::Lint | ReleaseStep::Version => {
+                unreachable!("lint and version run before the state exists")
+            }
+            ReleaseStep::Bump => self.bump(repo, state),
+            ReleaseStep::Changelog => self.changelog(repo, state),
+            ReleaseStep::Commit => self.commit(repo, state),
+            ReleaseStep::Tag => self.tag(repo, state),
+            ReleaseStep::Push => self.push(repo, state),
+            ReleaseStep::Publish => self.publish(repo, state),
+        }
+    }
+
+    fn bump(&self, repo: &Repository, state: &mut ReleaseState) -> Result<StepReport, CliError> {
+        let context = self.generator.hook_context(repo)?;
+        let version = context.new_version.clone();
+        if self.generator.is_dry_run() {
+            if !self.options.bump_files {
+                return Ok(skipped(ReleaseStep::Bump, "disabled with --no-bump-files"));
+            }
+            let files = self.generator.version_files()?;
+            if files.is_empty() {
+                return Ok(skipped(ReleaseStep::Bump, "no version files found"));
+            }
+            return Ok(planned(
+                ReleaseStep::Bump,
+                format!("would set version {version} in {}", files.join(", ")),
+            ));
+        }
+
+        let hooks = self.generator.release_hooks(repo)?;
+        let workdir = workdir(repo)?;
+        run_hooks(&hooks, HookStage::PreBump, &context, workdir)?;
+        let mut files = Vec::new();
+        if self.options.bump_files {
+            files = self.generator.update_versions(&version)?;
+        }
+        run_hooks(&hooks, HookStage::PostBump, &context, workdir)?;
+        add_paths(&mut state.files, &files);
+
+        if !self.options.bump_files {
+            Ok(skipped(ReleaseStep::Bump, "disabled with --no-bump-files"))
+        } else if files.is_empty() {
+            Ok(skipped(ReleaseStep::Bump, "no version files found"))
+        } else {
+            Ok(done(
+                ReleaseStep::Bump,
+                format!("set version {version} in {}", files.join(", ")),
+            ))
+        }
+    }
+
+    fn changelog(
+        &self,
+        repo: &Repository,
+        state: &mut ReleaseState,
+    ) -> Result<StepReport, CliError> {
+        let Some(file) = &self.options.changelog else {
+            return Ok(skipped(
+                ReleaseStep::Changelog,
+                "disabled with --no-changelog",
+            ));
+        };
+        let heading = format!("{} ({})", state.plan.new_tag, Utc::now().format("%Y-%m-%d"));
+        let notes = self.generator.release_notes(repo)?;
+        if self.generator.is_dry_run() {
+            let notes = if notes.is_empty() {
+                "No notable changes.".to_string()
+            } else {
+                notes
+            };
+            return Ok(planned(
+                ReleaseStep::Changelog,
+                format!("would add to {file}:\n## {heading}\n\n{notes}"),
+            ));
+        }
+
+        let path = workdir(repo)?.join(file);
+        let content = if path.exists() {
+            fs::read_to_string(&path)?
+        } else {
+            String::new()
+        };
+        fs::write(&path, prepend_release(&content, &heading, &notes))?;
+        add_paths(&mut state.files, std::slice::from_ref(file));
+        Ok(done(
+            ReleaseStep::Changelog,
+            format!("added {} to {file}", state.plan.new_tag),
+        ))
+    }
+
+    fn commit(&self, repo: &Repository, state: &mut ReleaseState) -> Result<StepReport, CliError> {
+        let version = self.generator.hook_context(repo)?.new_version;
+        let message = format!("chore: bump version to {version}");
+        if self.generator.is_dry_run() {
+            return Ok(planned(
+                ReleaseStep::Commit,
+                format!("would commit '{message}' with the files changed above"),
+            ));
+        }
+
+        // Files changed by hooks go into the bump commit, changes made beforehand do not
+        let mut dirty: Vec<String> = self.generator.dirty_paths(repo)?.into_iter().collect();
+        dirty.sort();
+        dirty.retain(|path| !state.dirty_before.contains(path));
+        add_paths(&mut state.files, &dirty);
+
+        match self
+            .generator
+            .commit_version_changes(repo, &version, &state.files)?
+        {
+            Some(oid) => {
+                state.committed = true;
+                Ok(done(
+                    ReleaseStep::Commit,
+                    format!("committed '{message}' ({})", &oid.to_string()[..7]),
+                ))
+            }
+            None => Ok(skipped(ReleaseStep::Commit, "no changes to commit")),
+        }
+    }
+
+    fn tag(&self, repo: &Repository, state: &ReleaseState) -> Result<StepReport, CliError> {
+        let tag = &state.plan.new_tag;
+        if self.generator.is_dry_run() {
+            return Ok(planned(ReleaseStep::Tag, format!("would create tag {tag}")));
+        }
+
+        let hooks = self.generator.release_hooks(repo)?;
+        let context = self.generator.hook_context(repo)?;
+        let workdir = workdir(repo)?;
+        // A tag left by an interrupted run is kept when it points at the release commit
+        let head = repo.head()?.peel_to_commit()?.id();
+        if let Ok(existing) = repo.find_reference(&format!("refs/tags/{tag}")) {
+            if existing.peel_to_commit()?.id() == head {
+                return Ok(done(ReleaseStep::Tag, format!("tag {tag} already exists")));
+            }
+        }
+        run_hooks(&hooks, HookStage::PreTag, &context, workdir)?;
+        self.generator.create_tag(repo, tag)?;
+        run_hooks(&hooks, HookStage::PostTag, &context, workdir)?;
+        Ok(done(ReleaseStep::Tag, format!("created tag {tag}")))
+    }
+
+    fn push(&self, repo: &Repository, state: &mut ReleaseState) -> Result<StepReport, CliError> {
+        let tag = &state.plan.new_tag;
+        if !self.generator.publishes() {
+            return Ok(skipped(ReleaseStep::Push, "disabled with --not-publish"));
+        }
+        if self.generator.is_dry_run() {
+            return Ok(planned(
+                ReleaseStep::Push,
+                format!("would push {} and {tag} to origin", state.branch),
+            ));
+        }
+
+        let mut pushed = Vec::new();
+        if state.committed && self.generator.push_branch(repo)? {
+            pushed.push(state.branch.as_str());
+        }
+        if self.generator.push_tag(repo, tag)? {
+            state.pushed = true;
+            pushed.push(tag.as_str());
+            let hooks = self.generator.release_hooks(repo)?;
+            let context = self.generator.hook_context(repo)?;
+            run_hooks(&hooks, HookStage::PostPush, &context, workdir(repo)?)?;
+        }
+        if pushed.is_empty() {
+            Ok(skipped(ReleaseStep::Push, "no origin remote"))
+        } else {
+            Ok(done(
+                ReleaseStep::Push,
+                format!("pushed {} to origin", pushed.join(" and ")),
+            ))
+        }
+    }
+
+    fn publish(&self, repo: &Repository, state: &ReleaseState) -> Result<StepReport, CliError> {
+        let tag = &state.plan.new_tag;
+        if !self.generator.publishes() {
+            return Ok(skipped(ReleaseStep::Publish, "disabled with --not-publish"));
+        }
+        let Some(target) = self.generator.publish_target(repo)? else {
+            return Ok(skipped(
+                ReleaseStep::Publish,
+                "enable with --publish-release or [publish] in .committy.toml",
+            ));
+        };
+        if self.generator.is_dry_run() {
+            let mut detail = format!(
+                "would publish the {} release {tag} on {}",
+                target.provider.as_str(),
+                target.repository
+            );
+            if state.plan.pre_release {
+                detail.push_str(" as a pre-release");
+            }
+            if !target.assets.is_empty() {
+                detail.push_str(&format!(" with assets {}", target.assets.join(", ")));
+            }
+            return Ok(planned(ReleaseStep::Publish, detail));
+        }
+        if !state.pushed {
+            return Ok(skipped(ReleaseStep::Publish, "the tag was not pushed"));
+        }
+
+        let published = self.generator.publish_release(repo, &target)?;
+        Ok(done(
+            ReleaseStep::Publish,
+            format!(
+                "{} {} release {}",
+                if published.created {
+                    "created"
+                } else {
+                    "updated"
+                },
+                published.provider,
+                published.url
+            ),
+        ))
+    }
+}
+
+fn workdir(repo: &Repository) -> Result<&Path, CliError> {
+    repo.workdir().ok_or_else(|| {
+        CliError::GitError(git2::Error::from_str("Repository has no working directory"))
+    })
+}
+
+fn add_paths(files: &mut Vec<String>, paths: &[String]) {
+    for path in paths {
+        if !files.contains(path) {
+            files.push(path.clone());
+        }
+    }
+}
+
+fn done(step: ReleaseStep, detail: String) -> StepReport {
+    StepReport {
+        step,
+        status: StepStatus::Done,
+        detail,
+    }
+}
+
+fn planned(step: ReleaseStep, detail: String) -> StepReport {
+    StepReport {
+        step,
+        status: StepStatus::Planned,
+        detail,
+    }
+}
+
+fn skipped(step: ReleaseStep, detail: &str) -> StepReport {
+    StepReport {
+        step,
+        status: StepStatus::Skipped,
+        detail: detail.to_string(),
+    }
+}
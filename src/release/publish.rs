@@ -0,0 +1,522 @@
+use crate::config::{ForgeProvider, PublishConfig};
+use crate::error::CliError;
+use crate::git::glob_regex;
+use log::{debug, info};
+use regex::Regex;
+use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::Method;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Where a release is published, resolved from the `[publish]` section and `origin`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublishTarget {
+    pub provider: ForgeProvider,
+    pub api_url: String,
+    pub repository: String,
+    pub token_env: String,
+    pub assets: Vec<String>,
+}
+
+/// A release to create or update.
+#[derive(Debug, Clone)]
+pub struct ReleaseRequest {
+    pub tag: String,
+    pub version: String,
+    pub name: String,
+    pub body: String,
+    pub prerelease: bool,
+}
+
+/// The release as published on the forge.
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishedRelease {
+    pub provider: String,
+    pub url: String,
+    /// `false` when an existing release was updated
+    pub created: bool,
+    pub assets: Vec<String>,
+}
+
+impl PublishTarget {
+    /// Fill in what the configuration leaves out from the `origin` URL: the provider
+    /// (GitLab when the host mentions it, GitHub otherwise), the API URL and the repository.
+    pub fn resolve(config: &PublishConfig, origin_url: Option<&str>) -> Result<Self, CliError> {
+        let origin = origin_url.and_then(parse_remote_url);
+        let host = origin.as_ref().map(|(host, _)| host.as_str());
+        let provider = config.provider.unwrap_or(match host {
+            Some(host) if host.contains("gitlab") => ForgeProvider::Gitlab,
+            _ => ForgeProvider::Github,
+        });
+        let api_url = match (&config.api_url, host) {
+            (Some(url), _) => url.trim_end_matches('/').to_string(),
+            (None, Some("github.com")) | (None, None) if provider == ForgeProvider::Github => {
+                "https://api.github.com".to_string()
+            }
+            (None, Some(host)) if provider == ForgeProvider::Github => {
+                format!("https://{host}/api/v3")
+            }
+            (None, Some(host)) => format!("https://{host}/api/v4"),
+            (None, None) => "https://gitlab.com/api/v4".to_string(),
+        };
+        let repository = match (&config.repository, origin) {
+            (Some(repository), _) => repository.clone(),
+            (None, Some((_, path))) => path,
+            (None, None) => {
+                return Err(CliError::RemoteNotFound(
+                    "Cannot tell which repository to publish to without an origin remote: set `repository` in the [publish] section of .committy.toml".to_string(),
+                ))
+            }
+        };
+        let token_env = config.token_env.clone().unwrap_or_else(|| {
+            match provider {
+                ForgeProvider::Github => "GITHUB_TOKEN",
+                ForgeProvider::Gitlab => "GITLAB_TOKEN",
+            }
+            .to_string()
+        });
+        Ok(PublishTarget {
+            provider,
+            api_url,
+            repository,
+            token_env,
+            assets: config.assets.clone(),
+        })
+    }
+
+    /// The API token, read from [`PublishTarget::token_env`].
+    pub fn token(&self) -> Result<String, CliError> {
+        std::env::var(&self.token_env)
+            .ok()
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| {
+                CliError::AuthError(format!(
+                    "Set {} to publish the {} release",
+                    self.token_env,
+                    self.provider.as_str()
+                ))
+            })
+    }
+
+    /// Create the release for `request.tag`, or update it when it already exists, and
+    /// attach the configured assets (replacing assets with the same name).
+    pub fn publish(
+        &self,
+        request: &ReleaseRequest,
+        root: &Path,
+    ) -> Result<PublishedRelease, CliError> {
+        let assets = expand_assets(root, &self.assets)?;
+        let forge = Forge {
+            target: self,
+            token: self.token()?,
+            client: Client::builder()
+                .user_agent(concat!("committy/", env!("CARGO_PKG_VERSION")))
+                .timeout(Duration::from_secs(120))
+                .build()
+                .map_err(|e| {
+                    CliError::PublishError(format!("Failed to create HTTP client: {e}"))
+                })?,
+        };
+        let published = match self.provider {
+            ForgeProvider::Github => forge.publish_github(request, &assets)?,
+            ForgeProvider::Gitlab => forge.publish_gitlab(request, &assets)?,
+        };
+        info!(
+            "📦 {} {} release {}",
+            if published.created {
+                "Created"
+            } else {
+                "Updated"
+            },
+            self.provider.as_str(),
+            published.url
+        );
+        Ok(published)
+    }
+}
+
+struct Forge<'a> {
+    target: &'a PublishTarget,
+    token: String,
+    client: Client,
+}
+
+impl Forge<'_> {
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let url = if path.starts_with("http://") || path.starts_with("https://") {
+            path.to_string()
+        } else {
+            format!("{}{path}", self.target.api_url)
+        };
+        let builder = self.client.request(method, url);
+        match self.target.provider {
+            ForgeProvider::Github => builder
+                .bearer_auth(&self.token)
+                .header("Accept", "application/vnd.github+json"),
+            ForgeProvider::Gitlab => builder.header("PRIVATE-TOKEN", &self.token),
+        }
+    }
+
+    /// Send a request and parse the JSON response. `None` for a 404 when `allow_missing`.
+    fn send(
+        &self,
+        builder: RequestBuilder,
+        allow_missing: bool,
+    ) -> Result<Option<Value>, CliError> {
+        let request = builder
+            .build()
+            .map_err(|e| CliError::PublishError(format!("Invalid request: {e}")))?;
+        let what = format!("{} {}", request.method(), request.url().path());
+        debug!("{} API request: {what}", self.target.provider.as_str());
+        let response = self.client.execute(request).map_err(|e| {
+            CliError::PublishError(format!(
+                "{} API request {what} failed: {e}",
+                self.target.provider.as_str()
+            ))
+        })?;
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND && allow_missing {
+            return Ok(None);
+        }
+        let text = response.text().unwrap_or_default();
+        if !status.is_success() {
+            let message = serde_json::from_str::<Value>(&text)
+                .ok()
+                .and_then(|v| v.get("message").or_else(|| v.get("error")).cloned())
+                .map(|m| {
+                    m.as_str()
+                        .map(String::from)
+                        .unwrap_or_else(|| m.to_string())
+                })
+                .unwrap_or(text);
+            let message = format!(
+                "{} API request {what} failed with {status}: {message}",
+                self.target.provider.as_str()
+            );
+            return Err(match status {
+                reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+                    CliError::AuthError(message)
+                }
+                _ => CliError::PublishError(message),
+            });
+        }
+        Ok(Some(serde_json::from_str(&text).unwrap_or(Value::Null)))
+    }
+
+    fn publish_github(
+        &self,
+        request: &ReleaseRequest,
+        assets: &[PathBuf],
+    ) -> Result<PublishedRelease, CliError> {
+        let base = format!("/repos/{}/releases", self.target.repository);
+        let existing = self.send(
+            self.request(
+                Method::GET,
+                &format!("{base}/tags/{}", encode_segment(&request.tag)),
+            ),
+            true,
+        )?;
+        let fields = json!({
+            "name": request.name,
+            "body": request.body,
+            "prerelease": request.prerelease,
+        });
+        let (release, created) = match existing {
+            Some(release) => {
+                let id = release["id"].as_u64().unwrap_or_default();
+                let updated = self.send(
+                    self.request(Method::PATCH, &format!("{base}/{id}"))
+                        .json(&fields),
+                    false,
+                )?;
+                // Assets are replaced by name
+                for asset in release["assets"].as_array().into_iter().flatten() {
+                    let name = asset["name"].as_str().unwrap_or_default();
+                    if assets.iter().any(|path| file_name(path) == name) {
+                        let id = asset["id"].as_u64().unwrap_or_default();
+                        self.send(
+                            self.request(Method::DELETE, &format!("{base}/assets/{id}")),
+                            true,
+                        )?;
+                    }
+                }
+                (updated.unwrap_or(release), false)
+            }
+            None => {
+                let mut fields = fields;
+                fields["tag_name"] = json!(request.tag);
+                let release = self.send(self.request(Method::POST, &base).json(&fields), false)?;
+                (release.unwrap_or(Value::Null), true)
+            }
+        };
+
+        let upload_url = release["upload_url"]
+            .as_str()
+            .map(|url| url.split('{').next().unwrap_or(url).to_string());
+        let mut uploaded = Vec::new();
+        for path in assets {
+            let Some(upload_url) = &upload_url else {
+                return Err(CliError::PublishError(
+                    "GitHub did not return an upload URL for the release assets".to_string(),
+                ));
+            };
+            let name = file_name(path);
+            debug!("Uploading release asset {name}");
+            self.send(
+                self.request(Method::POST, upload_url)
+                    .query(&[("name", name)])
+                    .header("Content-Type", "application/octet-stream")
+                    .body(fs::read(path)?),
+                false,
+            )?;
+            uploaded.push(name.to_string());
+        }
+
+        Ok(PublishedRelease {
+            provider: ForgeProvider::Github.as_str().to_string(),
+            url: release["html_url"].as_str().unwrap_or_default().to_string(),
+            created,
+            assets: uploaded,
+        })
+    }
+
+    /// GitLab has no pre-release flag; assets are uploaded to the generic package
+    /// registry and linked from the release.
+    fn publish_gitlab(
+        &self,
+        request: &ReleaseRequest,
+        assets: &[PathBuf],
+    ) -> Result<PublishedRelease, CliError> {
+        let project = format!("/projects/{}", encode_segment(&self.target.repository));
+        let release_path = format!("{project}/releases/{}", encode_segment(&request.tag));
+        let existing = self.send(self.request(Method::GET, &release_path), true)?;
+        let fields = json!({
+            "name": request.name,
+            "description": request.body,
+        });
+        let (release, created) = match existing {
+            Some(release) => {
+                let updated = self.send(
+                    self.request(Method::PUT, &release_path).json(&fields),
+                    false,
+                )?;
+                for link in release["assets"]["links"].as_array().into_iter().flatten() {
+                    let name = link["name"].as_str().unwrap_or_default();
+                    if assets.iter().any(|path| file_name(path) == name) {
+                        let id = link["id"].as_u64().unwrap_or_default();
+                        self.send(
+                            self.request(
+                                Method::DELETE,
+                                &format!("{release_path}/assets/links/{id}"),
+                            ),
+                            true,
+                        )?;
+                    }
+                }
+                (updated.unwrap_or(release), false)
+            }
+            None => {
+                let mut fields = fields;
+                fields["tag_name"] = json!(request.tag);
+                let release = self.send(
+                    self.request(Method::POST, &format!("{project}/releases"))
+                        .json(&fields),
+                    false,
+                )?;
+                (release.unwrap_or(Value::Null), true)
+            }
+        };
+
+        let package = self
+            .target
+            .repository
+            .rsplit('/')
+            .next()
+            .unwrap_or("release")
+            .to_string();
+        let mut uploaded = Vec::new();
+        for path in assets {
+            let name = file_name(path);
+            let package_path = format!(
+                "{project}/packages/generic/{}/{}/{}",
+                encode_segment(&package),
+                encode_segment(&request.version),
+                encode_segment(name)
+            );
+            debug!("Uploading release asset {name}");
+            self.send(
+                self.request(Method::PUT, &package_path)
+                    .body(fs::read(path)?),
+                false,
+            )?;
+            self.send(
+                self.request(Method::POST, &format!("{release_path}/assets/links"))
+                    .json(&json!({
+                        "name": name,
+                        "url": format!("{}{package_path}", self.target.api_url),
+                        "link_type": "package",
+                    })),
+                false,
+            )?;
+            uploaded.push(name.to_string());
+        }
+
+        Ok(PublishedRelease {
+            provider: ForgeProvider::Gitlab.as_str().to_string(),
+            url: release["_links"]["self"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            created,
+            assets: uploaded,
+        })
+    }
+}
+
+/// Files under `root` matching the asset globs (`*`, `?` and `**`), relative patterns
+/// only. A pattern that matches nothing is an error, so a missing build is not released.
+pub fn expand_assets(root: &Path, patterns: &[String]) -> Result<Vec<PathBuf>, CliError> {
+    let mut assets = Vec::new();
+    for pattern in patterns {
+        let pattern = pattern.trim().trim_start_matches("./");
+        if pattern.is_empty() {
+            continue;
+        }
+        // Only walk the directory before the first wildcard
+        let literal: Vec<&str> = pattern
+            .split('/')
+            .take_while(|segment| !segment.contains(['*', '?']))
+            .collect();
+        let regex = glob_regex(pattern)?;
+        let mut matched = Vec::new();
+        collect_files(root, &root.join(literal.join("/")), &regex, &mut matched)?;
+        if matched.is_empty() {
+            return Err(CliError::InputError(format!(
+                "Release asset pattern '{pattern}' matches no file"
+            )));
+        }
+        matched.sort();
+        for path in matched {
+            if !assets.contains(&path) {
+                assets.push(path);
+            }
+        }
+    }
+    Ok(assets)
+}
+
+fn collect_files(
+    root: &Path,
+    path: &Path,
+    regex: &Regex,
+    matched: &mut Vec<PathBuf>,
+) -> Result<(), CliError> {
+    if path.is_file() {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        if regex.is_match(&relative.to_string_lossy().replace('\\', "/")) {
+            matched.push(path.to_path_buf());
+        }
+    } else if path.is_dir() && path.file_name().is_none_or(|name| name != ".git") {
+        for entry in fs::read_dir(path)? {
+            collect_files(root, &entry?.path(), regex, matched)?;
+        }
+    }
+    Ok(())
+}
+
+fn file_name(path: &Path) -> &str {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+}
+
+/// Percent-encode a URL path segment.
+fn encode_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// Host and repository path of a git remote URL (`https://host/owner/name.git`,
+/// `git@host:owner/name.git` or `ssh://git@host:22/owner/name`).
+fn parse_remote_url(url: &str) -> Option<(String, String)> {
+    let url = url.trim();
+    let (host, path) = if let Some((_, rest)) = url.split_once("://") {
+        let (authority, path) = rest.split_once('/')?;
+        let host = authority.rsplit('@').next()?.split(':').next()?;
+        (host, path)
+    } else {
+        let (authority, path) = url.split_once(':')?;
+        (authority.rsplit('@').next()?, path)
+    };
+    let path = path.trim_matches('/').trim_end_matches(".git");
+    if host.is_empty() || !path.contains('/') {
+        return None;
+    }
+    Some((host.to_string(), path.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_target_from_origin() {
+        let config = PublishConfig::default();
+        let github =
+            PublishTarget::resolve(&config, Some("git@github.com:martient/committy.git")).unwrap();
+        assert_eq!(github.provider, ForgeProvider::Github);
+        assert_eq!(github.api_url, "https://api.github.com");
+        assert_eq!(github.repository, "martient/committy");
+        assert_eq!(github.token_env, "GITHUB_TOKEN");
+
+        let ghes =
+            PublishTarget::resolve(&config, Some("https://ghe.example.com/team/app")).unwrap();
+        assert_eq!(ghes.api_url, "https://ghe.example.com/api/v3");
+
+        let gitlab = PublishTarget::resolve(
+            &config,
+            Some("ssh://git@gitlab.example.com:2222/group/sub/app.git"),
+        )
+        .unwrap();
+        assert_eq!(gitlab.provider, ForgeProvider::Gitlab);
+        assert_eq!(gitlab.api_url, "https://gitlab.example.com/api/v4");
+        assert_eq!(gitlab.repository, "group/sub/app");
+        assert_eq!(gitlab.token_env, "GITLAB_TOKEN");
+
+        let configured = PublishConfig {
+            provider: Some(ForgeProvider::Gitlab),
+            api_url: Some("http://127.0.0.1:8080/api/v4/".to_string()),
+            repository: Some("group/app".to_string()),
+            ..Default::default()
+        };
+        let local = PublishTarget::resolve(&configured, None).unwrap();
+        assert_eq!(local.api_url, "http://127.0.0.1:8080/api/v4");
+        assert!(PublishTarget::resolve(&config, None).is_err());
+    }
+
+    #[test]
+    fn test_expand_assets() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("dist/linux")).unwrap();
+        fs::write(dir.path().join("dist/app.tar.gz"), "a").unwrap();
+        fs::write(dir.path().join("dist/linux/app.tar.gz"), "b").unwrap();
+        fs::write(dir.path().join("dist/notes.txt"), "c").unwrap();
+
+        let assets = expand_assets(dir.path(), &["dist/*.tar.gz".to_string()]).unwrap();
+        assert_eq!(assets, vec![dir.path().join("dist/app.tar.gz")]);
+        let assets = expand_assets(dir.path(), &["dist/**/*.tar.gz".to_string()]).unwrap();
+        assert_eq!(assets.len(), 2);
+        assert!(expand_assets(dir.path(), &["build/*.zip".to_string()]).is_err());
+        assert_eq!(encode_segment("group/app"), "group%2Fapp");
+    }
+}
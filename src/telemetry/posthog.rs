@@ -86,6 +86,7 @@ pub async fn publish_event(
                 debug!("Event sent: {event}");
                 return Ok(());
             }
+            #[allow(clippy::collapsible_match)]
             Ok(resp) if resp.status().is_client_error() || resp.status().is_server_error() => {
                 if attempt == 3 {
                     return Err(TelemetryError::Status(resp.status()));
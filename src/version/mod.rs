@@ -18,25 +18,35 @@ impl VersionFile {
         })
     }
 
-    pub fn update_version(&self, new_version: &str) -> Result<(), CliError> {
-        if !self.path.exists() {
+    /// The file's location under `root`. Absolute paths are used as is.
+    pub fn resolve(&self, root: &Path) -> PathBuf {
+        root.join(&self.path)
+    }
+
+    pub fn update_version(&self, root: &Path, new_version: &str) -> Result<(), CliError> {
+        let path = self.resolve(root);
+        if !path.exists() {
             return Ok(()); // Skip if file doesn't exist
         }
 
-        let content = std::fs::read_to_string(&self.path).map_err(CliError::IoError)?;
+        let content = std::fs::read_to_string(&path).map_err(CliError::IoError)?;
         let version_without_v = new_version.trim_start_matches('v');
         let new_content = self
             .pattern
             .replace_all(&content, &self.format.replace("{}", version_without_v))
             .to_string();
 
-        std::fs::write(&self.path, new_content).map_err(CliError::IoError)?;
+        std::fs::write(&path, new_content).map_err(CliError::IoError)?;
 
         Ok(())
     }
 }
 
+/// Updates the version in a set of files. Relative paths are resolved against the root
+/// given to [`VersionManager::with_root`], never against the process' current directory
+/// unless created with [`VersionManager::new`].
 pub struct VersionManager {
+    root: PathBuf,
     version_files: Vec<VersionFile>,
 }
 
@@ -48,7 +58,13 @@ impl Default for VersionManager {
 
 impl VersionManager {
     pub fn new() -> Self {
+        Self::with_root("")
+    }
+
+    /// Manager for the files of the project at `root`, e.g. a repository's workdir.
+    pub fn with_root(root: impl AsRef<Path>) -> Self {
         Self {
+            root: root.as_ref().to_path_buf(),
             version_files: Vec::new(),
         }
     }
@@ -110,20 +126,27 @@ impl VersionManager {
         Ok(())
     }
 
+    /// Registered files that exist, as registered (relative to the root).
+    pub fn existing_files(&self) -> Vec<PathBuf> {
+        self.version_files
+            .iter()
+            .filter(|file| file.resolve(&self.root).exists())
+            .map(|file| file.path.clone())
+            .collect()
+    }
+
     pub fn update_all_versions(&self, new_version: &str) -> Result<Vec<PathBuf>, CliError> {
         let mut updated_files = Vec::new();
 
         for file in &self.version_files {
-            if file.path.exists() {
-                file.update_version(new_version)?;
+            let path = file.resolve(&self.root);
+            if path.exists() {
+                file.update_version(&self.root, new_version)?;
+                std::fs::File::open(&path)?; // Wait for the file to be fully written
                 updated_files.push(file.path.clone());
             }
         }
 
-        for file in &updated_files {
-            std::fs::File::open(file)?; // Wait for the file to be fully written
-        }
-
         Ok(updated_files)
     }
 }
@@ -0,0 +1,181 @@
+mod common;
+
+use committy::api::{self, CommitRequest, GroupName, GroupOptions, VersionOptions};
+use committy::error::CliError;
+use committy::git::TagGeneratorOptions;
+use committy::release::pipeline::ReleaseOptions;
+use git2::{Repository, Signature};
+use std::fs;
+use std::path::Path;
+use structopt::StructOpt;
+use tempfile::tempdir;
+
+use common::commit_file;
+
+fn setup_repo() -> (tempfile::TempDir, Repository) {
+    common::setup_test_env();
+    let dir = tempdir().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Test User").unwrap();
+    config.set_str("user.email", "test@example.com").unwrap();
+    commit_file(&repo, "README", "readme", "chore: initial commit");
+    (dir, repo)
+}
+
+fn tag_head(repo: &Repository, name: &str) {
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    repo.tag(name, head.as_object(), &signature, name, false)
+        .unwrap();
+}
+
+#[test]
+fn test_plan_commit_reports_corrections() {
+    let (dir, _repo) = setup_repo();
+    let request = CommitRequest {
+        commit_type: "Feature".into(),
+        scope: "My Scope".into(),
+        short_message: "add the api".into(),
+        breaking_change: true,
+        ..Default::default()
+    };
+
+    let plan = api::plan_commit(dir.path(), &request).unwrap();
+    assert_eq!(plan.message, "feat(My-Scope)!: add the api");
+    let fields: Vec<&str> = plan.corrections.iter().map(|c| c.field.as_str()).collect();
+    assert_eq!(fields, ["type", "scope"]);
+
+    let invalid = CommitRequest {
+        commit_type: "nonsense".into(),
+        short_message: "x".into(),
+        ..Default::default()
+    };
+    assert!(matches!(
+        api::plan_commit(dir.path(), &invalid),
+        Err(CliError::InputError(message)) if message.starts_with("Invalid commit type 'nonsense'")
+    ));
+}
+
+#[test]
+fn test_create_commit_commits_staged_changes() {
+    let (dir, repo) = setup_repo();
+    let request = CommitRequest {
+        commit_type: "fix".into(),
+        short_message: "handle empty input".into(),
+        long_message: "Details".into(),
+        ..Default::default()
+    };
+    assert!(matches!(
+        api::create_commit(dir.path(), &request),
+        Err(CliError::NoStagedChanges)
+    ));
+
+    fs::write(dir.path().join("lib.rs"), "fn main() {}").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("lib.rs")).unwrap();
+    index.write().unwrap();
+
+    let commit = api::create_commit(dir.path(), &request).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(commit.sha, head.id().to_string());
+    assert_eq!(
+        head.message().unwrap(),
+        "fix: handle empty input\n\nDetails"
+    );
+    assert!(!commit.amended);
+}
+
+#[test]
+fn test_plan_and_apply_groups() {
+    let (dir, repo) = setup_repo();
+    fs::create_dir_all(dir.path().join("docs")).unwrap();
+    fs::write(dir.path().join("docs/guide.md"), "# Guide").unwrap();
+    fs::write(dir.path().join("main.rs"), "fn main() {}").unwrap();
+
+    let options = GroupOptions {
+        include_unstaged: true,
+        ai: None,
+    };
+    let plan = api::plan_groups(dir.path(), &options).unwrap();
+    let groups: Vec<(GroupName, &str)> = plan
+        .groups
+        .iter()
+        .map(|g| (g.name, g.suggested_message.as_str()))
+        .collect();
+    assert_eq!(
+        groups,
+        [
+            (GroupName::Docs, "docs: update docs"),
+            (GroupName::Code, "chore: update code")
+        ]
+    );
+    assert!(plan.errors.is_empty());
+
+    let applied = api::apply_groups(dir.path(), &options, true, false).unwrap();
+    assert!(applied.commits.iter().all(|c| c.ok));
+    assert_eq!(applied.pushed, None);
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(
+        applied.commits[1].sha.as_deref(),
+        Some(head.id().to_string().as_str())
+    );
+    assert_eq!(head.message().unwrap(), "chore: update code");
+}
+
+#[test]
+fn test_lint_range_and_next_version() {
+    let (dir, repo) = setup_repo();
+    tag_head(&repo, "v1.0.0");
+    commit_file(&repo, "a.txt", "a", "feat: add a feature");
+    commit_file(&repo, "b.txt", "b", "not conventional");
+
+    let report = api::lint_range(dir.path(), None, "HEAD").unwrap();
+    assert_eq!(report.count, 1);
+    assert_eq!(report.issues[0].message, "not conventional");
+    let report = api::lint_range(dir.path(), Some("v1.0.0"), "HEAD~1").unwrap();
+    assert_eq!(report.count, 0);
+
+    let options = VersionOptions {
+        source: dir.path().to_path_buf(),
+        ..Default::default()
+    };
+    let plan = api::compute_next_version(&options).unwrap().unwrap();
+    assert_eq!(plan.previous_tag, "v1.0.0");
+    assert_eq!(plan.new_tag, "v1.1.0");
+    assert!(repo.revparse_single("v1.1.0").is_err());
+    let options = VersionOptions {
+        bump: Some("patch".into()),
+        ..options
+    };
+    let plan = api::compute_next_version(&options).unwrap().unwrap();
+    assert_eq!(plan.new_tag, "v1.0.1");
+    let options = VersionOptions {
+        bump: Some("huge".into()),
+        ..options
+    };
+    assert!(matches!(
+        api::compute_next_version(&options),
+        Err(CliError::InputError(_))
+    ));
+
+    // A dry-run release plans every step without changing the repository
+    let options = TagGeneratorOptions::from_iter_safe([
+        "committy",
+        "--no-fetch",
+        "--dry-run",
+        "--source",
+        dir.path().to_str().unwrap(),
+    ])
+    .unwrap();
+    let release = ReleaseOptions {
+        lint: false,
+        bump_files: false,
+        changelog: None,
+        resume: false,
+    };
+    let report = api::create_release(&options, release).unwrap().unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.new_tag, "v1.1.0");
+    assert!(repo.revparse_single("v1.1.0").is_err());
+}
@@ -1,6 +1,13 @@
+use git2::{Repository, Signature};
 use once_cell::sync::Lazy;
 use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::process::Command;
 use std::sync::Once;
+use std::thread::JoinHandle;
 use tempfile::TempDir;
 
 #[allow(dead_code)]
@@ -25,3 +32,114 @@ pub fn setup_test_env() {
         env::set_var("HOME", TEST_HOME.path());
     });
 }
+
+/// Run git in `dir` and return its standard output.
+#[allow(dead_code)]
+pub fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("Failed to run git");
+    assert!(output.status.success(), "git {args:?} failed");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// A repository on `main` with a user and an empty `chore: init` commit.
+#[allow(dead_code)]
+pub fn setup_repo() -> TempDir {
+    setup_test_env();
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-b", "main"]);
+    git(dir.path(), &["config", "user.name", "Test User"]);
+    git(dir.path(), &["config", "user.email", "test@example.com"]);
+    git(
+        dir.path(),
+        &["commit", "--allow-empty", "-m", "chore: init"],
+    );
+    dir
+}
+
+/// Write `content` to `path` and commit it with `message` on top of HEAD.
+#[allow(dead_code)]
+pub fn commit_file(repo: &Repository, path: &str, content: &str, message: &str) {
+    let workdir = repo.workdir().unwrap().to_path_buf();
+    fs::write(workdir.join(path), content).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new(path)).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let parents = match repo.head() {
+        Ok(head) => vec![head.peel_to_commit().unwrap()],
+        Err(_) => Vec::new(),
+    };
+    let parents: Vec<&git2::Commit> = parents.iter().collect();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )
+    .unwrap();
+}
+
+/// A request received by [`stand_in_server`].
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct Recorded {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+/// Serve canned `(status, body)` responses in order, one request each, and return the
+/// requests received. The responses are built from the stand-in's base URL.
+#[allow(dead_code)]
+pub fn stand_in_server(
+    responses: impl FnOnce(&str) -> Vec<(u16, String)>,
+) -> (String, JoinHandle<Vec<Recorded>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let responses = responses(&url);
+    let handle = std::thread::spawn(move || {
+        let mut recorded = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut request_body = vec![0; length];
+            reader.read_exact(&mut request_body).unwrap();
+            let mut parts = request_line.split_whitespace();
+            recorded.push(Recorded {
+                method: parts.next().unwrap().to_string(),
+                path: parts.next().unwrap().to_string(),
+                body: String::from_utf8_lossy(&request_body).into_owned(),
+            });
+            write!(
+                stream,
+                "HTTP/1.1 {status} Stand-in\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        }
+        recorded
+    });
+    (url, handle)
+}
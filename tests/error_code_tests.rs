@@ -0,0 +1,105 @@
+mod common;
+
+use assert_cmd::Command;
+use common::{git, setup_repo};
+use predicates::prelude::*;
+use serde_json::Value;
+use std::path::Path;
+
+fn committy(dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir)
+        .env("RUST_LOG", "off")
+        .arg("--non-interactive");
+    cmd
+}
+
+fn json_error(stdout: &[u8]) -> Value {
+    let v: Value = serde_json::from_slice(stdout).unwrap();
+    assert_eq!(v["ok"], false);
+    assert_eq!(v["data"], Value::Null);
+    v["errors"][0].clone()
+}
+
+#[test]
+fn test_no_staged_changes_has_its_own_exit_code_and_json_error() {
+    let dir = setup_repo();
+    let assert = committy(dir.path())
+        .args([
+            "commit",
+            "--type",
+            "feat",
+            "--message",
+            "add x",
+            "--output",
+            "json",
+        ])
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains("No staged changes found"));
+
+    let error = json_error(&assert.get_output().stdout);
+    assert_eq!(error["code"], "no_staged_changes");
+    assert_eq!(error["message"], "No staged changes found");
+    assert_eq!(error["hint"], "For help, run 'committy --help'");
+}
+
+#[test]
+fn test_existing_tag_has_its_own_exit_code_and_json_error() {
+    let dir = setup_repo();
+    git(dir.path(), &["tag", "v1.0.0"]);
+    let assert = committy(dir.path())
+        .args([
+            "tag",
+            "--name",
+            "v1.0.0",
+            "--not-publish",
+            "--output",
+            "json",
+        ])
+        .assert()
+        .code(10);
+
+    let v: Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(v["command"], "tag");
+    let error = json_error(&assert.get_output().stdout);
+    assert_eq!(error["code"], "tag_exists");
+    assert!(error["hint"]
+        .as_str()
+        .unwrap()
+        .contains("--on-tag-collision skip"));
+}
+
+#[test]
+fn test_invalid_input_exits_with_2() {
+    let dir = setup_repo();
+    std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+    git(dir.path(), &["add", "a.txt"]);
+    committy(dir.path())
+        .args(["commit", "--message", "add a"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "--type and --message are required",
+        ));
+}
+
+#[test]
+fn test_lint_issues_are_structured_in_the_lint_report() {
+    let dir = setup_repo();
+    git(
+        dir.path(),
+        &["commit", "--allow-empty", "-m", "not conventional"],
+    );
+    let assert = committy(dir.path())
+        .args(["lint", "--output", "json"])
+        .assert()
+        .code(3);
+
+    // The report is printed once, with the lint error next to its payload
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert_eq!(stdout.lines().count(), 1);
+    let v: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(v["data"]["count"], 1);
+    assert_eq!(v["errors"][0]["code"], "lint_issues");
+}
@@ -78,7 +78,7 @@ fn test_group_commit_apply_without_auto_stage_only_staged_committed() {
     let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
     let v: Value = serde_json::from_str(output.trim()).unwrap();
     assert_eq!(v["ok"], Value::Bool(true));
-    let commits = v["commits"].as_array().expect("commits array");
+    let commits = v["data"]["commits"].as_array().expect("commits array");
     // Only docs group should be committed
     assert_eq!(commits.len(), 1);
     assert_eq!(commits[0]["group"], Value::String("docs".into()));
@@ -123,9 +123,93 @@ fn test_group_commit_apply_with_push_sets_pushed_false_without_remote() {
 
     let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
     let v: Value = serde_json::from_str(output.trim()).unwrap();
-    assert_eq!(v["mode"], Value::String("apply".into()));
+    assert_eq!(v["data"]["mode"], Value::String("apply".into()));
     // No remote -> push should fail and be false
-    assert_eq!(v["pushed"], Value::Bool(false));
+    assert_eq!(v["data"]["pushed"], Value::Bool(false));
+}
+
+#[test]
+fn test_group_commit_apply_fails_when_a_group_commit_fails() {
+    let temp_dir = setup_repo();
+
+    let docs_file = temp_dir.path().join("docs/LOCKED.md");
+    std::fs::create_dir_all(docs_file.parent().unwrap()).unwrap();
+    std::fs::write(&docs_file, "Locked\n").unwrap();
+    let _ = StdCommand::new("git")
+        .args(["add", "docs/LOCKED.md"])
+        .current_dir(&temp_dir)
+        .output()
+        .expect("Failed to stage LOCKED.md");
+    // A stale lock keeps the branch from moving, so the commit fails
+    let branch = StdCommand::new("git")
+        .args(["symbolic-ref", "HEAD"])
+        .current_dir(&temp_dir)
+        .output()
+        .expect("Failed to read HEAD");
+    let branch = String::from_utf8(branch.stdout).unwrap();
+    std::fs::write(
+        temp_dir.path().join(format!(".git/{}.lock", branch.trim())),
+        "",
+    )
+    .unwrap();
+
+    let assert = Command::cargo_bin("committy")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .env("RUST_LOG", "off")
+        .arg("--non-interactive")
+        .arg("group-commit")
+        .arg("--mode")
+        .arg("apply")
+        .arg("--output")
+        .arg("json")
+        .assert()
+        .code(12);
+
+    let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    let v: Value = serde_json::from_str(output.trim()).unwrap();
+    assert_eq!(v["ok"], Value::Bool(false));
+    assert_eq!(v["errors"][0]["code"], Value::String("git".into()));
+    assert_eq!(v["data"]["commits"][0]["ok"], Value::Bool(false));
+}
+
+#[test]
+fn test_group_commit_rejects_unreadable_ai_system_prompt_file() {
+    let temp_dir = setup_repo();
+
+    let docs_file = temp_dir.path().join("docs/PROMPT.md");
+    std::fs::create_dir_all(docs_file.parent().unwrap()).unwrap();
+    std::fs::write(&docs_file, "Prompt\n").unwrap();
+    let _ = StdCommand::new("git")
+        .args(["add", "docs/PROMPT.md"])
+        .current_dir(&temp_dir)
+        .output()
+        .expect("Failed to stage PROMPT.md");
+
+    let assert = Command::cargo_bin("committy")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .env("RUST_LOG", "off")
+        .env("OPENROUTER_API_KEY", "test-key")
+        .arg("--non-interactive")
+        .arg("group-commit")
+        .arg("--mode")
+        .arg("plan")
+        .arg("--ai")
+        .arg("--ai-system-prompt-file")
+        .arg(temp_dir.path().join("missing.txt"))
+        .arg("--output")
+        .arg("json")
+        .assert()
+        .code(7);
+
+    let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    let v: Value = serde_json::from_str(output.trim()).unwrap();
+    assert_eq!(v["ok"], Value::Bool(false));
+    assert_eq!(
+        v["errors"][0]["code"],
+        Value::String("invalid_config".into())
+    );
 }
 
 #[test]
@@ -165,10 +249,10 @@ fn test_group_commit_plan_json_offline() {
     let v: Value = serde_json::from_str(output.trim()).unwrap();
 
     assert_eq!(v["command"], Value::String("group-commit".into()));
-    assert_eq!(v["mode"], Value::String("plan".into()));
+    assert_eq!(v["data"]["mode"], Value::String("plan".into()));
     assert_eq!(v["ok"], Value::Bool(true));
 
-    let groups = v["groups"].as_array().expect("groups array");
+    let groups = v["data"]["groups"].as_array().expect("groups array");
     assert!(
         groups.len() >= 2,
         "expected at least 2 groups, got {}",
@@ -235,10 +319,10 @@ fn test_group_commit_apply_auto_stage_creates_commits() {
     let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
     let v: Value = serde_json::from_str(output.trim()).unwrap();
 
-    assert_eq!(v["mode"], Value::String("apply".into()));
+    assert_eq!(v["data"]["mode"], Value::String("apply".into()));
     assert_eq!(v["ok"], Value::Bool(true));
 
-    let commits = v["commits"].as_array().expect("commits array");
+    let commits = v["data"]["commits"].as_array().expect("commits array");
     assert!(
         commits.len() >= 2,
         "expected at least 2 commits, got {}",
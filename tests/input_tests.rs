@@ -38,29 +38,29 @@ fn test_scope_validation_edge_cases() {
 #[test]
 fn test_commit_type_suggestions() {
     // Exact matches
-    assert_eq!(suggest_commit_type("feat"), Some("feat"));
-    assert_eq!(suggest_commit_type("fix"), Some("fix"));
+    assert_eq!(suggest_commit_type("feat").as_deref(), Some("feat"));
+    assert_eq!(suggest_commit_type("fix").as_deref(), Some("fix"));
 
     // Case variations
-    assert_eq!(suggest_commit_type("FEAT"), Some("feat"));
-    assert_eq!(suggest_commit_type("Fix"), Some("fix"));
-    assert_eq!(suggest_commit_type("DOCS"), Some("docs"));
+    assert_eq!(suggest_commit_type("FEAT").as_deref(), Some("feat"));
+    assert_eq!(suggest_commit_type("Fix").as_deref(), Some("fix"));
+    assert_eq!(suggest_commit_type("DOCS").as_deref(), Some("docs"));
 
     // Common typos
-    assert_eq!(suggest_commit_type("feature"), Some("feat"));
-    assert_eq!(suggest_commit_type("fixx"), Some("fix"));
-    assert_eq!(suggest_commit_type("docs!"), Some("docs"));
-    assert_eq!(suggest_commit_type("feet"), Some("feat"));
-    assert_eq!(suggest_commit_type("ffix"), Some("fix"));
+    assert_eq!(suggest_commit_type("feature").as_deref(), Some("feat"));
+    assert_eq!(suggest_commit_type("fixx").as_deref(), Some("fix"));
+    assert_eq!(suggest_commit_type("docs!").as_deref(), Some("docs"));
+    assert_eq!(suggest_commit_type("feet").as_deref(), Some("feat"));
+    assert_eq!(suggest_commit_type("ffix").as_deref(), Some("fix"));
 
     // With whitespace
-    assert_eq!(suggest_commit_type("  feat  "), Some("feat"));
-    assert_eq!(suggest_commit_type(" fix "), Some("fix"));
+    assert_eq!(suggest_commit_type("  feat  ").as_deref(), Some("feat"));
+    assert_eq!(suggest_commit_type(" fix ").as_deref(), Some("fix"));
 
     // No close matches
-    assert_eq!(suggest_commit_type("completely-wrong"), None);
-    assert_eq!(suggest_commit_type("12345"), None);
-    assert_eq!(suggest_commit_type(""), None);
+    assert_eq!(suggest_commit_type("completely-wrong").as_deref(), None);
+    assert_eq!(suggest_commit_type("12345").as_deref(), None);
+    assert_eq!(suggest_commit_type("").as_deref(), None);
 }
 
 #[test]
@@ -234,7 +234,7 @@ fn test_lint_json_exit_code_and_payload() {
     let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
     let v: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
     assert_eq!(v["ok"], serde_json::json!(false));
-    assert_eq!(v["count"], serde_json::json!(1));
+    assert_eq!(v["data"]["count"], serde_json::json!(1));
 
     cleanup(temp_dir);
 }
@@ -267,7 +267,7 @@ fn test_tag_json_dry_run_output_non_interactive() {
     let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
     let v: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
     assert_eq!(v["ok"], serde_json::json!(true));
-    assert_eq!(v["new_tag"], serde_json::json!("v0.1.0"));
+    assert_eq!(v["data"]["new_tag"], serde_json::json!("v0.1.0"));
 
     cleanup(temp_dir);
 }
@@ -312,7 +312,7 @@ major_regex = '(?im)^(breaking change:|feat(?:\s*\([^)]*\))?!:)'
     let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
     let v: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
     // With override, fix should result in minor bump from 0.0.0 -> 0.1.0
-    assert_eq!(v["new_tag"], serde_json::json!("v0.1.0"));
+    assert_eq!(v["data"]["new_tag"], serde_json::json!("v0.1.0"));
 
     cleanup(temp_dir);
 }
@@ -345,7 +345,7 @@ fn test_tag_fix_default_is_patch() {
 
     let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
     let v: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
-    assert_eq!(v["new_tag"], serde_json::json!("v0.0.1"));
+    assert_eq!(v["data"]["new_tag"], serde_json::json!("v0.0.1"));
 
     cleanup(temp_dir);
 }
@@ -48,7 +48,7 @@ fn lint_message_valid_json() {
     let s = String::from_utf8(output).unwrap();
     let v: serde_json::Value = serde_json::from_str(&s).unwrap();
     assert_eq!(v["ok"], true);
-    assert_eq!(v["count"], 0);
+    assert_eq!(v["data"]["count"], 0);
 }
 
 #[test]
@@ -71,5 +71,5 @@ fn lint_message_invalid_json() {
     let s = String::from_utf8(output).unwrap();
     let v: serde_json::Value = serde_json::from_str(&s).unwrap();
     assert_eq!(v["ok"], false);
-    assert!(v["count"].as_u64().unwrap() >= 1);
+    assert!(v["data"]["count"].as_u64().unwrap() >= 1);
 }
@@ -0,0 +1,88 @@
+mod common;
+
+use assert_cmd::Command;
+use common::git;
+use serde_json::{json, Value};
+
+fn frame(message: Value) -> String {
+    let body = message.to_string();
+    format!("Content-Length: {}\r\n\r\n{body}", body.len())
+}
+
+/// Split the output of the server into its messages.
+fn messages(output: &str) -> Vec<Value> {
+    output
+        .split("Content-Length: ")
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (_, body) = part.split_once("\r\n\r\n").unwrap();
+            serde_json::from_str(body).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_lsp_lints_and_completes_commit_messages_of_a_repository() {
+    let dir = common::setup_repo();
+    for message in ["feat(api): add x", "fix(cli): fix y", "fix(api): fix z"] {
+        git(dir.path(), &["commit", "--allow-empty", "-m", message]);
+    }
+
+    let editmsg = dir.path().join(".git").join("COMMIT_EDITMSG");
+    let uri = format!("file://{}", editmsg.display());
+    let text = "fix(): add\n# Please enter the commit message for your changes.\n";
+    let input = [
+        frame(json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} })),
+        frame(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": {
+                "uri": uri, "languageId": "git-commit", "version": 1, "text": text,
+            } },
+        })),
+        frame(json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "textDocument/completion",
+            "params": {
+                "textDocument": { "uri": uri },
+                "position": { "line": 0, "character": 4 },
+            },
+        })),
+        frame(json!({ "jsonrpc": "2.0", "id": 3, "method": "shutdown" })),
+        frame(json!({ "jsonrpc": "2.0", "method": "exit" })),
+    ]
+    .concat();
+
+    let assert = Command::cargo_bin("committy")
+        .unwrap()
+        .env("RUST_LOG", "off")
+        .args(["lsp", "--stdio"])
+        .write_stdin(input)
+        .assert()
+        .success();
+    let output = messages(&String::from_utf8_lossy(&assert.get_output().stdout));
+    assert_eq!(output.len(), 4);
+
+    assert_eq!(output[0]["result"]["serverInfo"]["name"], "committy");
+
+    // The comment line is not part of the message
+    let diagnostics = &output[1]["params"]["diagnostics"];
+    assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+    assert_eq!(diagnostics[0]["message"], "Empty scope parenthesis");
+    assert_eq!(
+        diagnostics[0]["range"]["start"],
+        json!({ "line": 0, "character": 3 })
+    );
+
+    // Scopes come from the history, the most used first
+    let labels: Vec<&str> = output[2]["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap())
+        .collect();
+    assert_eq!(labels, ["api", "cli"]);
+
+    assert_eq!(output[3]["id"], 3);
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use assert_cmd::Command;
+use common::git;
+use serde_json::{json, Value};
+
+/// Send `messages` to `committy mcp`, one per line, and return its responses.
+fn exchange(messages: &[Value]) -> Vec<Value> {
+    let input: String = messages.iter().map(|m| format!("{m}\n")).collect();
+    let assert = Command::cargo_bin("committy")
+        .unwrap()
+        .env("RUST_LOG", "off")
+        .arg("mcp")
+        .write_stdin(input)
+        .assert()
+        .success();
+    String::from_utf8(assert.get_output().stdout.clone())
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("stdout carries only JSON-RPC messages"))
+        .collect()
+}
+
+fn call(id: u64, name: &str, arguments: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments },
+    })
+}
+
+#[test]
+fn test_mcp_server_lists_and_calls_tools_over_stdio() {
+    let dir = common::setup_repo();
+    git(dir.path(), &["tag", "v1.0.0"]);
+    git(
+        dir.path(),
+        &["commit", "--allow-empty", "-m", "feat: add x"],
+    );
+    let repo_path = dir.path().to_str().unwrap();
+
+    let responses = exchange(&[
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": { "protocolVersion": "2025-06-18", "capabilities": {} },
+        }),
+        json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+        call(3, "next_version", json!({ "repo_path": repo_path })),
+        call(4, "lint", json!({ "repo_path": repo_path })),
+        call(
+            5,
+            "commit",
+            json!({ "repo_path": repo_path, "commit_type": "feat", "short_message": "add y" }),
+        ),
+    ]);
+    assert_eq!(responses.len(), 5);
+
+    assert_eq!(responses[0]["result"]["protocolVersion"], "2025-06-18");
+
+    let names: Vec<&str> = responses[1]["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "plan_commit",
+            "commit",
+            "lint",
+            "lint_message",
+            "next_version",
+            "tag",
+            "group_commit_plan",
+            "group_commit_apply",
+        ]
+    );
+
+    let version = &responses[2]["result"];
+    assert_eq!(version["isError"], false);
+    assert_eq!(version["structuredContent"]["data"]["new_tag"], "v1.1.0");
+
+    let lint = &responses[3]["result"]["structuredContent"];
+    assert_eq!(lint["ok"], true);
+    assert_eq!(lint["data"]["count"], 0);
+
+    // Nothing is staged: a tool failure, reported in the envelope
+    let commit = &responses[4]["result"];
+    assert_eq!(commit["isError"], true);
+    assert_eq!(
+        commit["structuredContent"]["errors"][0]["code"],
+        "no_staged_changes"
+    );
+
+    // The read-only tools left no tag behind
+    assert_eq!(git(dir.path(), &["tag", "--list"]).trim(), "v1.0.0");
+}
@@ -0,0 +1,196 @@
+mod common;
+
+use assert_cmd::Command;
+use git2::{Repository, Signature};
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+use common::{commit_file, stand_in_server};
+
+/// v1.0.0, then a feature, with a `[publish]` section and a bare `origin`.
+fn setup_publish_repo(publish: &str) -> (tempfile::TempDir, tempfile::TempDir, Repository) {
+    let dir = tempdir().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Test User").unwrap();
+    config.set_str("user.email", "test@example.com").unwrap();
+    commit_file(&repo, ".gitignore", "dist/\n", "chore: initial commit");
+    commit_file(
+        &repo,
+        ".committy.toml",
+        publish,
+        "chore: configure publishing",
+    );
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    repo.tag("v1.0.0", head.as_object(), &signature, "v1.0.0", false)
+        .unwrap();
+    drop(head);
+    commit_file(&repo, "app.txt", "app", "feat(cli): publish releases");
+
+    fs::create_dir(dir.path().join("dist")).unwrap();
+    fs::write(dir.path().join("dist/app.tar.gz"), "archive").unwrap();
+    let remote_dir = tempdir().unwrap();
+    Repository::init_bare(remote_dir.path()).unwrap();
+    repo.remote("origin", remote_dir.path().to_str().unwrap())
+        .unwrap();
+    (dir, remote_dir, repo)
+}
+
+fn publish_tag(dir: &Path, extra: &[&str]) -> assert_cmd::assert::Assert {
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir)
+        .env("FORGE_TOKEN", "secret")
+        .args([
+            "--non-interactive",
+            "tag",
+            "--no-fetch",
+            "--publish-release",
+        ])
+        .args(extra);
+    cmd.assert()
+}
+
+#[test]
+fn test_publish_github_release_with_assets() {
+    let (url, server) = stand_in_server(|url| {
+        // The created release points its uploads at the stand-in too
+        let created = format!(
+            r#"{{"id":42,"html_url":"https://github.example/o/r/releases/v1.1.0","upload_url":"{url}/uploads/repos/o/r/releases/42/assets{{?name,label}}","assets":[]}}"#
+        );
+        vec![
+            (404, r#"{"message":"Not Found"}"#.to_string()),
+            (201, created),
+            (201, r#"{"id":1,"name":"app.tar.gz"}"#.to_string()),
+        ]
+    });
+    let (dir, remote_dir, _repo) = setup_publish_repo(&format!(
+        r#"
+[publish]
+provider = "github"
+api-url = "{url}"
+repository = "o/r"
+token-env = "FORGE_TOKEN"
+assets = ["dist/*.tar.gz"]
+"#
+    ));
+
+    publish_tag(dir.path(), &[]).success().stdout("v1.1.0\n");
+
+    let requests = server.join().unwrap();
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/repos/o/r/releases/tags/v1.1.0");
+    assert_eq!(requests[1].method, "POST");
+    assert_eq!(requests[1].path, "/repos/o/r/releases");
+    let body: serde_json::Value = serde_json::from_str(&requests[1].body).unwrap();
+    assert_eq!(body["tag_name"], "v1.1.0");
+    assert_eq!(body["prerelease"], false);
+    assert!(body["body"]
+        .as_str()
+        .unwrap()
+        .contains("### Features\n\n- **cli:** publish releases"));
+    assert_eq!(
+        requests[2].path,
+        "/uploads/repos/o/r/releases/42/assets?name=app.tar.gz"
+    );
+    assert_eq!(requests[2].body, "archive");
+    let remote = Repository::open_bare(remote_dir.path()).unwrap();
+    assert!(remote.revparse_single("v1.1.0").is_ok());
+}
+
+#[test]
+fn test_publish_updates_existing_gitlab_release() {
+    let existing = r#"{"name":"v1.1.0","assets":{"links":[{"id":7,"name":"app.tar.gz"}]},"_links":{"self":"https://gitlab.example/g/app/-/releases/v1.1.0"}}"#;
+    let (url, server) = stand_in_server(|_| {
+        vec![
+            (200, existing.to_string()),
+            (200, existing.to_string()),
+            (204, String::new()),
+            (201, r#"{"message":"201 Created"}"#.to_string()),
+            (201, r#"{"id":8,"name":"app.tar.gz"}"#.to_string()),
+        ]
+    });
+    let (dir, _remote_dir, _repo) = setup_publish_repo(&format!(
+        r#"
+[publish]
+provider = "gitlab"
+api-url = "{url}/api/v4"
+repository = "g/app"
+token-env = "FORGE_TOKEN"
+assets = ["dist/*.tar.gz"]
+"#
+    ));
+
+    publish_tag(dir.path(), &["--prerelease"])
+        .success()
+        .stdout("v1.1.0-beta.0\n");
+
+    let requests = server.join().unwrap();
+    let calls: Vec<(&str, &str)> = requests
+        .iter()
+        .map(|r| (r.method.as_str(), r.path.as_str()))
+        .collect();
+    assert_eq!(
+        calls,
+        [
+            ("GET", "/api/v4/projects/g%2Fapp/releases/v1.1.0-beta.0"),
+            ("PUT", "/api/v4/projects/g%2Fapp/releases/v1.1.0-beta.0"),
+            (
+                "DELETE",
+                "/api/v4/projects/g%2Fapp/releases/v1.1.0-beta.0/assets/links/7"
+            ),
+            (
+                "PUT",
+                "/api/v4/projects/g%2Fapp/packages/generic/app/1.1.0-beta.0/app.tar.gz"
+            ),
+            (
+                "POST",
+                "/api/v4/projects/g%2Fapp/releases/v1.1.0-beta.0/assets/links"
+            ),
+        ]
+    );
+    let link: serde_json::Value = serde_json::from_str(&requests[4].body).unwrap();
+    assert_eq!(
+        link["url"],
+        format!("{url}/api/v4/projects/g%2Fapp/packages/generic/app/1.1.0-beta.0/app.tar.gz")
+    );
+}
+
+#[test]
+fn test_publish_requires_token_before_tagging() {
+    let (dir, _remote_dir, repo) = setup_publish_repo(
+        r#"
+[publish]
+provider = "github"
+api-url = "http://127.0.0.1:9"
+repository = "o/r"
+token-env = "COMMITTY_TEST_MISSING_TOKEN"
+"#,
+    );
+
+    publish_tag(dir.path(), &[])
+        .failure()
+        .stderr(predicate::str::contains(
+            "Set COMMITTY_TEST_MISSING_TOKEN to publish the GitHub release",
+        ));
+    assert!(repo.revparse_single("v1.1.0").is_err());
+
+    // A failing API call is reported with its status and message
+    let (url, server) =
+        stand_in_server(|_| vec![(401, r#"{"message":"Bad credentials"}"#.to_string())]);
+    fs::write(
+        dir.path().join(".committy.toml"),
+        format!(
+            "[publish]\napi-url = \"{url}\"\nrepository = \"o/r\"\ntoken-env = \"FORGE_TOKEN\"\n"
+        ),
+    )
+    .unwrap();
+    publish_tag(dir.path(), &[])
+        .failure()
+        .stderr(predicate::str::contains(
+            "GitHub API request GET /repos/o/r/releases/tags/v1.1.0 failed with 401 Unauthorized: Bad credentials",
+        ));
+    server.join().unwrap();
+}
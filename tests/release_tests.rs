@@ -0,0 +1,228 @@
+mod common;
+
+use assert_cmd::Command;
+use git2::{Repository, Signature};
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+use common::commit_file;
+
+/// v1.0.0 with a Cargo.toml, followed by a feature commit.
+fn setup_release_repo() -> (tempfile::TempDir, Repository) {
+    let dir = tempdir().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Test User").unwrap();
+    config.set_str("user.email", "test@example.com").unwrap();
+
+    commit_file(
+        &repo,
+        "Cargo.toml",
+        "[package]\nname = \"demo\"\nversion = \"1.0.0\"\n",
+        "chore: initial commit",
+    );
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    repo.tag("v1.0.0", head.as_object(), &signature, "v1.0.0", false)
+        .unwrap();
+    drop(head);
+    commit_file(
+        &repo,
+        "src.txt",
+        "feature",
+        "feat(cli): add release command",
+    );
+    (dir, repo)
+}
+
+fn release(dir: &Path, extra: &[&str]) -> assert_cmd::assert::Assert {
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir)
+        .args(["--non-interactive", "release", "--no-fetch"])
+        .args(extra);
+    cmd.assert()
+}
+
+#[test]
+fn test_release_dry_run_plans_every_step() {
+    let (dir, repo) = setup_release_repo();
+
+    let output = release(dir.path(), &["--dry-run", "--output", "json"])
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let release = &json["data"]["release"];
+    assert_eq!(release["new_tag"], "v1.1.0");
+    assert_eq!(release["dry_run"], true);
+    let steps: Vec<(&str, &str)> = release["steps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| (s["name"].as_str().unwrap(), s["status"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            ("lint", "done"),
+            ("version", "done"),
+            ("bump", "planned"),
+            ("changelog", "planned"),
+            ("commit", "planned"),
+            ("tag", "planned"),
+            ("push", "planned"),
+            ("publish", "skipped"),
+        ]
+    );
+    assert!(release["steps"][2]["detail"]
+        .as_str()
+        .unwrap()
+        .contains("Cargo.toml"));
+    assert!(release["steps"][3]["detail"]
+        .as_str()
+        .unwrap()
+        .contains("- **cli:** add release command"));
+
+    // Nothing was changed
+    assert!(repo.revparse_single("v1.1.0").is_err());
+    assert!(!dir.path().join("CHANGELOG.md").exists());
+    assert!(fs::read_to_string(dir.path().join("Cargo.toml"))
+        .unwrap()
+        .contains("version = \"1.0.0\""));
+    assert!(!repo.path().join("committy-release.json").exists());
+}
+
+#[test]
+fn test_release_runs_all_steps() {
+    let (dir, repo) = setup_release_repo();
+    let remote_dir = tempdir().unwrap();
+    let remote = Repository::init_bare(remote_dir.path()).unwrap();
+    repo.remote("origin", remote_dir.path().to_str().unwrap())
+        .unwrap();
+
+    release(dir.path(), &[])
+        .success()
+        .stdout(predicate::str::contains("✓ changelog"))
+        .stdout(predicate::str::contains("Released v1.1.0!"));
+
+    assert!(fs::read_to_string(dir.path().join("Cargo.toml"))
+        .unwrap()
+        .contains("version = \"1.1.0\""));
+    let changelog = fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap();
+    assert!(changelog.starts_with("# Changelog\n\n## v1.1.0 ("));
+    assert!(changelog.contains("### Features\n\n- **cli:** add release command"));
+
+    let tagged = repo
+        .revparse_single("v1.1.0^{commit}")
+        .unwrap()
+        .peel_to_commit()
+        .unwrap();
+    assert_eq!(tagged.message().unwrap(), "chore: bump version to 1.1.0");
+    assert!(tagged
+        .tree()
+        .unwrap()
+        .get_path(Path::new("CHANGELOG.md"))
+        .is_ok());
+    assert!(remote.revparse_single("v1.1.0").is_ok());
+    assert_eq!(remote.revparse_single("master").unwrap().id(), tagged.id());
+    assert!(!repo.path().join("committy-release.json").exists());
+}
+
+#[test]
+fn test_release_resumes_after_failed_push() {
+    let (dir, repo) = setup_release_repo();
+    // Tags are listed from the fetch URL, pushes go to a push URL that does not exist yet
+    let remote_dir = tempdir().unwrap();
+    let remote = Repository::init_bare(remote_dir.path()).unwrap();
+    repo.remote("origin", remote_dir.path().to_str().unwrap())
+        .unwrap();
+    let missing = remote_dir.path().join("missing.git");
+    repo.remote_set_pushurl("origin", missing.to_str()).unwrap();
+
+    release(dir.path(), &[])
+        .failure()
+        .stderr(predicate::str::contains("Release step 'push' failed"))
+        .stderr(predicate::str::contains("committy release --resume"));
+    assert!(repo.revparse_single("v1.1.0").is_ok());
+    assert!(repo.path().join("committy-release.json").exists());
+
+    // A new run does not start over while a release is in progress
+    release(dir.path(), &[])
+        .failure()
+        .stderr(predicate::str::contains(
+            "A release of v1.1.0 is in progress",
+        ));
+
+    // Once the push URL is fixed, the release picks up at the push step
+    repo.remote_set_pushurl("origin", None).unwrap();
+    release(dir.path(), &["--resume", "--output", "json"])
+        .success()
+        .stdout(predicate::str::contains(r#""resumed":true"#))
+        .stdout(predicate::str::contains(
+            r#"{"name":"tag","status":"resumed","detail":"completed in a previous run"}"#,
+        ))
+        .stdout(predicate::str::contains(r#""name":"push","status":"done""#));
+    assert!(remote.revparse_single("v1.1.0").is_ok());
+    assert!(!repo.path().join("committy-release.json").exists());
+    // The version was not bumped twice
+    assert!(repo.revparse_single("v1.2.0").is_err());
+
+    release(dir.path(), &["--abort"])
+        .success()
+        .stdout("No release in progress.\n");
+}
+
+#[test]
+fn test_release_resumes_after_failed_commit() {
+    let (dir, repo) = setup_release_repo();
+    // A stale lock on the branch makes the bump commit fail once the files are staged
+    let branch = repo.head().unwrap().name().unwrap().to_string();
+    let lock = repo.path().join(format!("{branch}.lock"));
+    std::fs::write(&lock, "").unwrap();
+
+    release(dir.path(), &["--not-publish"])
+        .failure()
+        .stderr(predicate::str::contains("Release step 'commit' failed"));
+    assert!(repo.path().join("committy-release.json").exists());
+    assert!(repo.revparse_single("v1.1.0").is_err());
+
+    std::fs::remove_file(&lock).unwrap();
+    release(dir.path(), &["--not-publish", "--resume"]).success();
+    assert!(repo.revparse_single("v1.1.0").is_ok());
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.summary(), Some("chore: bump version to 1.1.0"));
+    assert!(!repo.path().join("committy-release.json").exists());
+}
+
+#[test]
+fn test_release_stops_on_lint_issues() {
+    let (dir, repo) = setup_release_repo();
+    commit_file(&repo, "other.txt", "x", "not a conventional commit");
+
+    release(dir.path(), &["--not-publish"])
+        .code(3)
+        .stderr(predicate::str::contains(
+            "have lint issues, nothing was released",
+        ))
+        .stderr(predicate::str::contains(
+            "Found 1 commit(s) with lint issues",
+        ));
+    assert!(repo.revparse_single("v1.1.0").is_err());
+    assert!(!repo.path().join("committy-release.json").exists());
+
+    release(
+        dir.path(),
+        &["--not-publish", "--no-lint", "--no-changelog"],
+    )
+    .success()
+    .stdout(predicate::str::contains(
+        "- changelog disabled with --no-changelog",
+    ))
+    .stdout(predicate::str::contains(
+        "- push      disabled with --not-publish",
+    ));
+    assert!(repo.revparse_single("v1.1.0").is_ok());
+}
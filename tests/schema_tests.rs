@@ -0,0 +1,166 @@
+mod common;
+
+use assert_cmd::Command;
+use committy::cli::commands::schema::{output_schema, SCHEMA_COMMANDS};
+use common::git;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn schema_path(command: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("schemas")
+        .join(format!("{command}.schema.json"))
+}
+
+fn setup_repo() -> tempfile::TempDir {
+    let dir = common::setup_repo();
+    git(dir.path(), &["tag", "-a", "v1.0.0", "-m", "v1.0.0"]);
+    git(
+        dir.path(),
+        &["commit", "--allow-empty", "-m", "feat: add feature"],
+    );
+    dir
+}
+
+/// Run `committy --non-interactive <args>` in `dir` and parse its JSON output.
+fn run_json(dir: &Path, args: &[&str]) -> Value {
+    let output = Command::cargo_bin("committy")
+        .unwrap()
+        .current_dir(dir)
+        .arg("--non-interactive")
+        .args(args)
+        .output()
+        .unwrap();
+    serde_json::from_slice(&output.stdout).unwrap_or_else(|e| {
+        panic!(
+            "{args:?} printed invalid JSON ({e}): {}",
+            String::from_utf8_lossy(&output.stdout)
+        )
+    })
+}
+
+fn assert_valid(command: &str, instance: &Value) {
+    let schema: Value =
+        serde_json::from_str(&fs::read_to_string(schema_path(command)).unwrap()).unwrap();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    let errors: Vec<String> = validator
+        .iter_errors(instance)
+        .map(|e| e.to_string())
+        .collect();
+    assert!(
+        errors.is_empty(),
+        "{command} output does not match its schema: {errors:?}\n{instance}"
+    );
+    assert_eq!(instance["command"], command);
+}
+
+#[test]
+fn test_published_schemas_are_up_to_date() {
+    // COMMITTY_UPDATE_SCHEMAS=1 cargo test --test schema_tests rewrites them
+    let update = std::env::var_os("COMMITTY_UPDATE_SCHEMAS").is_some();
+    for command in SCHEMA_COMMANDS {
+        let schema = output_schema(command).unwrap();
+        let generated = format!("{}\n", serde_json::to_string_pretty(&schema).unwrap());
+        let path = schema_path(command);
+        if update {
+            fs::write(&path, &generated).unwrap();
+            continue;
+        }
+        let published = fs::read_to_string(&path).unwrap_or_default();
+        assert_eq!(
+            published,
+            generated,
+            "{} is out of date, run COMMITTY_UPDATE_SCHEMAS=1 cargo test --test schema_tests",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn test_command_outputs_match_their_schemas() {
+    let dir = setup_repo();
+    let dir = dir.path();
+
+    let tag = run_json(dir, &["tag", "--no-fetch", "--dry-run", "--output", "json"]);
+    assert_valid("tag", &tag);
+    assert_eq!(tag["data"]["previous_tag"], "v1.0.0");
+    assert_eq!(tag["data"]["new_tag"], "v1.1.0");
+
+    let lint = run_json(dir, &["lint", "--output", "json"]);
+    assert_valid("lint", &lint);
+    assert_eq!(lint["ok"], true);
+
+    let lint_message = run_json(
+        dir,
+        &[
+            "lint-message",
+            "--message",
+            "bad message",
+            "--output",
+            "json",
+        ],
+    );
+    assert_valid("lint-message", &lint_message);
+    assert_eq!(lint_message["ok"], false);
+    assert_eq!(lint_message["errors"].as_array().unwrap().len(), 1);
+
+    let version = run_json(dir, &["version", "--no-fetch", "--output", "json"]);
+    assert_valid("version", &version);
+
+    let release = run_json(
+        dir,
+        &["release", "--no-fetch", "--dry-run", "--output", "json"],
+    );
+    assert_valid("release", &release);
+    assert_eq!(release["data"]["released"], true);
+
+    let branch = run_json(dir, &["branch", "--name", "feat-x", "--output", "json"]);
+    assert_valid("branch", &branch);
+    assert_eq!(branch["data"]["name"], "feat-x");
+    assert_eq!(branch["data"]["base"], "main");
+
+    fs::write(dir.join("notes.md"), "# Notes").unwrap();
+    let plan = run_json(dir, &["group-commit", "--include-unstaged"]);
+    assert_valid("group-commit", &plan);
+    assert_eq!(plan["data"]["mode"], "plan");
+
+    git(dir, &["add", "notes.md"]);
+    let commit = run_json(
+        dir,
+        &[
+            "commit",
+            "--type",
+            "docs",
+            "--scope",
+            "Read Me",
+            "--message",
+            "add notes",
+            "--output",
+            "json",
+        ],
+    );
+    assert_valid("commit", &commit);
+    assert_eq!(commit["data"]["message"], "docs(Read-Me): add notes");
+    assert_eq!(commit["data"]["sha"].as_str().unwrap().len(), 40);
+    assert_eq!(
+        commit["warnings"][0],
+        "Auto-corrected scope from 'Read Me' to 'Read-Me'"
+    );
+
+    // Failures are described by the same schema
+    let failed = run_json(
+        dir,
+        &[
+            "commit",
+            "--type",
+            "docs",
+            "--message",
+            "nothing staged",
+            "--output",
+            "json",
+        ],
+    );
+    assert_valid("commit", &failed);
+    assert_eq!(failed["errors"][0]["code"], "no_staged_changes");
+}
@@ -215,3 +215,726 @@ fn test_tag_with_staged_changes() {
         "Please commit your staged changes before doing that",
     ));
 }
+
+#[test]
+fn test_calver_micro_counter_per_period() {
+    use chrono::Datelike;
+
+    let dir = setup_test_repo();
+    let repo = Repository::open(dir.path()).unwrap();
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let today = chrono::Utc::now().date_naive();
+    let current = format!("{}.{}.0", today.year(), today.month());
+
+    // An old-period release followed by a release in the current period
+    for tag in ["2001.12.7", current.as_str()] {
+        repo.tag(
+            tag,
+            repo.head().unwrap().peel_to_commit().unwrap().as_object(),
+            &signature,
+            "CalVer release",
+            false,
+        )
+        .unwrap();
+    }
+
+    {
+        let file_path = dir.path().join("calver.txt");
+        fs::write(&file_path, "calver change").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("calver.txt")).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "fix: calver change",
+            &tree,
+            &[&parent],
+        )
+        .unwrap();
+    }
+
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("--non-interactive")
+        .arg("tag")
+        .arg("--version-scheme")
+        .arg("calver")
+        .arg("--calver-format")
+        .arg("YYYY.MM.MICRO")
+        .arg("--not-with-v")
+        .arg("--dry-run")
+        .arg("--no-fetch");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "{}.{}.1",
+            today.year(),
+            today.month()
+        )));
+}
+
+#[test]
+fn test_calver_rejects_unknown_format_token() {
+    let dir = setup_test_repo();
+
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("--non-interactive")
+        .arg("tag")
+        .arg("--version-scheme")
+        .arg("calver")
+        .arg("--calver-format")
+        .arg("YYYY.QQ.MICRO")
+        .arg("--dry-run")
+        .arg("--no-fetch");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown CalVer token 'QQ'"));
+}
+
+fn commit_empty(repo: &Repository, message: &str) -> git2::Oid {
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let tree = head.tree().unwrap();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &[&head],
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_tag_message_template_with_release_notes() {
+    let dir = setup_test_repo();
+    let repo = Repository::open(dir.path()).unwrap();
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.tag("v1.0.0", head.as_object(), &signature, "v1.0.0", false)
+        .unwrap();
+    commit_empty(&repo, "feat(cli): add release notes");
+    commit_empty(&repo, "fix: handle missing tags");
+    commit_empty(&repo, "chore: tidy up");
+
+    fs::write(
+        dir.path().join(".committy.toml"),
+        "tag_message_template = \"\"\"\nRelease {version} (since {previous_tag})\n\n{notes}\"\"\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("tag")
+        .arg("--name")
+        .arg("v1.1.0")
+        .arg("--not-publish");
+    cmd.assert().success();
+
+    let tag = repo
+        .revparse_single("refs/tags/v1.1.0")
+        .unwrap()
+        .into_tag()
+        .expect("annotated tag");
+    let message = tag.message().unwrap();
+    assert!(
+        message.starts_with("Release 1.1.0 (since v1.0.0)"),
+        "{message}"
+    );
+    assert!(message.contains("### Features\n\n- **cli:** add release notes ("));
+    assert!(message.contains("### Bug Fixes\n\n- handle missing tags ("));
+    assert!(!message.contains("tidy up"), "{message}");
+}
+
+#[test]
+fn test_tag_signed_with_ssh_key() {
+    if std::process::Command::new("ssh-keygen")
+        .arg("-?")
+        .output()
+        .is_err()
+    {
+        eprintln!("ssh-keygen not available, skipping");
+        return;
+    }
+    let dir = setup_test_repo();
+    let key_dir = tempdir().unwrap();
+    let key_path = key_dir.path().join("id_ed25519");
+    let status = std::process::Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-C", "test", "-f"])
+        .arg(&key_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let repo = Repository::open(dir.path()).unwrap();
+    let mut config = repo.config().unwrap();
+    config.set_str("gpg.format", "ssh").unwrap();
+    config
+        .set_str("user.signingkey", key_path.to_str().unwrap())
+        .unwrap();
+    config.set_bool("tag.gpgSign", true).unwrap();
+
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("tag")
+        .arg("--name")
+        .arg("v1.0.0")
+        .arg("--not-publish");
+    cmd.assert().success();
+
+    let tag = repo
+        .revparse_single("refs/tags/v1.0.0")
+        .unwrap()
+        .into_tag()
+        .expect("annotated tag");
+    assert!(tag
+        .message()
+        .unwrap()
+        .contains("-----BEGIN SSH SIGNATURE-----"));
+
+    // git itself accepts the signature
+    let public_key = fs::read_to_string(key_path.with_extension("pub")).unwrap();
+    let allowed_signers = key_dir.path().join("allowed_signers");
+    fs::write(&allowed_signers, format!("test@example.com {public_key}")).unwrap();
+    let verify = std::process::Command::new("git")
+        .current_dir(dir.path())
+        .arg("-c")
+        .arg(format!(
+            "gpg.ssh.allowedSignersFile={}",
+            allowed_signers.display()
+        ))
+        .args(["verify-tag", "v1.0.0"])
+        .output()
+        .unwrap();
+    assert!(
+        verify.status.success(),
+        "{}",
+        String::from_utf8_lossy(&verify.stderr)
+    );
+
+    // --no-sign overrides tag.gpgSign
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("tag")
+        .arg("--name")
+        .arg("v1.0.1")
+        .arg("--not-publish")
+        .arg("--no-sign");
+    cmd.assert().success();
+    let unsigned = repo
+        .revparse_single("refs/tags/v1.0.1")
+        .unwrap()
+        .into_tag()
+        .unwrap();
+    assert!(!unsigned.message().unwrap().contains("SIGNATURE"));
+}
+
+/// main: initial (v1.0.0) -> "fix: on main"; feature: initial -> "feat: on feature" (v1.5.0).
+fn setup_divergent_repo() -> (tempfile::TempDir, Repository, git2::Oid) {
+    let dir = setup_test_repo();
+    let repo = Repository::open(dir.path()).unwrap();
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let initial = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.tag("v1.0.0", initial.as_object(), &signature, "v1.0.0", false)
+        .unwrap();
+
+    let tree = initial.tree().unwrap();
+    let feature = repo
+        .commit(
+            None,
+            &signature,
+            &signature,
+            "feat: on feature",
+            &tree,
+            &[&initial],
+        )
+        .unwrap();
+    repo.branch("feature", &repo.find_commit(feature).unwrap(), false)
+        .unwrap();
+    repo.tag(
+        "v1.5.0",
+        &repo.find_object(feature, None).unwrap(),
+        &signature,
+        "v1.5.0",
+        false,
+    )
+    .unwrap();
+    drop(tree);
+    drop(initial);
+
+    commit_empty(&repo, "fix: on main");
+    (dir, repo, feature)
+}
+
+fn dry_run_tag(dir: &std::path::Path, extra: &[&str]) -> String {
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir)
+        .arg("--non-interactive")
+        .arg("tag")
+        .arg("--release-branches")
+        .arg("master,main")
+        .arg("--dry-run")
+        .arg("--no-fetch")
+        .args(extra);
+    let output = cmd.output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+#[test]
+fn test_tags_on_unmerged_branches_are_ignored() {
+    let (dir, _repo, _feature) = setup_divergent_repo();
+    assert_eq!(dry_run_tag(dir.path(), &[]), "v1.0.1");
+    // --all-tags restores the global behavior
+    assert_eq!(dry_run_tag(dir.path(), &["--all-tags"]), "v1.5.1");
+}
+
+#[test]
+fn test_tags_on_merged_branches_are_considered() {
+    let (dir, repo, feature) = setup_divergent_repo();
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let main_head = repo.head().unwrap().peel_to_commit().unwrap();
+    let feature_commit = repo.find_commit(feature).unwrap();
+    let tree = main_head.tree().unwrap();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "fix: merge feature",
+        &tree,
+        &[&main_head, &feature_commit],
+    )
+    .unwrap();
+
+    assert_eq!(dry_run_tag(dir.path(), &[]), "v1.5.1");
+}
+
+#[test]
+fn test_tag_format_with_package_placeholder() {
+    let dir = setup_test_repo();
+    let repo = Repository::open(dir.path()).unwrap();
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let initial = repo.head().unwrap().peel_to_commit().unwrap();
+    for tag in ["core@1.0.0", "cli@2.0.0", "v9.0.0"] {
+        repo.tag(tag, initial.as_object(), &signature, tag, false)
+            .unwrap();
+    }
+    drop(initial);
+    commit_empty(&repo, "feat: core feature");
+
+    assert_eq!(
+        dry_run_tag(
+            dir.path(),
+            &["--tag-format", "{package}@{version}", "--package", "core"]
+        ),
+        "core@1.1.0"
+    );
+    // The repository's tag format applies when no --tag-format is given
+    fs::write(
+        dir.path().join(".committy.toml"),
+        "tag_format = \"{package}@{version}\"\n",
+    )
+    .unwrap();
+    assert_eq!(dry_run_tag(dir.path(), &["--package", "cli"]), "cli@2.1.0");
+
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("--non-interactive")
+        .arg("tag")
+        .arg("--dry-run")
+        .arg("--no-fetch")
+        .arg("--tag-format")
+        .arg("{package}@{version}");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("no package name was given"));
+}
+
+#[test]
+fn test_tag_format_creates_custom_tags() {
+    let dir = setup_test_repo();
+    let repo = Repository::open(dir.path()).unwrap();
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let initial = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.tag(
+        "release-1.4.0",
+        initial.as_object(),
+        &signature,
+        "release-1.4.0",
+        false,
+    )
+    .unwrap();
+    drop(initial);
+    commit_empty(&repo, "fix: patch release");
+
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("--non-interactive")
+        .arg("tag")
+        .arg("--no-fetch")
+        .arg("--not-publish")
+        .arg("--tag-format")
+        .arg("release-{version}");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("release-1.4.1"));
+    assert!(repo.revparse_single("refs/tags/release-1.4.1").is_ok());
+}
+
+/// v1.0.0 -> "fix: on main", with v1.0.1 already taken on the given side.
+fn setup_collision_repo(remote_taken: bool) -> (tempfile::TempDir, tempfile::TempDir, Repository) {
+    let dir = setup_test_repo();
+    let remote_dir = tempdir().unwrap();
+    Repository::init_bare(remote_dir.path()).unwrap();
+    let repo = Repository::open(dir.path()).unwrap();
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let initial = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.tag("v1.0.0", initial.as_object(), &signature, "v1.0.0", false)
+        .unwrap();
+    repo.remote("origin", remote_dir.path().to_str().unwrap())
+        .unwrap();
+
+    // v1.0.1 was released from another commit that is not part of HEAD
+    let tree = initial.tree().unwrap();
+    let other = repo
+        .commit(
+            None,
+            &signature,
+            &signature,
+            "fix: elsewhere",
+            &tree,
+            &[&initial],
+        )
+        .unwrap();
+    repo.tag(
+        "v1.0.1",
+        &repo.find_object(other, None).unwrap(),
+        &signature,
+        "v1.0.1",
+        false,
+    )
+    .unwrap();
+    if remote_taken {
+        let mut remote = repo.find_remote("origin").unwrap();
+        remote.push(&["refs/tags/v1.0.1"], None).unwrap();
+        repo.tag_delete("v1.0.1").unwrap();
+    }
+    drop(tree);
+    drop(initial);
+    commit_empty(&repo, "fix: on main");
+    (dir, remote_dir, repo)
+}
+
+/// Run a real, pushed `committy tag` so that the tags of origin are checked.
+fn pushed_tag(dir: &std::path::Path, extra: &[&str]) -> assert_cmd::assert::Assert {
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir)
+        .arg("--non-interactive")
+        .arg("tag")
+        .args(extra);
+    cmd.assert()
+}
+
+#[test]
+fn test_tag_collision_aborts_by_default() {
+    let (dir, _remote, _repo) = setup_collision_repo(false);
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("--non-interactive")
+        .arg("tag")
+        .arg("--dry-run")
+        .arg("--no-fetch");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Tag v1.0.1 already exists"));
+
+    let (dir, _remote, _repo) = setup_collision_repo(true);
+    pushed_tag(dir.path(), &[])
+        .failure()
+        .stderr(predicate::str::contains("Tag v1.0.1 already exists"));
+
+    // Without a fetch the tag is only on the remote, which is still checked before tagging
+    let (dir, _remote, repo) = setup_collision_repo(true);
+    pushed_tag(dir.path(), &["--no-fetch"])
+        .code(10)
+        .stderr(predicate::str::contains("Tag v1.0.1 already exists"));
+    assert!(repo.revparse_single("refs/tags/v1.0.1").is_err());
+}
+
+#[test]
+fn test_tag_collision_skips_to_next_free_version() {
+    let (dir, _remote, _repo) = setup_collision_repo(false);
+    assert_eq!(
+        dry_run_tag(dir.path(), &["--on-tag-collision", "skip"]),
+        "v1.0.2"
+    );
+
+    let (dir, remote, _repo) = setup_collision_repo(true);
+    pushed_tag(dir.path(), &["--on-tag-collision", "skip"])
+        .success()
+        .stdout(predicate::str::contains("v1.0.2"));
+    let remote = Repository::open_bare(remote.path()).unwrap();
+    assert!(remote.revparse_single("refs/tags/v1.0.2").is_ok());
+}
+
+#[test]
+fn test_dry_run_does_not_contact_the_remote() {
+    let (dir, _remote, repo) = setup_collision_repo(false);
+    repo.remote_set_url("origin", "/nonexistent/committy-remote")
+        .unwrap();
+    repo.tag_delete("v1.0.1").unwrap();
+    assert_eq!(dry_run_tag(dir.path(), &[]), "v1.0.1");
+}
+
+#[test]
+fn test_tag_name_that_already_exists_fails_cleanly() {
+    let dir = setup_test_repo();
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .args(["tag", "--name", "v1.0.0", "--not-publish"]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .args(["tag", "--name", "v1.0.0", "--not-publish"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Tag v1.0.0 already exists"));
+}
+
+fn guarded_tag(dir: &std::path::Path, guards: &str) -> assert_cmd::assert::Assert {
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir)
+        .arg("--non-interactive")
+        .arg("tag")
+        .arg("--dry-run")
+        .arg("--no-fetch")
+        .arg("--release-guards")
+        .arg(guards);
+    cmd.assert()
+}
+
+#[test]
+fn test_release_guards_block_dirty_tree_and_lint_issues() {
+    let dir = setup_test_repo();
+    let repo = Repository::open(dir.path()).unwrap();
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    fs::write(dir.path().join("README.md"), "hello").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(std::path::Path::new("README.md")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "docs: add readme",
+        &tree,
+        &[&head],
+    )
+    .unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.tag("v1.0.0", head.as_object(), &signature, "v1.0.0", false)
+        .unwrap();
+    commit_empty(&repo, "fix: handle empty input");
+    commit_empty(&repo, "did some stuff");
+    fs::write(dir.path().join("README.md"), "changed").unwrap();
+
+    guarded_tag(dir.path(), "lint,clean")
+        .failure()
+        .stderr(predicate::str::contains("Release guards failed"))
+        .stderr(predicate::str::contains("lint: 1 commit(s) since v1.0.0"))
+        .stderr(predicate::str::contains("clean: the worktree"));
+
+    // The guards are opt-in
+    guarded_tag(dir.path(), "").success().stdout("v1.0.1\n");
+
+    fs::write(dir.path().join("README.md"), "hello").unwrap();
+    commit_empty(&repo, "fix: another one");
+    guarded_tag(dir.path(), "clean").success();
+    guarded_tag(dir.path(), "lint").failure();
+    guarded_tag(dir.path(), "unknown")
+        .failure()
+        .stderr(predicate::str::contains("Unknown release guard 'unknown'"));
+
+    // Without --release-guards, the repository's guards apply and a malformed file fails
+    let tag = || {
+        let mut cmd = Command::cargo_bin("committy").unwrap();
+        cmd.current_dir(dir.path())
+            .args(["--non-interactive", "tag", "--dry-run", "--no-fetch"]);
+        cmd.assert()
+    };
+    fs::write(
+        dir.path().join(".committy.toml"),
+        "release_guards = [\"lint\"]\n",
+    )
+    .unwrap();
+    tag()
+        .failure()
+        .stderr(predicate::str::contains("lint: 1 commit(s) since v1.0.0"));
+    fs::write(
+        dir.path().join(".committy.toml"),
+        "release_guards = \"lint\"\n",
+    )
+    .unwrap();
+    tag().code(7);
+}
+
+#[test]
+fn test_release_guards_branch_and_upstream() {
+    let dir = setup_test_repo();
+    let remote_dir = tempdir().unwrap();
+    Repository::init_bare(remote_dir.path()).unwrap();
+    let repo = Repository::open(dir.path()).unwrap();
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.tag("v1.0.0", head.as_object(), &signature, "v1.0.0", false)
+        .unwrap();
+    drop(head);
+    commit_empty(&repo, "feat: new feature");
+    repo.remote("origin", remote_dir.path().to_str().unwrap())
+        .unwrap();
+
+    guarded_tag(dir.path(), "upstream")
+        .failure()
+        .stderr(predicate::str::contains("has no upstream branch"));
+
+    let push = std::process::Command::new("git")
+        .current_dir(dir.path())
+        .args(["push", "-q", "-u", "origin", "HEAD"])
+        .status()
+        .unwrap();
+    assert!(push.success());
+    guarded_tag(dir.path(), "upstream,release-branch")
+        .success()
+        .stdout("v1.1.0\n");
+
+    commit_empty(&repo, "fix: not pushed yet");
+    guarded_tag(dir.path(), "upstream")
+        .failure()
+        .stderr(predicate::str::contains("1 commit(s) ahead of origin/"));
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("feature", &head, false).unwrap();
+    repo.set_head("refs/heads/feature").unwrap();
+    guarded_tag(dir.path(), "release-branch")
+        .failure()
+        .stderr(predicate::str::contains("feature is not a release branch"));
+}
+
+#[cfg(unix)]
+fn setup_hooks_repo(hooks: &str) -> (tempfile::TempDir, Repository) {
+    let dir = setup_test_repo();
+    let repo = Repository::open(dir.path()).unwrap();
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.tag("v1.0.0", head.as_object(), &signature, "v1.0.0", false)
+        .unwrap();
+    drop(head);
+
+    fs::write(dir.path().join(".committy.toml"), hooks).unwrap();
+    let mut index = repo.index().unwrap();
+    index
+        .add_path(std::path::Path::new(".committy.toml"))
+        .unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "feat: add release hooks",
+        &tree,
+        &[&head],
+    )
+    .unwrap();
+    drop(tree);
+    drop(head);
+    (dir, repo)
+}
+
+#[cfg(unix)]
+#[test]
+fn test_release_hooks_run_around_release_steps() {
+    let (dir, repo) = setup_hooks_repo(
+        r#"
+[hooks]
+pre-bump = ['echo "$COMMITTY_PREVIOUS_VERSION -> $COMMITTY_NEW_VERSION" > hooks.log']
+post-bump = ['echo "$COMMITTY_HOOK $COMMITTY_NEW_TAG" >> hooks.log']
+pre-tag = ['test -z "$(git tag -l "$COMMITTY_NEW_TAG")"']
+post-tag = ['git rev-parse --verify -q "refs/tags/$COMMITTY_NEW_TAG" > /dev/null']
+post-push = ['touch pushed']
+"#,
+    );
+    let remote_dir = tempdir().unwrap();
+    Repository::init_bare(remote_dir.path()).unwrap();
+    repo.remote("origin", remote_dir.path().to_str().unwrap())
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .args(["--non-interactive", "tag", "--no-fetch"]);
+    cmd.assert().success().stdout("v1.1.0\n");
+
+    // The file written by the hooks is part of the bump commit, which is tagged
+    let tagged = repo
+        .revparse_single("v1.1.0^{commit}")
+        .unwrap()
+        .peel_to_commit()
+        .unwrap();
+    assert_eq!(tagged.message().unwrap(), "chore: bump version to 1.1.0");
+    let blob = tagged
+        .tree()
+        .unwrap()
+        .get_path(std::path::Path::new("hooks.log"))
+        .unwrap()
+        .to_object(&repo)
+        .unwrap()
+        .peel_to_blob()
+        .unwrap();
+    assert_eq!(
+        std::str::from_utf8(blob.content()).unwrap(),
+        "1.0.0 -> 1.1.0\npost-bump v1.1.0\n"
+    );
+    assert!(dir.path().join("pushed").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_failing_release_hook_aborts_the_release() {
+    let (dir, repo) = setup_hooks_repo("[hooks]\npre-tag = ['exit 3']\n");
+
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .args(["--non-interactive", "tag", "--no-fetch", "--not-publish"]);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "pre-tag hook 'exit 3' failed with exit code 3",
+    ));
+    assert!(repo.revparse_single("v1.1.0").is_err());
+
+    // --no-hooks skips them
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path()).args([
+        "--non-interactive",
+        "tag",
+        "--no-fetch",
+        "--not-publish",
+        "--no-hooks",
+    ]);
+    cmd.assert().success().stdout("v1.1.0\n");
+}
@@ -480,3 +480,501 @@ fn test_chore_on_beta_increments_counter() {
         stdout
     );
 }
+
+#[test]
+fn test_prerelease_channels_keep_separate_counters() {
+    // Scenario: v1.0.0 on main, v1.1.0-beta.3 from develop, now releasing from next (rc channel)
+    // The rc line must start its own counter instead of continuing the beta one
+    let (dir, repo) = setup_repo_with_commits("next", vec!["feat: release candidate work"]);
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let initial_commit = head.parent(0).unwrap();
+    repo.tag(
+        "v1.0.0",
+        initial_commit.as_object(),
+        &signature,
+        "Release v1.0.0",
+        false,
+    )
+    .unwrap();
+    repo.tag(
+        "v1.1.0-beta.3",
+        initial_commit.as_object(),
+        &signature,
+        "Beta release v1.1.0-beta.3",
+        false,
+    )
+    .unwrap();
+
+    let run_tag = || {
+        let mut cmd = assert_cmd::Command::cargo_bin("committy").unwrap();
+        cmd.current_dir(dir.path())
+            .arg("--non-interactive")
+            .arg("tag")
+            .arg("--prerelease-channels")
+            .arg("develop=beta,next=rc,feat/*=alpha.{branch}")
+            .arg("--dry-run")
+            .arg("--no-fetch")
+            .arg("--output")
+            .arg("json");
+        String::from_utf8(cmd.output().unwrap().stdout).unwrap()
+    };
+
+    let stdout = run_tag();
+    assert!(
+        stdout.contains("\"new_tag\":\"v1.1.0-rc.0\""),
+        "Expected v1.1.0-rc.0 but got: {}",
+        stdout
+    );
+
+    // Once an rc exists, the next run on the same channel only increments its counter
+    repo.tag(
+        "v1.1.0-rc.0",
+        repo.head().unwrap().peel_to_commit().unwrap().as_object(),
+        &signature,
+        "Release candidate v1.1.0-rc.0",
+        false,
+    )
+    .unwrap();
+    let file_path = dir.path().join("rc-fix.txt");
+    fs::write(&file_path, "rc fix").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(std::path::Path::new("rc-fix.txt")).unwrap();
+    index.write().unwrap();
+    let tree_id = index.write_tree().unwrap();
+    let tree = repo.find_tree(tree_id).unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "fix: rc regression",
+        &tree,
+        &[&parent],
+    )
+    .unwrap();
+
+    let stdout = run_tag();
+    assert!(
+        stdout.contains("\"new_tag\":\"v1.1.0-rc.1\""),
+        "Expected v1.1.0-rc.1 but got: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_prerelease_channel_with_branch_placeholder() {
+    let (dir, repo) = setup_repo_with_commits("feat/login", vec!["feat: login form"]);
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let initial_commit = head.parent(0).unwrap();
+    repo.tag(
+        "v1.0.0",
+        initial_commit.as_object(),
+        &signature,
+        "Release v1.0.0",
+        false,
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("--non-interactive")
+        .arg("tag")
+        .arg("--prerelease-channels")
+        .arg("develop=beta,feat/*=alpha.{branch}")
+        .arg("--dry-run")
+        .arg("--no-fetch")
+        .arg("--output")
+        .arg("json");
+
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(
+        stdout.contains("\"new_tag\":\"v1.1.0-alpha.feat-login.0\""),
+        "Expected v1.1.0-alpha.feat-login.0 but got: {}",
+        stdout
+    );
+
+    // The same mapping from the repository's .committy.toml
+    fs::write(
+        dir.path().join(".committy.toml"),
+        "[[prerelease_channels]]\nbranch = \"feat/*\"\nchannel = \"alpha.{branch}\"\n",
+    )
+    .unwrap();
+    let stdout = String::from_utf8(run_tag_json(dir.path()).stdout).unwrap();
+    assert!(
+        stdout.contains("\"new_tag\":\"v1.1.0-alpha.feat-login.0\""),
+        "Expected v1.1.0-alpha.feat-login.0 but got: {}",
+        stdout
+    );
+
+    // A malformed mapping is an error, not silently ignored
+    fs::write(
+        dir.path().join(".committy.toml"),
+        "[[prerelease_channels]]\nbranch = \"feat/*\"\n",
+    )
+    .unwrap();
+    let output = run_tag_json(dir.path());
+    assert_eq!(output.status.code(), Some(7));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["errors"][0]["code"], "invalid_config");
+}
+
+/// Tag the first commit of the repo and compute the next tag on `main`.
+fn next_tag_on_main(commits: Vec<&str>, base_tag: &str, extra: &[&str]) -> String {
+    let (dir, repo) = setup_repo_with_commits("main", commits);
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let mut initial_commit = repo.head().unwrap().peel_to_commit().unwrap();
+    while initial_commit.parent_count() > 0 {
+        initial_commit = initial_commit.parent(0).unwrap();
+    }
+    repo.tag(
+        base_tag,
+        initial_commit.as_object(),
+        &signature,
+        base_tag,
+        false,
+    )
+    .unwrap();
+
+    let config_dir = tempdir().unwrap();
+    let mut cmd = assert_cmd::Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .env("COMMITTY_CONFIG_DIR", config_dir.path())
+        .arg("--non-interactive")
+        .arg("tag")
+        .arg("--dry-run")
+        .arg("--no-fetch")
+        .arg("--output")
+        .arg("json")
+        .args(extra);
+    let output = cmd.output().unwrap();
+    assert!(
+        output.status.success(),
+        "tag failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["data"]["new_tag"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[test]
+fn test_initial_development_demotes_bumps_below_1_0() {
+    // Without the mode, a breaking change on 0.x graduates to 1.0.0
+    assert_eq!(
+        next_tag_on_main(vec!["feat!: drop legacy api"], "v0.3.0", &[]),
+        "v1.0.0"
+    );
+    // In initial development, breaking changes bump minor and features bump patch
+    assert_eq!(
+        next_tag_on_main(
+            vec!["feat!: drop legacy api"],
+            "v0.3.0",
+            &["--initial-development"]
+        ),
+        "v0.4.0"
+    );
+    assert_eq!(
+        next_tag_on_main(vec!["feat: add api"], "v0.3.0", &["--initial-development"]),
+        "v0.3.1"
+    );
+    // Once stable, the mode has no effect
+    assert_eq!(
+        next_tag_on_main(vec!["feat: add api"], "v1.3.0", &["--initial-development"]),
+        "v1.4.0"
+    );
+}
+
+#[test]
+fn test_explicit_bump_and_release_as() {
+    assert_eq!(
+        next_tag_on_main(
+            vec!["fix: small fix"],
+            "v0.9.0",
+            &["--initial-development", "--bump", "major"]
+        ),
+        "v1.0.0"
+    );
+    assert_eq!(
+        next_tag_on_main(vec!["fix: small fix"], "v0.9.0", &["--release-as", "1.0.0"]),
+        "v1.0.0"
+    );
+    // --release-as may re-version an already tagged commit
+    assert_eq!(
+        next_tag_on_main(vec![], "v0.9.0", &["--release-as", "v1.0.0"]),
+        "v1.0.0"
+    );
+}
+
+#[test]
+fn test_release_as_must_move_forward() {
+    let (dir, repo) = setup_repo_with_commits("main", vec!["fix: small fix"]);
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.tag(
+        "v2.0.0",
+        head.parent(0).unwrap().as_object(),
+        &signature,
+        "v2.0.0",
+        false,
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("--non-interactive")
+        .arg("tag")
+        .arg("--dry-run")
+        .arg("--no-fetch")
+        .arg("--release-as")
+        .arg("1.0.0");
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("must be greater than"));
+}
+
+/// v1.2.0 on the initial commit, `release/<line>` branched from it, and v2.0.0 on main.
+fn setup_maintenance_repo(line: &str, commits: Vec<&str>) -> (tempfile::TempDir, Repository) {
+    let (dir, repo) = setup_repo_with_commits("main", vec!["feat!: new major"]);
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let initial_commit = head.parent(0).unwrap();
+    repo.tag(
+        "v1.2.0",
+        initial_commit.as_object(),
+        &signature,
+        "v1.2.0",
+        false,
+    )
+    .unwrap();
+    repo.tag("v2.0.0", head.as_object(), &signature, "v2.0.0", false)
+        .unwrap();
+
+    let branch = format!("release/{line}");
+    repo.branch(&branch, &initial_commit, false).unwrap();
+    repo.set_head(&format!("refs/heads/{branch}")).unwrap();
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+        .unwrap();
+
+    let mut parent = initial_commit;
+    for (i, message) in commits.iter().enumerate() {
+        let name = format!("backport{i}.txt");
+        fs::write(dir.path().join(&name), message).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new(&name)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let id = repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &[&parent],
+            )
+            .unwrap();
+        parent = repo.find_commit(id).unwrap();
+    }
+    drop(parent);
+    drop(head);
+    (dir, repo)
+}
+
+fn run_tag_json(dir: &std::path::Path) -> std::process::Output {
+    let mut cmd = assert_cmd::Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir)
+        .arg("--non-interactive")
+        .arg("tag")
+        .arg("--dry-run")
+        .arg("--no-fetch")
+        .arg("--output")
+        .arg("json");
+    cmd.output().unwrap()
+}
+
+#[test]
+fn test_maintenance_branch_stays_on_its_line() {
+    let (dir, _repo) = setup_maintenance_repo("1.2", vec!["fix: backport security fix"]);
+    let output = run_tag_json(dir.path());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("\"new_tag\":\"v1.2.1\""),
+        "Expected v1.2.1 but got: {stdout} {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("\"pre_release\":false"), "{stdout}");
+}
+
+#[test]
+fn test_maintenance_branch_rejects_leaving_the_line() {
+    let (dir, _repo) = setup_maintenance_repo("1.2", vec!["feat: backport feature"]);
+    let output = run_tag_json(dir.path());
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("v1.3.0 would leave maintenance line 1.2.x"),
+        "{stderr}"
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("invalid_input"), "{stdout}");
+
+    // A major line accepts minor releases
+    let (dir, _repo) = setup_maintenance_repo("1.x", vec!["feat: backport feature"]);
+    let stdout = String::from_utf8(run_tag_json(dir.path()).stdout).unwrap();
+    assert!(stdout.contains("\"new_tag\":\"v1.3.0\""), "{stdout}");
+}
+
+#[test]
+fn test_bump_prerelease_level() {
+    assert_eq!(
+        next_tag_on_main(vec!["fix: small fix"], "v1.0.0", &["--bump", "prerelease"]),
+        "v1.0.1-beta.0"
+    );
+    assert_eq!(
+        next_tag_on_main(vec!["fix: small fix"], "v1.0.0", &["--bump", "minor"]),
+        "v1.1.0"
+    );
+}
+
+#[test]
+fn test_skip_markers_and_ignored_paths_exclude_commits() {
+    // A skipped feature does not raise the bump level
+    assert_eq!(
+        next_tag_on_main(
+            vec!["feat: experimental flag [skip release]", "fix: typo"],
+            "v1.0.0",
+            &[]
+        ),
+        "v1.0.1"
+    );
+    // Each commit in these repositories touches its own fileN.txt
+    assert_eq!(
+        next_tag_on_main(
+            vec!["feat: only touches ignored file", "fix: real fix"],
+            "v1.0.0",
+            &["--release-ignore-paths", "file0.txt"]
+        ),
+        "v1.0.1"
+    );
+    assert_eq!(
+        next_tag_on_main(
+            vec!["chore: release prep <no-release>"],
+            "v1.0.0",
+            &["--release-skip-markers", "<no-release>"]
+        ),
+        ""
+    );
+
+    // The same settings from the repository's .committy.toml
+    let (dir, repo) = setup_repo_with_commits(
+        "main",
+        vec![
+            "feat: only touches ignored file",
+            "fix: real fix <no-release>",
+        ],
+    );
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let initial = head.parent(0).unwrap().parent(0).unwrap();
+    repo.tag("v1.0.0", initial.as_object(), &signature, "v1.0.0", false)
+        .unwrap();
+    fs::write(
+        dir.path().join(".committy.toml"),
+        "release_skip_markers = [\"<no-release>\"]\nrelease_ignore_paths = [\"file0.txt\"]\n",
+    )
+    .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&run_tag_json(dir.path()).stdout).unwrap();
+    assert_eq!(json["data"]["new_tag"], serde_json::Value::Null);
+
+    // A malformed file is reported instead of falling back to the defaults
+    fs::write(
+        dir.path().join(".committy.toml"),
+        "release_ignore_paths = \"file0.txt\"\n",
+    )
+    .unwrap();
+    let output = run_tag_json(dir.path());
+    assert_eq!(output.status.code(), Some(7));
+}
+
+#[test]
+fn test_no_releasable_commits_means_no_release() {
+    // Without releasable commits the --default-bump is not applied
+    assert_eq!(
+        next_tag_on_main(
+            vec!["docs: update guide", "update readme"],
+            "v1.0.0",
+            &["--release-ignore-paths", "*.txt", "--default-bump", "patch"]
+        ),
+        ""
+    );
+    // An explicit bump still releases
+    assert_eq!(
+        next_tag_on_main(
+            vec!["docs: update guide"],
+            "v1.0.0",
+            &["--release-ignore-paths", "*.txt", "--bump", "patch"]
+        ),
+        "v1.0.1"
+    );
+}
+
+#[test]
+fn test_commit_type_bump_levels_from_repo_config() {
+    let (dir, repo) = setup_repo_with_commits("main", vec!["docs: update guide"]);
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    let mut initial_commit = repo.head().unwrap().peel_to_commit().unwrap();
+    while initial_commit.parent_count() > 0 {
+        initial_commit = initial_commit.parent(0).unwrap();
+    }
+    repo.tag(
+        "v1.0.0",
+        initial_commit.as_object(),
+        &signature,
+        "v1.0.0",
+        false,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join(".committy.toml"),
+        "[[commit_types]]\nname = \"docs\"\nbump = \"none\"\n\n\
+         [[commit_types]]\nname = \"deps\"\nbump = \"minor\"\n",
+    )
+    .unwrap();
+
+    let config_dir = tempdir().unwrap();
+    let next_tag = || {
+        let mut cmd = assert_cmd::Command::cargo_bin("committy").unwrap();
+        cmd.current_dir(dir.path())
+            .env("COMMITTY_CONFIG_DIR", config_dir.path())
+            .args(["--non-interactive", "tag", "--dry-run", "--no-fetch"]);
+        let output = cmd.output().unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    };
+
+    // docs commits no longer release
+    assert_eq!(next_tag(), "");
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let tree = head.tree().unwrap();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "deps: update serde",
+        &tree,
+        &[&head],
+    )
+    .unwrap();
+    assert_eq!(next_tag(), "v1.1.0");
+}
@@ -143,3 +143,24 @@ fn test_linter_with_empty_repo() {
 
     assert!(issues.is_empty());
 }
+
+#[test]
+fn test_linter_accepts_commit_types_from_repo_config() {
+    common::setup_test_env();
+    let (temp_dir, repo) = setup_test_repo();
+    std::fs::write(
+        temp_dir.path().join(".committy.toml"),
+        "[[commit_types]]\nname = \"deps\"\ndescription = \"Dependency updates\"\n",
+    )
+    .unwrap();
+
+    create_commit(&repo, "deps: bump serde to 1.0.200");
+    create_commit(&repo, "wip: not a registered type");
+
+    let linter = CommitLinter::new(temp_dir.path().to_str().unwrap()).unwrap();
+    let issues = linter.check_commits_since_last_tag().unwrap();
+
+    assert_eq!(issues.len(), 1);
+    assert!(issues[0].message.contains("wip"));
+    assert!(issues[0].issue.contains("deps"));
+}
@@ -0,0 +1,164 @@
+use assert_cmd::Command;
+use git2::{Repository, Signature};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn commit_file(repo: &Repository, dir: &Path, name: &str, message: &str) -> git2::Oid {
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    fs::write(dir.join(name), message).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new(name)).unwrap();
+    index.write().unwrap();
+    let tree_id = index.write_tree().unwrap();
+    let tree = repo.find_tree(tree_id).unwrap();
+    let parents = match repo.head() {
+        Ok(head) => vec![head.peel_to_commit().unwrap()],
+        Err(_) => vec![],
+    };
+    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parent_refs,
+    )
+    .unwrap()
+}
+
+fn setup_tagged_repo() -> (tempfile::TempDir, Repository) {
+    let dir = tempdir().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Test User").unwrap();
+    config.set_str("user.email", "test@example.com").unwrap();
+
+    fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"1.0.0\"\n",
+    )
+    .unwrap();
+    commit_file(&repo, dir.path(), "README.md", "chore: initial commit");
+    {
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("Cargo.toml")).unwrap();
+        index.write().unwrap();
+    }
+    commit_file(&repo, dir.path(), "lib.txt", "chore: add manifest");
+    repo.branch(
+        "main",
+        &repo.head().unwrap().peel_to_commit().unwrap(),
+        false,
+    )
+    .unwrap();
+    repo.set_head("refs/heads/main").unwrap();
+
+    let signature = Signature::now("Test User", "test@example.com").unwrap();
+    repo.tag(
+        "v1.0.0",
+        repo.head().unwrap().peel_to_commit().unwrap().as_object(),
+        &signature,
+        "Release v1.0.0",
+        false,
+    )
+    .unwrap();
+
+    (dir, repo)
+}
+
+fn run_version(dir: &Path, extra: &[&str]) -> String {
+    let mut cmd = Command::cargo_bin("committy").unwrap();
+    cmd.current_dir(dir)
+        .arg("--non-interactive")
+        .arg("version")
+        .arg("--no-fetch")
+        .args(extra);
+    let output = cmd.output().unwrap();
+    assert!(
+        output.status.success(),
+        "version failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+#[test]
+fn test_version_on_exact_tag() {
+    let (dir, _repo) = setup_tagged_repo();
+    assert_eq!(run_version(dir.path(), &[]), "1.0.0");
+}
+
+#[test]
+fn test_dev_version_after_tag() {
+    let (dir, repo) = setup_tagged_repo();
+    commit_file(&repo, dir.path(), "a.txt", "feat: add a");
+    let head = commit_file(&repo, dir.path(), "b.txt", "fix: fix b");
+    let short = repo
+        .find_object(head, None)
+        .unwrap()
+        .short_id()
+        .unwrap()
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    assert_eq!(
+        run_version(dir.path(), &[]),
+        format!("1.1.0-dev.2+g{short}")
+    );
+
+    let stdout = run_version(dir.path(), &["--output", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["ok"], true);
+    assert_eq!(json["data"]["base_tag"], "v1.0.0");
+    assert_eq!(json["data"]["commits_since_tag"], 2);
+    assert_eq!(json["data"]["sha"], short);
+    assert_eq!(json["data"]["dirty"], false);
+
+    // Custom format
+    assert_eq!(
+        run_version(
+            dir.path(),
+            &["--dev-format", "{version}-snapshot.{commits}"]
+        ),
+        "1.1.0-snapshot.2"
+    );
+}
+
+#[test]
+fn test_dev_version_dirty_marker() {
+    let (dir, _repo) = setup_tagged_repo();
+    fs::write(dir.path().join("lib.txt"), "uncommitted edit").unwrap();
+
+    // Without --dirty, a dirty tree on the tag still reports the tag version
+    assert_eq!(run_version(dir.path(), &[]), "1.0.0");
+
+    let version = run_version(dir.path(), &["--dirty"]);
+    assert!(
+        version.starts_with("1.0.1-dev.0+g") && version.ends_with(".dirty"),
+        "unexpected version: {version}"
+    );
+}
+
+#[test]
+fn test_dev_version_bump_files_without_tagging() {
+    let (dir, repo) = setup_tagged_repo();
+    commit_file(&repo, dir.path(), "a.txt", "fix: patch something");
+    let head_before = repo.head().unwrap().peel_to_commit().unwrap().id();
+
+    let version = run_version(
+        dir.path(),
+        &["--bump-files", "--dev-format", "{version}-dev.{commits}"],
+    );
+    assert_eq!(version, "1.0.1-dev.1");
+
+    let cargo = fs::read_to_string(dir.path().join("Cargo.toml")).unwrap();
+    assert!(cargo.contains("version = \"1.0.1-dev.1\""), "{cargo}");
+
+    // No commit and no tag were created
+    let head_after = repo.head().unwrap().peel_to_commit().unwrap().id();
+    assert_eq!(head_before, head_after);
+    assert_eq!(repo.tag_names(None).unwrap().len(), 1);
+}
@@ -1,5 +1,6 @@
 mod common;
 
+use committy::error::CliError;
 use committy::git::{TagGenerator, TagGeneratorOptions};
 use committy::version::VersionManager;
 use std::fs;
@@ -452,3 +453,106 @@ version = "1.0.0"
         "Tag does not point to version bump commit"
     );
 }
+
+#[test]
+fn test_plan_reports_bump_and_accepts_overrides() {
+    let dir = tempdir().expect("Failed to create temp directory");
+    let repo = git2::Repository::init(dir.path()).expect("Failed to init repo");
+    let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+    let tree_id = repo.index().unwrap().write_tree().unwrap();
+    let tree = repo.find_tree(tree_id).unwrap();
+    let initial = repo
+        .commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+        .unwrap();
+    let initial = repo.find_commit(initial).unwrap();
+    repo.branch("main", &initial, false).unwrap();
+    repo.set_head("refs/heads/main").unwrap();
+    repo.tag("v1.0.0", initial.as_object(), &sig, "v1.0.0", false)
+        .unwrap();
+    repo.commit(
+        Some("HEAD"),
+        &sig,
+        &sig,
+        "fix: small fix",
+        &tree,
+        &[&initial],
+    )
+    .unwrap();
+
+    let options = TagGeneratorOptions::from_iter_safe(&[
+        "test",
+        "--source",
+        &dir.path().to_string_lossy(),
+        "--dry-run",
+        "--no-fetch",
+    ])
+    .unwrap();
+    let mut generator = TagGenerator::new(options, false);
+
+    let plan = generator.plan().unwrap().expect("a new tag");
+    assert_eq!(plan.previous_tag, "v1.0.0");
+    assert_eq!(plan.bump, "patch");
+    assert_eq!(plan.new_tag, "v1.0.1");
+    assert!(!plan.pre_release);
+
+    generator.set_bump("major");
+    let plan = generator.plan().unwrap().unwrap();
+    assert_eq!(
+        (plan.bump.as_str(), plan.new_tag.as_str()),
+        ("major", "v2.0.0")
+    );
+
+    generator.set_bump("prerelease");
+    let plan = generator.plan().unwrap().unwrap();
+    assert_eq!(plan.bump, "prerelease");
+    assert_eq!(plan.new_tag, "v1.0.1-beta.0");
+
+    generator.set_release_as("v1.5.0").unwrap();
+    let plan = generator.plan().unwrap().unwrap();
+    assert_eq!(plan.new_tag, "v1.5.0");
+    assert!(!plan.pre_release);
+
+    generator.set_bump("prerelease");
+    generator.set_bump("minor");
+    let plan = generator.plan().unwrap().unwrap();
+    assert_eq!(plan.new_tag, "v1.1.0");
+    assert!(generator.set_release_as("not-a-version").is_err());
+
+    // A custom version below the current tag is rejected by the plan, not by the parse,
+    // and the next choice plans normally
+    generator.set_release_as("0.9.0").unwrap();
+    let error = generator.plan().unwrap_err();
+    assert!(matches!(error, CliError::SemVerError(_)), "{error}");
+    assert!(
+        error.to_string().contains("must be greater than"),
+        "{error}"
+    );
+    generator.set_bump("patch");
+    assert_eq!(generator.plan().unwrap().unwrap().new_tag, "v1.0.1");
+}
+
+#[test]
+fn test_version_manager_resolves_files_against_root() {
+    let temp_dir = setup_version_files();
+    let cwd = std::env::current_dir().unwrap();
+
+    let mut version_manager = VersionManager::with_root(temp_dir.path());
+    version_manager
+        .register_common_files()
+        .expect("Failed to register common files");
+    let updated_files = version_manager
+        .update_all_versions("2.1.0")
+        .expect("Failed to update versions");
+
+    // Paths stay relative to the root and the working directory is left alone
+    assert_eq!(
+        updated_files,
+        vec![
+            std::path::PathBuf::from("Cargo.toml"),
+            std::path::PathBuf::from("package.json")
+        ]
+    );
+    assert_eq!(std::env::current_dir().unwrap(), cwd);
+    let cargo_contents = fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+    assert!(cargo_contents.contains("version = \"2.1.0\""));
+}